@@ -1,6 +1,6 @@
 use capybar::{
     root::Root,
-    util::Color,
+    util::{Color, UpdateRate},
     widgets::{
         battery::{Battery, BatterySettings},
         clock::{Clock, ClockSettings},
@@ -10,7 +10,6 @@ use capybar::{
         Margin, Style, WidgetData, WidgetNew,
     },
 };
-use wayland_client::{globals::registry_queue_init, Connection};
 
 struct Palete {
     background: Color,
@@ -25,20 +24,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         font: Color::from_hex(0xf5e0dcff),
     };
 
-    let conn = Connection::connect_to_env()?;
-    let (globals, mut event_queue) = registry_queue_init(&conn)?;
-
     let mut bar = Bar::new(
         None,
         BarSettings {
-            default_data: WidgetData {
-                width: 1920,
-                ..WidgetData::default()
-            },
+            default_data: WidgetData::with_width(1920),
             padding: (10, 10, 10),
 
             style: Style {
-                background: Some(catpuccin_mocha.background),
+                background: Some(catpuccin_mocha.background.into()),
                 border: Some((1, catpuccin_mocha.border)),
 
                 ..Style::default()
@@ -52,16 +45,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     bar.create_widget_left(
         CPU::new,
         CPUSettings {
-            update_rate: 1000,
+            update_rate: UpdateRate::from_secs(1),
             text_settings: TextSettings {
                 font_color: catpuccin_mocha.font,
                 size: 25.0,
 
                 ..TextSettings::default()
             },
-            default_data: WidgetData {
-                ..WidgetData::default()
-            },
+            default_data: WidgetData::default(),
             style: Style {
                 margin: Margin {
                     left: 10,
@@ -96,9 +87,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                 ..TextSettings::default()
             },
-            default_data: WidgetData {
-                ..WidgetData::default()
-            },
+            default_data: WidgetData::default(),
             style: Style {
                 margin: Margin {
                     left: 0,
@@ -112,7 +101,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         },
     )?;
 
-    let mut capybar = Root::new(&globals, &mut event_queue, Some(bar))?;
+    let (mut capybar, mut event_queue) = Root::connect(Some(bar))?;
 
     // Fonts can be replaces by your liking. The first font added will be used for normal text, the
     // second for emoji
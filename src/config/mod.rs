@@ -2,31 +2,153 @@ pub mod util;
 pub mod widgets;
 
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::path::PathBuf;
+use thiserror::Error;
 
+use crate::util::{with_palette, Palette};
 use util::font::PreloadedFonts;
 use widgets::bar::Bar;
 
-#[derive(Deserialize, Debug)]
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// Config could not be deserialized. `key` is the path to the offending value, e.g.
+    /// `bar.left[0].widget`
+    #[error("Invalid value of `{key}`: {error}")]
+    Parse { key: String, error: anyhow::Error },
+
+    /// Config was deserialized, but breaks constraints checked by [Config::validate]
+    #[error(
+        "Config is invalid:\n{}",
+        .0.iter().map(|problem| format!("  - {problem}")).collect::<Vec<_>>().join("\n")
+    )]
+    Invalid(Vec<String>),
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Config {
     pub preloaded_fonts: PreloadedFonts,
 
+    /// Named colors, other colors of the config can refer to them by name
+    #[serde(default)]
+    pub palette: Palette,
+
     pub bar: Bar,
 }
 
+/// Palette is read before the rest of the config, so colors can be resolved while the config is
+/// deserialized
+#[derive(Deserialize)]
+struct PaletteSection {
+    #[serde(default)]
+    palette: Palette,
+}
+
 impl Config {
     pub const fn default() -> Self {
         Self {
             preloaded_fonts: PreloadedFonts::default(),
+            palette: Palette::new(),
             bar: Bar::default(),
         }
     }
 
     pub fn parse_toml(file: PathBuf) -> Result<Self> {
         let content = std::fs::read_to_string(file)?;
-        let t: Config = toml::from_str(&content)?;
+        let palette = Config::deserialize_palette(toml::Deserializer::new(&content))?;
+        let t = Config::deserialize_tracked(&palette, toml::Deserializer::new(&content))?;
 
         Ok(t)
     }
+
+    #[cfg(feature = "serde_json")]
+    pub fn parse_json(file: PathBuf) -> Result<Self> {
+        let content = std::fs::read_to_string(file)?;
+        let palette =
+            Config::deserialize_palette(&mut serde_json::Deserializer::from_str(&content))?;
+        let t = Config::deserialize_tracked(
+            &palette,
+            &mut serde_json::Deserializer::from_str(&content),
+        )?;
+
+        Ok(t)
+    }
+
+    #[cfg(feature = "serde_yaml")]
+    pub fn parse_yaml(file: PathBuf) -> Result<Self> {
+        let content = std::fs::read_to_string(file)?;
+        let palette = Config::deserialize_palette(serde_yaml::Deserializer::from_str(&content))?;
+        let t =
+            Config::deserialize_tracked(&palette, serde_yaml::Deserializer::from_str(&content))?;
+
+        Ok(t)
+    }
+
+    /// Parse a config choosing the format by extension of the file: `json`, `yaml` or `yml` if
+    /// the corresponding feature is enabled, TOML otherwise.
+    pub fn parse_file(file: PathBuf) -> Result<Self> {
+        match file.extension().and_then(|e| e.to_str()) {
+            #[cfg(feature = "serde_json")]
+            Some("json") => Config::parse_json(file),
+            #[cfg(feature = "serde_yaml")]
+            Some("yaml" | "yml") => Config::parse_yaml(file),
+            _ => Config::parse_toml(file),
+        }
+    }
+
+    /// Deserialize only the palette of a config, ignoring everything else
+    fn deserialize_palette<'de, D>(deserializer: D) -> Result<Palette, ConfigError>
+    where
+        D: Deserializer<'de>,
+        D::Error: Send + Sync + 'static,
+    {
+        serde_path_to_error::deserialize(deserializer)
+            .map(|section: PaletteSection| section.palette)
+            .map_err(|e| ConfigError::Parse {
+                key: e.path().to_string(),
+                error: e.into_inner().into(),
+            })
+    }
+
+    /// Deserialize a config keeping track of the key that failed to deserialize. Colors can be
+    /// names from `palette`
+    fn deserialize_tracked<'de, D>(palette: &Palette, deserializer: D) -> Result<Self, ConfigError>
+    where
+        D: Deserializer<'de>,
+        D::Error: Send + Sync + 'static,
+    {
+        with_palette(palette, || serde_path_to_error::deserialize(deserializer)).map_err(|e| {
+            ConfigError::Parse {
+                key: e.path().to_string(),
+                error: e.into_inner().into(),
+            }
+        })
+    }
+
+    /// Check constraints that are not enforced by types of the config, e.g. that every used font
+    /// was preloaded and update rates of services are positive. All problems are reported at
+    /// once.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let fonts = self.preloaded_fonts.list.len();
+        let mut problems = Vec::new();
+
+        let sides = [
+            ("left", &self.bar.left),
+            ("center", &self.bar.center),
+            ("right", &self.bar.right),
+        ];
+        for (side, widgets) in sides {
+            for (i, widget) in widgets.iter().enumerate() {
+                for problem in widget.validate(fonts) {
+                    problems.push(format!("bar.{side}[{i}]: {problem}"));
+                }
+            }
+        }
+
+        match problems.is_empty() {
+            true => Ok(()),
+            false => Err(ConfigError::Invalid(problems)),
+        }
+    }
 }
@@ -1,8 +1,18 @@
-use serde::{de::Visitor, Deserialize};
+use std::path::PathBuf;
 
-use crate::util::{fonts, Color};
+use serde::{
+    de::{value::MapAccessDeserializer, MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Serialize,
+};
 
-#[derive(Default, Deserialize, Debug)]
+use crate::util::{
+    fonts::{self, FontsError, FontsMap},
+    Color,
+};
+
+#[derive(Default, Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PreloadedFonts {
     pub list: Vec<Font>,
 }
@@ -11,11 +21,52 @@ impl PreloadedFonts {
     pub const fn default() -> Self {
         Self { list: Vec::new() }
     }
+
+    /// Add listed fonts to `fonts` in order
+    pub fn load(&self, fonts: &FontsMap) -> Result<(), FontsError> {
+        for font in &self.list {
+            match font {
+                Font::Name(name) => fonts.add_font_by_name(name)?,
+                Font::Path { path } => fonts.add_font_by_path(path)?,
+            };
+        }
+
+        Ok(())
+    }
 }
 
+/// Font preloaded either by name, found using fontconfig, or from a file. Fonts get ids in the
+/// order they are listed. Deserialized from a plain string or a `{ path = "..." }` table.
+///
+/// Fonts are only looked up while the config is parsed, they are loaded into
+/// [Environment::fonts](crate::root::Environment::fonts) once the config is applied.
 #[derive(Debug, Clone)]
-pub struct Font {
-    pub name: String,
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema), serde(untagged))]
+pub enum Font {
+    Name(String),
+    Path { path: PathBuf },
+}
+
+impl Serialize for Font {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Font::Name(name) => serializer.serialize_str(name),
+            Font::Path { path } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("path", path)?;
+                map.end()
+            }
+        }
+    }
+}
+
+/// Font file in config
+#[derive(Deserialize)]
+struct FontPath {
+    path: PathBuf,
 }
 
 impl<'de> Deserialize<'de> for Font {
@@ -29,22 +80,34 @@ impl<'de> Deserialize<'de> for Font {
             type Value = Font;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("Expected font name that can be found using fontconfig")
+                formatter.write_str(
+                    "Expected font name that can be found using fontconfig or a table with a path \
+                    to a font file",
+                )
             }
 
             fn visit_str<E>(self, name: &str) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
             {
-                match fonts::add_font_by_name(name) {
-                    Ok(_) => Ok(Font {
-                        name: name.to_string(),
-                    }),
+                match fonts::find_by_name(name) {
+                    Ok(_) => Ok(Font::Name(name.to_string())),
                     Err(e) => Err(E::custom(e.to_string())),
                 }
             }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let FontPath { path } = FontPath::deserialize(MapAccessDeserializer::new(map))?;
+                match std::fs::metadata(&path) {
+                    Ok(_) => Ok(Font::Path { path }),
+                    Err(e) => Err(serde::de::Error::custom(e.to_string())),
+                }
+            }
         }
-        deserializer.deserialize_str(FontVisitor)
+        deserializer.deserialize_any(FontVisitor)
     }
 }
 
@@ -59,9 +122,7 @@ pub struct FontStyle {
 
 impl Font {
     pub const fn default() -> Self {
-        Self {
-            name: String::new(),
-        }
+        Self::Name(String::new())
     }
 }
 
@@ -1,17 +1,32 @@
 use std::{fmt::Display, path::PathBuf};
 
 use anyhow::Result;
-use capybar::{config::Config, root::Root};
-use clap::{Args, Parser, ValueEnum};
+use capybar::{config::Config, root::Root, util::ipc};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::env::var;
 use thiserror::Error;
-use wayland_client::{globals::registry_queue_init, Connection};
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
     #[command(flatten)]
     args: Arguments,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// Send a command to the running bar, e.g. `reload` or `quit`
+    Ctl {
+        /// Socket of the bar. Defaults to `$XDG_RUNTIME_DIR/capybar.sock`
+        #[arg(long, value_name = "FILE")]
+        socket: Option<PathBuf>,
+
+        #[arg(required = true, num_args = 1..)]
+        command: Vec<String>,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -23,17 +38,43 @@ struct Arguments {
     #[arg(long, value_name = "FILE")]
     /// Directory where the config is located
     cfg_path: Option<PathBuf>,
+
+    /// Print JSON schema of the config and exit
+    #[cfg(feature = "schema")]
+    #[arg(long)]
+    dump_schema: bool,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum ConfigTypes {
     Toml,
+    #[cfg(feature = "serde_json")]
+    Json,
+    #[cfg(feature = "serde_yaml")]
+    Yaml,
+}
+
+impl ConfigTypes {
+    /// Extensions of config files of the type, the first one is preferred
+    fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            ConfigTypes::Toml => &["toml"],
+            #[cfg(feature = "serde_json")]
+            ConfigTypes::Json => &["json"],
+            #[cfg(feature = "serde_yaml")]
+            ConfigTypes::Yaml => &["yaml", "yml"],
+        }
+    }
 }
 
 impl Display for ConfigTypes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConfigTypes::Toml => write!(f, "toml"),
+            #[cfg(feature = "serde_json")]
+            ConfigTypes::Json => write!(f, "json"),
+            #[cfg(feature = "serde_yaml")]
+            ConfigTypes::Yaml => write!(f, "yaml"),
         }
     }
 }
@@ -47,11 +88,36 @@ enum Errors {
         place it config at `~/.config/capybar/config.<TYPE>"
     )]
     ConfigNotExist,
+    #[error("Socket path is unknown: `XDG_RUNTIME_DIR` is not set, pass `--socket <FILE>`")]
+    NoSocket,
+}
+
+fn ctl(socket: Option<PathBuf>, command: &[String]) -> Result<()> {
+    let socket = socket.or_else(ipc::socket_path).ok_or(Errors::NoSocket)?;
+    let command: ipc::Command = command.join(" ").parse()?;
+
+    let output = ipc::send(&socket, &command)?;
+    if !output.is_empty() {
+        println!("{output}");
+    }
+
+    Ok(())
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let Some(Commands::Ctl { socket, command }) = cli.command {
+        return ctl(socket, &command);
+    }
+
+    #[cfg(feature = "schema")]
+    if cli.args.dump_schema {
+        let schema = schemars::schema_for!(Config);
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
     let mut cfg_path;
     match cli.args.cfg_path {
         None => {
@@ -68,7 +134,12 @@ fn main() -> Result<()> {
 
     if cfg_path.is_dir() {
         cfg_path.push("capybar");
-        let file_name = "config.".to_string() + &cli.args.cfg_type.to_string();
+        let extensions = cli.args.cfg_type.extensions();
+        let file_name = extensions
+            .iter()
+            .map(|extension| format!("config.{extension}"))
+            .find(|file_name| cfg_path.join(file_name).exists())
+            .unwrap_or_else(|| format!("config.{}", extensions[0]));
         cfg_path.push(file_name);
     }
 
@@ -77,14 +148,23 @@ fn main() -> Result<()> {
     }
 
     let config = match cli.args.cfg_type {
-        ConfigTypes::Toml => Config::parse_toml(cfg_path)?,
+        ConfigTypes::Toml => Config::parse_toml(cfg_path.clone())?,
+        #[cfg(feature = "serde_json")]
+        ConfigTypes::Json => Config::parse_json(cfg_path.clone())?,
+        #[cfg(feature = "serde_yaml")]
+        ConfigTypes::Yaml => Config::parse_yaml(cfg_path.clone())?,
     };
+    config.validate()?;
 
-    let conn = Connection::connect_to_env()?;
-    let (globals, mut event_queue) = registry_queue_init(&conn)?;
-
-    let mut capybar = Root::new(&globals, &mut event_queue, None)?;
+    let (mut capybar, mut event_queue) = Root::connect(None)?;
     capybar.apply_config(config)?;
+    capybar.reload_on_sighup(cfg_path)?;
+    capybar.exit_on_signals()?;
+    if let Some(socket) = ipc::socket_path() {
+        if let Err(e) = capybar.listen_ipc(&socket) {
+            println!("Commands are not accepted: {e}");
+        }
+    }
 
     capybar.run(&mut event_queue)?;
 
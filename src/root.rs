@@ -1,11 +1,14 @@
 use std::{
-    cell::RefCell,
+    any::Any,
+    cell::{Cell, RefCell},
     cmp::{max, min},
     collections::HashMap,
+    io::{self, ErrorKind},
     num::NonZeroU32,
+    os::fd::{AsRawFd, BorrowedFd},
+    path::{Path, PathBuf},
     rc::Rc,
-    thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::{anyhow, Result};
@@ -17,7 +20,7 @@ use smithay_client_toolkit::{
     registry::{ProvidesRegistryState, RegistryState},
     registry_handlers,
     seat::{
-        keyboard::{KeyEvent, KeyboardHandler, Keysym, Modifiers},
+        keyboard::{KeyEvent, KeyboardHandler, Keymap, Keysym, Modifiers},
         pointer::{PointerEvent, PointerEventKind, PointerHandler},
         Capability, SeatHandler, SeatState,
     },
@@ -32,30 +35,178 @@ use smithay_client_toolkit::{
 };
 use thiserror::Error;
 use wayland_client::{
-    globals::GlobalList,
+    backend::WaylandError,
+    globals::{registry_queue_init, GlobalList},
     protocol::{wl_keyboard, wl_output, wl_pointer, wl_seat, wl_surface},
-    Connection, EventQueue, QueueHandle,
+    Connection, Dispatch, EventQueue, QueueHandle, WEnum,
+};
+use wayland_protocols_wlr::output_power_management::v1::client::{
+    zwlr_output_power_manager_v1::ZwlrOutputPowerManagerV1,
+    zwlr_output_power_v1::{self, ZwlrOutputPowerV1},
 };
 
 use crate::{
-    config::Config,
-    services::{Service, ServiceError, ServiceNew},
+    config::{self, Config},
+    services::{
+        clients::{wayland::keyboard::layout_names, KeyboardLayouts},
+        Service, ServiceError, ServiceNew,
+    },
     util::{
-        fonts::{self, FontsError},
-        signals::{Signal, SignalNames},
-        Drawer,
+        drawer::{Drawer, DrawerError},
+        fonts::{FontsError, FontsMap},
+        ipc,
+        resize::ResizeDebounce,
+        schedule, sighup,
+        signals::{ConnectionId, Signal, SignalNames},
+        terminate,
+        visibility::{self, BarState, Visibility},
     },
     widgets::{
-        containers::{bar::Bar, Container},
-        Widget, WidgetNew,
+        containers::{
+            bar::{Bar, BarAnchor, BarLayer, BarSettings, KeyboardMode},
+            Container,
+        },
+        draw_with_opacity, next_update_of,
+        text::Text,
+        ScrollEvent, Widget, WidgetNew,
     },
 };
 
+/// Distance between a bar and it's tooltips in pixels
+const TOOLTIP_GAP: i32 = 4;
+
+/// How long a bar has to stay narrower or lower than it's surface before the surface shrinks
+const RESIZE_DELAY: Duration = Duration::from_secs(1);
+
 /// Structure containing things all the widgets in capybar needs access to
 pub struct Environment {
     pub config: Config,
     pub drawer: RefCell<Drawer>,
-    pub signals: RefCell<HashMap<SignalNames, Signal>>,
+
+    /// Signals are shared between environments of bars on all outputs, so a value emitted by a
+    /// service reaches widgets of every bar.
+    pub signals: Rc<RefCell<HashMap<SignalNames, Signal>>>,
+
+    /// Fonts widgets are drawn with, [TextSettings::fontid](crate::widgets::text::TextSettings::fontid)
+    /// is an index in it. Shared between environments of bars on all outputs.
+    pub fonts: Rc<FontsMap>,
+
+    /// Whether the bar is shown on it's output and the output is powered on. Services are not run
+    /// and the bar is not redrawn while it is hidden, widgets may also check it to skip expensive
+    /// work.
+    pub visible: Cell<bool>,
+}
+
+impl Environment {
+    /// Environment without a wayland connection, drawing with a [Drawer::headless] of provided
+    /// size. Lets widgets be bound, prepared and drawn offscreen, e.g. in tests.
+    pub fn headless(width: i32, height: i32) -> Self {
+        Environment {
+            config: Config::default(),
+            drawer: RefCell::new(Drawer::headless(width, height)),
+            signals: Rc::new(RefCell::new(HashMap::new())),
+            fonts: Rc::new(FontsMap::new()),
+            visible: Cell::new(true),
+        }
+    }
+
+    /// Connect one callback to several signals at once. Missing signals are created, so the
+    /// callback starts receiving values as soon as anything emits them. Callback gets the name
+    /// of the signal that emitted a value alongside the value itself.
+    ///
+    /// Returns ids of the connections in the same order as `names`
+    pub fn connect_signals<F>(&self, names: &[SignalNames], f: F) -> Vec<ConnectionId>
+    where
+        F: Fn(&SignalNames, &dyn Any) + 'static,
+    {
+        let f = Rc::new(f);
+        let mut signals = self.signals.borrow_mut();
+
+        names
+            .iter()
+            .map(|name| {
+                let f = Rc::clone(&f);
+                let signal_name = name.clone();
+                signals
+                    .entry(name.clone())
+                    .or_default()
+                    .connect(move |data| f(&signal_name, data))
+            })
+            .collect()
+    }
+
+    /// Run `f` and collect callbacks it connected to signals, so they can be disconnected later
+    /// with [Environment::disconnect] without touching callbacks connected by anyone else
+    pub fn track_connections<T>(
+        &self,
+        f: impl FnOnce() -> T,
+    ) -> (T, Vec<(SignalNames, ConnectionId)>) {
+        let before: HashMap<SignalNames, Vec<ConnectionId>> = self
+            .signals
+            .borrow()
+            .iter()
+            .map(|(name, signal)| (name.clone(), signal.connection_ids()))
+            .collect();
+
+        let result = f();
+
+        let connections = self
+            .signals
+            .borrow()
+            .iter()
+            .flat_map(|(name, signal)| {
+                let old = before.get(name);
+                signal
+                    .connection_ids()
+                    .into_iter()
+                    .filter(move |id| old.is_none_or(|old| !old.contains(id)))
+                    .map(move |id| (name.clone(), id))
+            })
+            .collect();
+
+        (result, connections)
+    }
+
+    /// Disconnect callbacks collected by [Environment::track_connections]
+    pub fn disconnect(&self, connections: &[(SignalNames, ConnectionId)]) {
+        let signals = self.signals.borrow();
+        for (name, id) in connections {
+            if let Some(signal) = signals.get(name) {
+                signal.disconnect(*id);
+            }
+        }
+    }
+}
+
+/// Wait until any of `fds` has data to read or `timeout` passes. Waits indefinitely without a
+/// timeout. Returns `false` on timeout or if the wait was interrupted by a signal, e.g. `SIGHUP`.
+fn wait_readable(fds: &[BorrowedFd], timeout: Option<Duration>) -> io::Result<bool> {
+    let mut poll_fds: Vec<_> = fds
+        .iter()
+        .map(|fd| libc::pollfd {
+            fd: fd.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        })
+        .collect();
+    // Rounded up, so the deadline has passed after waking up
+    let timeout = timeout.map_or(-1, |t| {
+        t.as_nanos().div_ceil(1_000_000).min(i32::MAX as u128) as i32
+    });
+
+    match unsafe {
+        libc::poll(
+            poll_fds.as_mut_ptr(),
+            poll_fds.len() as libc::nfds_t,
+            timeout,
+        )
+    } {
+        -1 => match io::Error::last_os_error() {
+            e if e.kind() == ErrorKind::Interrupted => Ok(false),
+            e => Err(e),
+        },
+        n => Ok(n > 0),
+    }
 }
 
 #[derive(Error, Debug)]
@@ -64,26 +215,257 @@ pub enum RootError {
     EnvironmentNotInit,
 }
 
+impl From<BarLayer> for Layer {
+    fn from(layer: BarLayer) -> Self {
+        match layer {
+            BarLayer::Background => Layer::Background,
+            BarLayer::Bottom => Layer::Bottom,
+            BarLayer::Top => Layer::Top,
+            BarLayer::Overlay => Layer::Overlay,
+        }
+    }
+}
+
+impl From<KeyboardMode> for KeyboardInteractivity {
+    fn from(mode: KeyboardMode) -> Self {
+        match mode {
+            KeyboardMode::None => KeyboardInteractivity::None,
+            KeyboardMode::OnDemand => KeyboardInteractivity::OnDemand,
+            KeyboardMode::Exclusive => KeyboardInteractivity::Exclusive,
+        }
+    }
+}
+
+/// Layer surface showing a bar on a single output
+struct OutputBar {
+    layer: LayerSurface,
+    bar: Bar,
+    env: Rc<Environment>,
+
+    first_configure: bool,
+    /// Frame was committed and the compositor has not asked for the next one yet
+    frame_pending: bool,
+    width: u32,
+    height: u32,
+    /// Follows size of the bar, see [ResizeDebounce]
+    resize: ResizeDebounce,
+    /// Outputs the surface is shown on and power of the output the bar is created for
+    visibility: Visibility<wl_output::WlOutput>,
+    /// Reports power of the output the bar is created for. `None` if the compositor does not
+    /// support wlr-output-power-management, the output is considered powered on then
+    power: Option<ZwlrOutputPowerV1>,
+    /// Callbacks widgets of the bar connected to signals
+    connections: Vec<(SignalNames, ConnectionId)>,
+    /// Bars created earlier have lower numbers
+    created: usize,
+}
+
+impl Drop for OutputBar {
+    fn drop(&mut self) {
+        self.disconnect_signals();
+        if let Some(power) = self.power.take() {
+            power.destroy();
+        }
+    }
+}
+
+impl OutputBar {
+    /// Bind and initialise the bar, then configure the layer surface according to it's settings.
+    /// Bars without a configured width take the width of the output.
+    fn init(&mut self, shm: &mut Shm, output_size: Option<(i32, i32)>) -> Result<()> {
+        let settings = self.bar.settings();
+        let mut anchor = match settings.anchor {
+            BarAnchor::Top => Anchor::TOP,
+            BarAnchor::Bottom => Anchor::BOTTOM,
+        };
+        let (top, right, bottom, left) = settings.margins;
+        self.layer.set_margin(top, right, bottom, left);
+        self.layer.set_layer(settings.layer.into());
+        self.layer
+            .set_keyboard_interactivity(settings.keyboard_interactivity.into());
+
+        // Bar spans the output between side margins, so it is anchored to both sides
+        if let Some((width, _)) = output_size {
+            if settings.default_data.width == 0 && !settings.fit_content {
+                self.bar.data_mut().width = (width - left - right).max(1) as usize;
+                anchor |= Anchor::LEFT | Anchor::RIGHT;
+            }
+        }
+        self.layer.set_anchor(anchor);
+
+        self.bar.bind(Rc::clone(&self.env))?;
+        // Widgets that failed to initialise may have connected already
+        let (result, connections) = self.env.track_connections(|| self.bar.init());
+        self.connections.extend(connections);
+        result?;
+
+        self.width = self.bar.data().width as u32;
+        self.height = max(1, self.bar.data().height as u32);
+        if let Some((_, height)) = output_size {
+            self.height = min(self.height, height as u32);
+        }
+
+        self.layer.set_size(self.width, self.height);
+        self.layer
+            .set_exclusive_zone(self.bar.settings().exclusive_zone(self.height as i32));
+        self.layer.commit();
+
+        self.env
+            .drawer
+            .borrow_mut()
+            .update_sizes(shm, self.width as i32, self.height as i32)?;
+
+        Ok(())
+    }
+
+    /// Disconnect callbacks connected by widgets of the bar
+    fn disconnect_signals(&mut self) {
+        self.env.disconnect(&self.connections);
+        self.connections.clear();
+    }
+
+    fn draw(&mut self, shm: &mut Shm, qh: &QueueHandle<Root>) -> Result<()> {
+        self.bar.prepare()?;
+
+        let wanted = {
+            let bar = self.bar.data();
+            (max(1, bar.width as u32), max(1, bar.height as u32))
+        };
+        if let Some((width, height)) =
+            self.resize
+                .next_size((self.width, self.height), wanted, Instant::now())
+        {
+            // Surface keeps it's size if new buffers can not be allocated, resizing is
+            // retried on the next frame
+            self.env
+                .drawer
+                .borrow_mut()
+                .update_sizes(shm, width as i32, height as i32)?;
+
+            self.width = width;
+            self.height = height;
+
+            self.layer.set_size(self.width, self.height);
+            self.layer
+                .set_exclusive_zone(self.bar.settings().exclusive_zone(self.height as i32));
+        }
+
+        self.env.drawer.borrow_mut().clear()?;
+
+        self.bar.run()?;
+        draw_with_opacity(&self.bar)?;
+
+        // Widgets report areas they changed while drawing. Nothing is committed if there are none,
+        // so the compositor does not have to redraw the bar.
+        let mut drawer = self.env.drawer.borrow_mut();
+        if !drawer.is_damaged() {
+            self.frame_pending = false;
+            return Ok(());
+        }
+
+        // Request our next frame
+        self.layer
+            .wl_surface()
+            .frame(qh, self.layer.wl_surface().clone());
+
+        drawer.commit(self.layer.wl_surface());
+        self.frame_pending = true;
+
+        Ok(())
+    }
+
+    fn is_visible(&self) -> bool {
+        self.env.visible.get()
+    }
+
+    /// Expose changed [OutputBar::visibility] to widgets
+    fn update_visibility(&self) {
+        self.env.visible.set(self.visibility.is_visible());
+    }
+
+    fn state(&self) -> BarState {
+        BarState {
+            configured: !self.first_configure,
+            frame_pending: self.frame_pending,
+            visible: self.is_visible(),
+        }
+    }
+
+    /// Whether the bar is waiting to be drawn outside of frame callbacks, see [BarState::is_idle]
+    fn is_idle(&self) -> bool {
+        self.state().is_idle()
+    }
+}
+
+/// Pointer resting over a bar
+struct Hover {
+    output: wl_output::WlOutput,
+    /// Position of the pointer on the bar surface
+    position: (usize, usize),
+    /// Tooltip of the widget under the pointer
+    tooltip: Option<String>,
+    /// Moment the pointer moved over the widget with the tooltip
+    since: Instant,
+}
+
+/// Floating surface showing a tooltip of a hovered widget
+struct Tooltip {
+    layer: LayerSurface,
+    text: Text,
+    env: Rc<Environment>,
+}
+
+impl Tooltip {
+    fn draw(&self) -> Result<()> {
+        self.env.drawer.borrow_mut().clear()?;
+        self.text.draw()?;
+        self.env.drawer.borrow_mut().commit(self.layer.wl_surface());
+        Ok(())
+    }
+}
+
 pub struct Root {
     flag: bool,
 
     registry_state: RegistryState,
     seat_state: SeatState,
     output_state: OutputState,
+    compositor: CompositorState,
+    layer_shell: LayerShell,
     shm: Shm,
+    /// Reports power of outputs, optional
+    output_power: Option<ZwlrOutputPowerManagerV1>,
+    qh: QueueHandle<Root>,
 
-    first_configure: bool,
-    width: u32,
-    height: u32,
     shift: Option<u32>,
-    layer: LayerSurface,
     keyboard: Option<wl_keyboard::WlKeyboard>,
     keyboard_focus: bool,
+    keyboard_layouts: KeyboardLayouts,
     pointer: Option<wl_pointer::WlPointer>,
-
+    hover: Option<Hover>,
+    tooltip: Option<Tooltip>,
+
+    /// Bars shown on each output
+    bars: HashMap<wl_output::WlOutput, OutputBar>,
+    /// [OutputBar::created] of the next bar
+    next_bar: usize,
+    /// Bar passed to [Root::new]. It is shown on the first output it matches.
     bar: Option<Bar>,
+    /// Layout from an applied config, a new bar is built from it for every matching output
+    bar_config: Option<config::widgets::bar::Bar>,
+    signals: Rc<RefCell<HashMap<SignalNames, Signal>>>,
+    fonts: Rc<FontsMap>,
+
     services: Vec<Box<dyn Service>>,
     env: Option<Rc<Environment>>,
+    reload_path: Option<PathBuf>,
+    ipc: Option<ipc::Server>,
+    /// Event loop stops at the next iteration
+    quit: bool,
+    /// Moment bars were drawn last time. `None` until the first [Root::tick]
+    last_draw: Option<Instant>,
+    /// Events were received since the last draw, they may have changed widgets
+    pending: bool,
 }
 
 impl CompositorHandler for Root {
@@ -91,9 +473,41 @@ impl CompositorHandler for Root {
         &mut self,
         _conn: &Connection,
         _qh: &QueueHandle<Self>,
-        _surface: &wl_surface::WlSurface,
-        _new_factor: i32,
+        surface: &wl_surface::WlSurface,
+        new_factor: i32,
     ) {
+        if let Some(key) = self.output_of(surface) {
+            let bar = self.bars.get_mut(&key).unwrap();
+            if let Err(a) = bar
+                .env
+                .drawer
+                .borrow_mut()
+                .update_scale(&mut self.shm, new_factor)
+            {
+                println!("{a}");
+                return;
+            }
+
+            // Buffers of the new scale are empty, so the bar is drawn again
+            if bar.is_idle() {
+                if let Err(a) = self.draw(&key) {
+                    println!("{a}");
+                }
+            }
+        } else if let Some(tooltip) = &self.tooltip {
+            if tooltip.layer.wl_surface() != surface {
+                return;
+            }
+
+            let updated = tooltip
+                .env
+                .drawer
+                .borrow_mut()
+                .update_scale(&mut self.shm, new_factor);
+            if let Err(a) = updated.map_err(Into::into).and_then(|_| tooltip.draw()) {
+                println!("{a}");
+            }
+        }
     }
 
     fn transform_changed(
@@ -108,31 +522,55 @@ impl CompositorHandler for Root {
     fn frame(
         &mut self,
         _conn: &Connection,
-        qh: &QueueHandle<Self>,
-        _surface: &wl_surface::WlSurface,
+        _qh: &QueueHandle<Self>,
+        surface: &wl_surface::WlSurface,
         _time: u32,
     ) {
-        if let Err(a) = self.draw(qh) {
-            println!("{a}");
-        }
+        let Some(output) = self.output_of(surface) else {
+            return;
+        };
+        // Bar is drawn by the next tick, so it's still limited by the refresh rate
+        let bar = self.bars.get_mut(&output).unwrap();
+        bar.frame_pending = false;
+        self.pending |= bar.is_visible();
     }
 
     fn surface_enter(
         &mut self,
         _conn: &Connection,
         _qh: &QueueHandle<Self>,
-        _surface: &wl_surface::WlSurface,
-        _output: &wl_output::WlOutput,
+        surface: &wl_surface::WlSurface,
+        output: &wl_output::WlOutput,
     ) {
+        let Some(key) = self.output_of(surface) else {
+            return;
+        };
+        let bar = self.bars.get_mut(&key).unwrap();
+        let shown = bar.visibility.enter(output.clone());
+        bar.update_visibility();
+
+        // Frame callbacks stop once the bar is hidden, so the draw loop is restarted here
+        if shown {
+            if let Err(a) = self.draw(&key) {
+                println!("{a}");
+            }
+        }
     }
 
     fn surface_leave(
         &mut self,
         _conn: &Connection,
         _qh: &QueueHandle<Self>,
-        _surface: &wl_surface::WlSurface,
-        _output: &wl_output::WlOutput,
+        surface: &wl_surface::WlSurface,
+        output: &wl_output::WlOutput,
     ) {
+        let Some(key) = self.output_of(surface) else {
+            return;
+        };
+        let bar = self.bars.get_mut(&key).unwrap();
+
+        bar.visibility.leave(output);
+        bar.update_visibility();
     }
 }
 
@@ -145,8 +583,16 @@ impl OutputHandler for Root {
         &mut self,
         _conn: &Connection,
         _qh: &QueueHandle<Self>,
-        _output: wl_output::WlOutput,
+        output: wl_output::WlOutput,
     ) {
+        // Outputs present on startup are handled in init
+        if self.env.is_none() {
+            return;
+        }
+
+        if let Err(a) = self.add_output(output) {
+            println!("{a}");
+        }
     }
 
     fn update_output(
@@ -161,29 +607,64 @@ impl OutputHandler for Root {
         &mut self,
         _conn: &Connection,
         _qh: &QueueHandle<Self>,
-        _output: wl_output::WlOutput,
+        output: wl_output::WlOutput,
     ) {
+        self.bars.remove(&output);
+        if self
+            .hover
+            .as_ref()
+            .is_some_and(|hover| hover.output == output)
+        {
+            self.hover = None;
+            self.tooltip = None;
+        }
+
+        for bar in self.bars.values_mut() {
+            bar.visibility.leave(&output);
+            bar.update_visibility();
+        }
     }
 }
 
 impl LayerShellHandler for Root {
-    fn closed(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _layer: &LayerSurface) {}
+    fn closed(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, layer: &LayerSurface) {
+        if self.is_tooltip(layer) {
+            self.tooltip = None;
+            return;
+        }
+
+        if let Some(output) = self.output_of(layer.wl_surface()) {
+            self.bars.remove(&output);
+        }
+    }
 
     fn configure(
         &mut self,
         _conn: &Connection,
-        qh: &QueueHandle<Self>,
-        _layer: &LayerSurface,
+        _qh: &QueueHandle<Self>,
+        layer: &LayerSurface,
         configure: LayerSurfaceConfigure,
         _serial: u32,
     ) {
-        self.width = NonZeroU32::new(configure.new_size.0).map_or(256, NonZeroU32::get);
-        self.height = NonZeroU32::new(configure.new_size.1).map_or(256, NonZeroU32::get);
+        if self.is_tooltip(layer) {
+            if let Err(a) = self.tooltip.as_ref().unwrap().draw() {
+                println!("{a}");
+            }
+            return;
+        }
 
-        if self.first_configure {
-            self.first_configure = false;
+        let Some(output) = self.output_of(layer.wl_surface()) else {
+            return;
+        };
+        let bar = self.bars.get_mut(&output).unwrap();
 
-            if let Err(a) = self.draw(qh) {
+        bar.width = NonZeroU32::new(configure.new_size.0).map_or(256, NonZeroU32::get);
+        bar.height = NonZeroU32::new(configure.new_size.1).map_or(256, NonZeroU32::get);
+
+        if bar.first_configure {
+            bar.first_configure = false;
+
+            if let Err(a) = self.draw(&output) {
                 println!("{a}");
             }
         }
@@ -251,7 +732,7 @@ impl KeyboardHandler for Root {
         _: &[u32],
         _: &[Keysym],
     ) {
-        if self.layer.wl_surface() == surface {
+        if self.output_of(surface).is_some() {
             self.keyboard_focus = true;
         }
     }
@@ -264,7 +745,7 @@ impl KeyboardHandler for Root {
         surface: &wl_surface::WlSurface,
         _: u32,
     ) {
-        if self.layer.wl_surface() == surface {
+        if self.output_of(surface).is_some() {
             self.keyboard_focus = false;
         }
     }
@@ -295,9 +776,32 @@ impl KeyboardHandler for Root {
         _: &QueueHandle<Self>,
         _: &wl_keyboard::WlKeyboard,
         _serial: u32,
-        _: Modifiers,
-        _layout: u32,
+        modifiers: Modifiers,
+        layout: u32,
+    ) {
+        let signal = self
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::Modifiers)
+            .or_default()
+            .clone();
+        signal.emit(&modifiers);
+
+        if self.keyboard_layouts.active != layout as usize {
+            self.keyboard_layouts.active = layout as usize;
+            self.emit_keyboard_layouts();
+        }
+    }
+
+    fn update_keymap(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &wl_keyboard::WlKeyboard,
+        keymap: Keymap<'_>,
     ) {
+        self.keyboard_layouts.names = layout_names(&keymap.as_string());
+        self.emit_keyboard_layouts();
     }
 }
 
@@ -311,18 +815,52 @@ impl PointerHandler for Root {
     ) {
         use PointerEventKind::*;
         for event in events {
-            if &event.surface != self.layer.wl_surface() {
+            let Some(output) = self.output_of(&event.surface) else {
                 continue;
-            }
+            };
             match event.kind {
-                Enter { .. } => {}
-                Leave { .. } => {}
-                Motion { .. } => {}
-                Press { .. } => {
+                Enter { .. } | Motion { .. } => self.hover_at(output, event.position),
+                Leave { .. } => {
+                    self.hover = None;
+                    self.tooltip = None;
+                }
+                Press { button, .. } => {
                     self.shift = self.shift.xor(Some(0));
+
+                    let (x, y) = event.position;
+                    if x < 0.0 || y < 0.0 {
+                        continue;
+                    }
+
+                    let bar = &self.bars[&output].bar;
+                    if let Err(a) = bar.click_at(x as usize, y as usize, button) {
+                        println!("{a}");
+                    }
                 }
                 Release { .. } => {}
-                Axis { .. } => {}
+                Axis {
+                    horizontal,
+                    vertical,
+                    ..
+                } => {
+                    let (x, y) = event.position;
+                    // Frames that only stop scrolling have no movement
+                    if x < 0.0
+                        || y < 0.0
+                        || (horizontal.absolute == 0.0 && vertical.absolute == 0.0)
+                    {
+                        continue;
+                    }
+
+                    let scroll = ScrollEvent {
+                        delta_x: horizontal.absolute,
+                        delta_y: vertical.absolute,
+                    };
+                    let bar = &self.bars[&output].bar;
+                    if let Err(a) = bar.scroll_at(x as usize, y as usize, scroll) {
+                        println!("{a}");
+                    }
+                }
             }
         }
     }
@@ -342,6 +880,18 @@ impl ProvidesRegistryState for Root {
 }
 
 impl Root {
+    /// Connect to the Wayland compositor from the environment and create [Root] with it's event
+    /// queue. Use [Root::new] to set up the connection manually.
+    pub fn connect(bar: Option<Bar>) -> Result<(Root, EventQueue<Root>)> {
+        let conn = Connection::connect_to_env()?;
+        let (globals, mut event_queue) = registry_queue_init(&conn)?;
+
+        let root = Root::new(&globals, &mut event_queue, bar)?;
+        Ok((root, event_queue))
+    }
+
+    /// Create [Root] on an existing connection. A bar passed here is shown on a single output,
+    /// use [Root::apply_config] to get a bar on every output.
     pub fn new(
         globals: &GlobalList,
         event_queue: &mut EventQueue<Root>,
@@ -353,10 +903,7 @@ impl Root {
             CompositorState::bind(globals, &qh).expect("wl_compositor is not available");
         let layer_shell = LayerShell::bind(globals, &qh).expect("layer shell is not available");
         let shm = Shm::bind(globals, &qh).expect("wl_shm is not available");
-
-        let surface = compositor.create_surface(&qh);
-
-        let layer = layer_shell.create_layer_surface(&qh, surface, Layer::Top, Some("Bar"), None);
+        let output_power = globals.bind(&qh, 1..=1, ()).ok();
 
         let root = Root {
             flag: true,
@@ -364,116 +911,664 @@ impl Root {
             registry_state: RegistryState::new(globals),
             seat_state: SeatState::new(globals, &qh),
             output_state: OutputState::new(globals, &qh),
+            compositor,
+            layer_shell,
             shm,
+            output_power,
+            qh,
 
-            first_configure: true,
-            width: 16,
-            height: 16,
             shift: None,
-            layer,
             keyboard: None,
             keyboard_focus: false,
+            keyboard_layouts: KeyboardLayouts::default(),
             pointer: None,
+            hover: None,
+            tooltip: None,
 
+            bars: HashMap::new(),
+            next_bar: 0,
             bar,
+            bar_config: None,
+            signals: Rc::new(RefCell::new(HashMap::new())),
+            fonts: Rc::new(FontsMap::new()),
+
             services: Vec::new(),
             env: None,
+            reload_path: None,
+            ipc: None,
+            quit: false,
+            last_draw: None,
+            pending: false,
         };
 
         Ok(root)
     }
 
+    /// Use the bar layout from `config`. A separate bar is built for every output matching
+    /// [BarSettings::outputs](crate::widgets::containers::bar::BarSettings::outputs). Preloaded
+    /// fonts of the config are added after fonts added with [Root::add_font_by_name].
     pub fn apply_config(&mut self, config: Config) -> Result<()> {
-        if self.bar.is_some() {
+        if self.bar.is_some() || self.bar_config.is_some() {
             return Err(anyhow!("Config can only be applied once"));
         }
-        let mut bar = Bar::new(None, config.bar.settings)?;
 
-        for widget in config.bar.left {
+        config.preloaded_fonts.load(&self.fonts)?;
+
+        // Catch errors in the config before any output is known
+        Self::build_bar(&config.bar)?;
+        self.bar_config = Some(config.bar);
+        Ok(())
+    }
+
+    fn build_bar(config: &config::widgets::bar::Bar) -> Result<Bar> {
+        let mut bar = Bar::new(None, config.settings.clone())?;
+
+        for widget in &config.left {
             widget.create_in_container(bar.left().get_mut())?;
         }
 
-        for widget in config.bar.center {
+        for widget in &config.center {
             widget.create_in_container(bar.center().get_mut())?;
         }
 
-        for widget in config.bar.right {
+        for widget in &config.right {
             widget.create_in_container(bar.right().get_mut())?;
         }
 
-        self.bar = Some(bar);
-        Ok(())
+        Ok(bar)
+    }
+
+    fn new_env(&mut self) -> Result<Environment, DrawerError> {
+        Ok(Environment {
+            config: Config::default(),
+            drawer: RefCell::new(Drawer::new(&mut self.shm, 1, 1)?),
+            signals: Rc::clone(&self.signals),
+            fonts: Rc::clone(&self.fonts),
+            visible: Cell::new(true),
+        })
     }
 
     fn init(&mut self) -> Result<&mut Self> {
-        if self.bar.is_none() {
+        if self.bar.is_none() && self.bar_config.is_none() {
             return Err(anyhow!("Empty bar can not be created"));
         }
 
-        self.layer.set_anchor(Anchor::TOP);
-        self.layer
-            .set_keyboard_interactivity(KeyboardInteractivity::OnDemand);
-        self.width = 1;
-        self.height = 1;
-
-        self.env = Some(Rc::new(Environment {
-            config: Config::default(),
-            drawer: RefCell::new(Drawer::new(&mut self.shm, 1, 1)),
-            signals: RefCell::new(HashMap::new()),
-        }));
+        let env = Rc::new(self.new_env()?);
+        self.env = Some(Rc::clone(&env));
 
         for service in &mut self.services {
-            service.bind(Rc::clone(self.env.as_ref().unwrap()))?;
+            service.bind(Rc::clone(&env))?;
 
             service.init()?;
         }
 
-        let bar = self.bar.as_mut().unwrap();
-        bar.bind(Rc::clone(self.env.as_ref().unwrap()))?;
-        bar.init()?;
+        for output in self.output_state.outputs() {
+            self.add_output(output)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Settings of the bar that would be created for the next output
+    fn pending_settings(&self) -> Option<&BarSettings> {
+        match (&self.bar, &self.bar_config) {
+            (Some(bar), _) => Some(bar.settings()),
+            (None, Some(config)) => Some(&config.settings),
+            (None, None) => None,
+        }
+    }
 
-        self.height = max(self.height, bar.data_mut().height as u32);
+    fn output_name(&self, output: &wl_output::WlOutput) -> Option<String> {
+        self.output_state.info(output).and_then(|info| info.name)
+    }
 
-        for output in self.output_state().outputs() {
-            let info = self
-                .output_state
-                .info(&output)
-                .ok_or_else(|| "output has no info".to_owned())
-                .unwrap();
+    fn output_size(&self, output: &wl_output::WlOutput) -> Option<(i32, i32)> {
+        self.output_state
+            .info(output)
+            .and_then(|info| info.logical_size)
+    }
 
-            if let Some((width, height)) = info.logical_size {
-                self.width = max(self.width, width as u32);
-                self.height = min(self.height, height as u32);
+    /// Emit known keyboard layouts, see [SignalNames::KeyboardLayouts]
+    fn emit_keyboard_layouts(&self) {
+        let signal = self
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::KeyboardLayouts)
+            .or_default()
+            .clone();
+        signal.emit(&self.keyboard_layouts);
+    }
+
+    /// Output the bar with the `surface` is created for
+    fn output_of(&self, surface: &wl_surface::WlSurface) -> Option<wl_output::WlOutput> {
+        self.bars
+            .iter()
+            .find(|(_, bar)| bar.layer.wl_surface() == surface)
+            .map(|(output, _)| output.clone())
+    }
+
+    /// Create a surface with a bar on the `output` if the bar should be shown there
+    fn add_output(&mut self, output: wl_output::WlOutput) -> Result<()> {
+        if self.bars.contains_key(&output) {
+            return Ok(());
+        }
+
+        let name = self.output_name(&output);
+        match self.pending_settings() {
+            Some(settings) if settings.shown_on(name.as_deref()) => {}
+            _ => return Ok(()),
+        }
+
+        let bar = match self.bar.take() {
+            Some(bar) => bar,
+            None => Self::build_bar(self.bar_config.as_ref().unwrap())?,
+        };
+
+        let surface = self.compositor.create_surface(&self.qh);
+        let layer = self.layer_shell.create_layer_surface(
+            &self.qh,
+            surface,
+            bar.settings().layer.into(),
+            Some(bar.settings().namespace()),
+            Some(&output),
+        );
+
+        let mut output_bar = OutputBar {
+            layer,
+            bar,
+            env: Rc::new(self.new_env()?),
+
+            first_configure: true,
+            frame_pending: false,
+            width: 1,
+            height: 1,
+            resize: ResizeDebounce::new(RESIZE_DELAY),
+            visibility: Visibility::new(),
+            power: self
+                .output_power
+                .as_ref()
+                .map(|manager| manager.get_output_power(&output, &self.qh, output.clone())),
+            connections: Vec::new(),
+            created: self.next_bar,
+        };
+        self.next_bar += 1;
+
+        let size = self.output_size(&output);
+        output_bar.init(&mut self.shm, size)?;
+        self.bars.insert(output, output_bar);
+
+        Ok(())
+    }
+
+    /// Create bars on outputs that match the current config and remove them from outputs that
+    /// do not
+    fn sync_outputs(&mut self) {
+        let removed: Vec<_> = self
+            .bars
+            .keys()
+            .filter(|output| {
+                let name = self.output_name(output);
+                !self
+                    .pending_settings()
+                    .is_some_and(|settings| settings.shown_on(name.as_deref()))
+            })
+            .cloned()
+            .collect();
+        for output in removed {
+            self.bars.remove(&output);
+        }
+
+        for output in self.output_state.outputs() {
+            if let Err(a) = self.add_output(output) {
+                println!("{a}");
             }
         }
+    }
 
-        self.layer.set_size(self.width, self.height);
-        self.layer.set_exclusive_zone(self.height as i32);
-        self.layer.commit();
+    /// Replace bars on all outputs with ones built from a new config. Callbacks of the old
+    /// widgets are disconnected from signals, last emitted values are kept so new widgets get
+    /// them right away. Callbacks connected by anyone else stay connected. Services added via
+    /// [Root::create_service] are torn down and initialised again.
+    ///
+    /// Fonts are replaced with preloaded fonts of the new config, so their ids match the config.
+    /// Fonts added with [Root::add_font_by_name] are dropped.
+    ///
+    /// If any of the new bars fails to initialise, the old ones are restored and an error is
+    /// returned.
+    pub fn reload_config(&mut self, config: Config) -> Result<()> {
+        let fonts = FontsMap::new();
+        config.preloaded_fonts.load(&fonts)?;
+
+        let mut new_bars = Vec::new();
+        for output in self.bars.keys() {
+            new_bars.push((output.clone(), Self::build_bar(&config.bar)?));
+        }
+        if new_bars.is_empty() {
+            Self::build_bar(&config.bar)?;
+        }
+
+        let old_pending = self.bar.take();
+        let old_config = self.bar_config.replace(config.bar);
+        let old_fonts = self.fonts.replace(fonts);
+
+        if self.env.is_none() {
+            return Ok(());
+        }
+
+        self.disconnect_signals();
+        let mut old_bars = Vec::new();
+        let mut result = Ok(());
+        for (output, bar) in new_bars {
+            let size = self.output_size(&output);
+            let output_bar = self.bars.get_mut(&output).unwrap();
+
+            old_bars.push((output, std::mem::replace(&mut output_bar.bar, bar)));
+            result = output_bar.init(&mut self.shm, size);
+            if result.is_err() {
+                break;
+            }
+        }
+
+        if let Err(e) = result {
+            self.disconnect_signals();
+            self.bar = old_pending;
+            self.bar_config = old_config;
+            self.fonts.replace(old_fonts);
+
+            for (output, bar) in old_bars {
+                let size = self.output_size(&output);
+                let output_bar = self.bars.get_mut(&output).unwrap();
+
+                output_bar.bar = bar;
+                output_bar.init(&mut self.shm, size)?;
+            }
+            return Err(e);
+        }
+
+        self.restart_services()?;
+        self.sync_outputs();
+        Ok(())
+    }
+
+    /// Disconnect callbacks of widgets of all bars. Callbacks connected by services or embedders
+    /// are kept.
+    fn disconnect_signals(&mut self) {
+        for bar in self.bars.values_mut() {
+            bar.disconnect_signals();
+        }
+    }
+
+    /// Tear services down and initialise them again, so they emit their current state to widgets
+    /// of new bars. Teardown errors are only reported.
+    fn restart_services(&mut self) -> Result<()> {
+        let Some(env) = self.env.clone() else {
+            return Ok(());
+        };
+
+        for service in &mut self.services {
+            if let Err(a) = service.teardown() {
+                println!("{a}");
+            }
+            service.bind(Rc::clone(&env))?;
+            service.init()?;
+        }
+        Ok(())
+    }
+
+    /// Reload config from a file at `path` every time the process receives `SIGHUP`. Format of
+    /// the file is chosen by [Config::parse_file]. Config errors are reported and the current bar
+    /// is kept.
+    pub fn reload_on_sighup(&mut self, path: PathBuf) -> Result<()> {
+        sighup::install()?;
+        self.reload_path = Some(path);
+        Ok(())
+    }
+
+    /// Stop the bar once the process receives `SIGTERM` or `SIGINT`, e.g. with Ctrl-C, instead
+    /// of being killed. See [Root::exit].
+    pub fn exit_on_signals(&mut self) -> Result<()> {
+        terminate::install()?;
+        Ok(())
+    }
+
+    /// Stop the event loop. [Root::run] returns and [Root::tick] returns `false` at the next
+    /// iteration after services are torn down and bars are removed from outputs.
+    pub fn exit(&mut self) {
+        self.quit = true;
+    }
+
+    fn reload_if_requested(&mut self) {
+        if self.reload_path.is_none() || !sighup::take() {
+            return;
+        }
+
+        if let Err(e) = self.reload_from_file() {
+            println!("Config was not reloaded: {e}");
+        }
+    }
+
+    /// Reload config from the file passed to [Root::reload_on_sighup]
+    fn reload_from_file(&mut self) -> Result<()> {
+        let Some(path) = &self.reload_path else {
+            return Err(anyhow!("Bar was not started from a config file"));
+        };
+
+        let config = Config::parse_file(path.clone())?;
+        config.validate()?;
+        self.reload_config(config)
+    }
+
+    /// Accept commands on a unix socket at `path`, see [ipc]. The socket is polled by the event
+    /// loop and removed once the root is dropped.
+    pub fn listen_ipc(&mut self, path: &Path) -> Result<()> {
+        self.ipc = Some(ipc::Server::bind(path)?);
+        Ok(())
+    }
+
+    fn handle_ipc(&mut self) {
+        let Some(mut server) = self.ipc.take() else {
+            return;
+        };
+
+        server.handle(|command| self.run_command(command).map_err(|e| e.to_string()));
+        self.ipc = Some(server);
+    }
+
+    /// Execute a command received via [ipc]. Returns output of the command
+    fn run_command(&mut self, command: ipc::Command) -> Result<String> {
+        match command {
+            ipc::Command::Quit => self.exit(),
+            ipc::Command::Reload => self.reload_from_file()?,
+            ipc::Command::Dump => return Ok(self.debug_tree()),
+        }
+
+        Ok(String::new())
+    }
+
+    /// [Widget::debug_tree] of every shown bar
+    pub fn debug_tree(&self) -> String {
+        self.bars()
+            .map(|bar| bar.debug_tree())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Lowest delay between redraws among all bars
+    fn refresh_rate(&self) -> Duration {
+        self.bars
+            .values()
+            .map(|bar| bar.bar.settings().refresh_rate())
+            .min()
+            .or_else(|| self.pending_settings().map(BarSettings::refresh_rate))
+            .unwrap_or_else(|| BarSettings::default().refresh_rate())
+    }
+
+    /// Earliest moment a shown bar or a service has to be updated. `None` if everything is
+    /// waiting for events.
+    fn next_deadline(&self) -> Option<Instant> {
+        let bars = self
+            .bars
+            .values()
+            .filter(|bar| bar.is_visible())
+            .map(|bar| schedule::earliest(next_update_of(&bar.bar), bar.resize.deadline()));
+        // Services are not run while every bar is hidden, so their deadlines would stay in the past
+        let services = self
+            .services
+            .iter()
+            .filter(|_| self.is_visible())
+            .map(|service| service.next_update());
+
+        bars.chain(services)
+            .chain([self.tooltip_deadline()])
+            .fold(None, schedule::earliest)
+    }
+
+    /// Whether `layer` is the surface of the shown tooltip
+    fn is_tooltip(&self, layer: &LayerSurface) -> bool {
+        self.tooltip
+            .as_ref()
+            .is_some_and(|tooltip| tooltip.layer.wl_surface() == layer.wl_surface())
+    }
+
+    /// Track the pointer moving to `position` on the bar of `output`. Tooltip is hidden once the
+    /// pointer leaves the widget it belongs to.
+    fn hover_at(&mut self, output: wl_output::WlOutput, position: (f64, f64)) {
+        if position.0 < 0.0 || position.1 < 0.0 {
+            return;
+        }
+        let position = (position.0 as usize, position.1 as usize);
+        let Some(bar) = self.bars.get(&output) else {
+            return;
+        };
+
+        let tooltip = bar.bar.tooltip_at(position.0, position.1);
+        match &mut self.hover {
+            Some(hover) if hover.output == output && hover.tooltip == tooltip => {
+                hover.position = position;
+            }
+            _ => {
+                self.tooltip = None;
+                self.hover = Some(Hover {
+                    output,
+                    position,
+                    tooltip,
+                    since: Instant::now(),
+                });
+            }
+        }
+    }
+
+    /// Moment the tooltip of the hovered widget has to be shown
+    fn tooltip_deadline(&self) -> Option<Instant> {
+        let hover = self.hover.as_ref()?;
+        if self.tooltip.is_some() || hover.tooltip.is_none() {
+            return None;
+        }
 
-        self.env.as_ref().unwrap().drawer.borrow_mut().update_sizes(
-            &mut self.shm,
-            self.width as i32,
-            self.height as i32,
+        let bar = self.bars.get(&hover.output)?;
+        Some(hover.since + bar.bar.settings().tooltip.delay())
+    }
+
+    /// Show the tooltip of the hovered widget if the pointer rested over it long enough. It is
+    /// placed under the pointer on the side of the bar facing the rest of the output.
+    fn show_tooltip_if_due(&mut self) -> Result<()> {
+        if self
+            .tooltip_deadline()
+            .is_none_or(|deadline| Instant::now() < deadline)
+        {
+            return Ok(());
+        }
+
+        let hover = self.hover.as_ref().unwrap();
+        let settings = self.bars[&hover.output].bar.settings().clone();
+        let content = hover.tooltip.clone().unwrap_or_default();
+        let (output, position) = (hover.output.clone(), hover.position);
+        let bar_height = self.bars[&output].height as i32;
+        let scale = self.bars[&output].env.drawer.borrow().scale();
+
+        let env = Rc::new(self.new_env()?);
+        let mut text = Text::new(None, settings.tooltip.text_settings(&content))?;
+        text.bind(Rc::clone(&env))?;
+        text.init()?;
+        text.prepare()?;
+        let width = max(1, text.data().width) as i32;
+        let height = max(1, text.data().height) as i32;
+
+        let surface = self.compositor.create_surface(&self.qh);
+        let layer = self.layer_shell.create_layer_surface(
+            &self.qh,
+            surface,
+            Layer::Overlay,
+            Some("Tooltip"),
+            Some(&output),
         );
 
-        Ok(self)
+        // Exclusive zone of the bar is ignored, so the margin includes the bar itself
+        let (top, _, bottom, left) = settings.margins;
+        let x = max(0, left + position.0 as i32 - width / 2);
+        match settings.anchor {
+            BarAnchor::Top => {
+                layer.set_anchor(Anchor::TOP | Anchor::LEFT);
+                layer.set_margin(top + bar_height + TOOLTIP_GAP, 0, 0, x);
+            }
+            BarAnchor::Bottom => {
+                layer.set_anchor(Anchor::BOTTOM | Anchor::LEFT);
+                layer.set_margin(0, 0, bottom + bar_height + TOOLTIP_GAP, x);
+            }
+        }
+        layer.set_exclusive_zone(-1);
+        layer.set_keyboard_interactivity(KeyboardInteractivity::None);
+        layer.set_size(width as u32, height as u32);
+        layer.commit();
+
+        {
+            let mut drawer = env.drawer.borrow_mut();
+            drawer.update_scale(&mut self.shm, scale)?;
+            drawer.update_sizes(&mut self.shm, width, height)?;
+        }
+        self.tooltip = Some(Tooltip { layer, text, env });
+
+        Ok(())
     }
 
+    /// Run the event loop until [Root::exit] is called
     pub fn run(&mut self, event_queue: &mut EventQueue<Root>) -> Result<&mut Self> {
-        event_queue.blocking_dispatch(self)?;
-        self.init()?;
+        while self.tick(event_queue)? {}
+        Ok(self)
+    }
+
+    /// Single iteration of the event loop, for embedding the bar into another one: received
+    /// events are dispatched and bars are drawn if they are due. Waits for events until the next
+    /// update of widgets at most. The first call initializes the bar. Returns `false` once
+    /// [Root::exit] was called and everything is shut down.
+    pub fn tick(&mut self, event_queue: &mut EventQueue<Root>) -> Result<bool> {
+        let last_draw = match self.last_draw {
+            Some(last_draw) => last_draw,
+            None => {
+                event_queue.blocking_dispatch(self)?;
+                self.init()?;
+                *self.last_draw.insert(Instant::now())
+            }
+        };
+
+        if self.quit {
+            self.last_draw = None;
+            self.pending = false;
+            self.shutdown(event_queue)?;
+            return Ok(false);
+        }
+
+        // Bars are not redrawn more often than the refresh rate, even if widgets are updated
+        // continuously or events keep arriving
+        let earliest_draw = last_draw + self.refresh_rate();
+        let deadline = match self.pending {
+            true => Some(earliest_draw),
+            false => self.next_deadline().map(|d| d.max(earliest_draw)),
+        };
+
+        self.pending |= Self::dispatch_until(event_queue, self, deadline)? > 0;
+        self.reload_if_requested();
+        self.handle_ipc();
+        if terminate::take() {
+            self.exit();
+        }
+        if let Err(a) = self.show_tooltip_if_due() {
+            println!("{a}");
+        }
+
+        let now = Instant::now();
+        if now >= earliest_draw && (self.pending || deadline.is_some_and(|d| now >= d)) {
+            self.draw_idle();
+            self.last_draw = Some(now);
+            self.pending = false;
+        }
+
+        Ok(true)
+    }
+
+    /// Release everything acquired while running: services are torn down, surfaces of bars and
+    /// tooltips are destroyed and the socket is removed. Teardown errors are only reported, so
+    /// every service gets it's chance.
+    fn shutdown(&mut self, event_queue: &mut EventQueue<Root>) -> Result<()> {
+        for service in &self.services {
+            if let Err(a) = service.teardown() {
+                println!("{a}");
+            }
+        }
+
+        self.disconnect_signals();
+        self.hover = None;
+        self.tooltip = None;
+        self.bars.clear();
+        self.ipc = None;
+        self.quit = false;
+
+        event_queue.flush()?;
+        Ok(())
+    }
+
+    /// Dispatch received events. If there are none, wait for new ones until `deadline` or
+    /// indefinitely without it. Returns amount of dispatched events.
+    fn dispatch_until(
+        event_queue: &mut EventQueue<Root>,
+        root: &mut Root,
+        deadline: Option<Instant>,
+    ) -> Result<usize> {
+        let dispatched = event_queue.dispatch_pending(root)?;
+        event_queue.flush()?;
+        if dispatched > 0 {
+            return Ok(dispatched);
+        }
+
+        if let Some(guard) = event_queue.prepare_read() {
+            let timeout = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+            // Commands to the socket wake the loop up as well, they are handled after dispatch
+            let mut fds = vec![guard.connection_fd()];
+            fds.extend(root.ipc.iter().flat_map(ipc::Server::fds));
+            if wait_readable(&fds, timeout)? {
+                match guard.read() {
+                    Ok(_) => {}
+                    Err(WaylandError::Io(e)) if e.kind() == ErrorKind::WouldBlock => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+
+        Ok(event_queue.dispatch_pending(root)?)
+    }
+
+    /// Run services once and draw bars that are not waiting for a frame callback. Services are
+    /// not run while every bar is hidden.
+    fn draw_idle(&mut self) {
+        let work = visibility::idle_work(
+            self.bars
+                .iter()
+                .map(|(output, bar)| (output.clone(), bar.state())),
+        );
 
-        loop {
-            thread::sleep(Duration::from_millis(100));
-            event_queue.blocking_dispatch(self)?;
+        if work.run_services {
+            for service in &self.services {
+                if let Err(a) = service.run() {
+                    println!("{a}");
+                }
+            }
         }
 
-        //Ok(self)
+        for output in work.draw {
+            if let Err(a) = self.draw(&output) {
+                println!("{a}");
+            }
+        }
     }
 
     pub fn add_font_by_name(&mut self, name: &'static str) -> Result<(), FontsError> {
-        fonts::add_font_by_name(name)
+        self.fonts.add_font_by_name(name).map(|_| ())
+    }
+
+    /// Load a font from a file, e.g. one bundled with the bar. Returns id of the font to use in
+    /// widget settings.
+    pub fn add_font_by_path(&mut self, path: &Path) -> Result<usize, FontsError> {
+        self.fonts.add_font_by_path(path)
     }
 
     pub fn create_service<W, F>(&mut self, f: F, settings: W::Settings) -> Result<()>
@@ -485,59 +1580,107 @@ impl Root {
         Ok(())
     }
 
-    fn draw(&mut self, qh: &QueueHandle<Self>) -> Result<()> {
+    fn draw(&mut self, output: &wl_output::WlOutput) -> Result<()> {
         if self.env.is_none() {
             return Err(RootError::EnvironmentNotInit.into());
         }
 
-        for service in &mut self.services {
-            service.run()?;
+        if let Some(bar) = self.bars.get_mut(output) {
+            bar.draw(&mut self.shm, &self.qh)?;
         }
 
-        self.bar.as_ref().unwrap().prepare()?;
+        self.flag = false;
+        Ok(())
+    }
 
-        {
-            let bar = self.bar.as_ref().unwrap().data();
-            if self.width != bar.width as u32 || self.height != bar.height as u32 {
-                self.width = bar.width as u32;
-                self.height = bar.height as u32;
-
-                self.layer.set_size(self.width, self.height);
-                self.layer.set_exclusive_zone(self.height as i32);
-
-                self.env.as_ref().unwrap().drawer.borrow_mut().update_sizes(
-                    &mut self.shm,
-                    self.width as i32,
-                    self.height as i32,
-                );
-            }
-        }
+    /// Whether a bar is shown on at least one output. Before surfaces enter any output they are
+    /// considered visible, so the first frame is drawn.
+    pub fn is_visible(&self) -> bool {
+        self.env.is_none() || self.bars.values().any(OutputBar::is_visible)
+    }
 
-        self.layer
-            .wl_surface()
-            .damage_buffer(0, 0, self.width as i32, self.height as i32);
+    /// Earliest created of the shown bars, or the bar passed to [Root::new] before it is shown on
+    /// an output
+    #[deprecated(note = "a bar is shown on every output, use `Root::bars` instead")]
+    pub fn bar(&self) -> Option<&Bar> {
+        self.bars
+            .values()
+            .min_by_key(|bar| bar.created)
+            .map(|bar| &bar.bar)
+            .or(self.bar.as_ref())
+    }
 
-        self.bar.as_ref().unwrap().run()?;
-        self.bar.as_ref().unwrap().draw()?;
+    /// Earliest created of the shown bars mutably, or the bar passed to [Root::new] before it is
+    /// shown on an output
+    #[deprecated(note = "a bar is shown on every output, use `Root::bars_mut` instead")]
+    pub fn bar_mut(&mut self) -> Option<&mut Bar> {
+        match self.bars.values_mut().min_by_key(|bar| bar.created) {
+            Some(bar) => Some(&mut bar.bar),
+            None => self.bar.as_mut(),
+        }
+    }
 
-        // Request our next frame
-        self.layer
-            .wl_surface()
-            .frame(qh, self.layer.wl_surface().clone());
+    /// Bars shown on outputs
+    pub fn bars(&self) -> impl Iterator<Item = &Bar> {
+        self.bars.values().map(|bar| &bar.bar)
+    }
 
-        self.env
-            .as_ref()
-            .unwrap()
-            .drawer
-            .borrow_mut()
-            .commit(self.layer.wl_surface());
+    /// Get shown bars mutably to add or remove widgets after creation. Bars are laid out again and
+    /// redrawn on the next frame, surfaces are resized if needed.
+    ///
+    /// Widgets added to a running bar should be created with it's environment, e.g. via
+    /// [Bar::create_widget_left], so they get bound and initialised.
+    pub fn bars_mut(&mut self) -> impl Iterator<Item = &mut Bar> {
+        self.bars.values_mut().map(|bar| &mut bar.bar)
+    }
+}
 
-        self.flag = false;
-        Ok(())
+impl Dispatch<ZwlrOutputPowerManagerV1, ()> for Root {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrOutputPowerManagerV1,
+        _: <ZwlrOutputPowerManagerV1 as wayland_client::Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
     }
+}
+
+impl Dispatch<ZwlrOutputPowerV1, wl_output::WlOutput> for Root {
+    fn event(
+        root: &mut Self,
+        power: &ZwlrOutputPowerV1,
+        event: zwlr_output_power_v1::Event,
+        output: &wl_output::WlOutput,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let Some(bar) = root.bars.get_mut(output) else {
+            return;
+        };
 
-    pub fn bar(&self) -> &Option<Bar> {
-        &self.bar
+        let shown = match event {
+            zwlr_output_power_v1::Event::Mode { mode } => bar
+                .visibility
+                .set_powered(mode != WEnum::Value(zwlr_output_power_v1::Mode::Off)),
+            // Power of the output can't be reported, e.g. another client manages it. The output
+            // is considered powered on from now on
+            zwlr_output_power_v1::Event::Failed => {
+                power.destroy();
+                bar.power = None;
+                bar.visibility.set_powered(true)
+            }
+            _ => false,
+        };
+        bar.update_visibility();
+
+        // Frame callbacks stop once the bar is hidden, so the draw loop is restarted here
+        if shown {
+            if let Err(a) = root.draw(output) {
+                println!("{a}");
+            }
+        }
     }
 }
 
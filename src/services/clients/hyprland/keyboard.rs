@@ -1,13 +1,13 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, rc::Rc, time::Instant};
 
 use anyhow::anyhow;
-use chrono::{DateTime, Duration, Local};
+use chrono::{DateTime, Local};
 use hyprland::{data::Devices, shared::HyprData};
 
 use crate::{
     root::Environment,
     services::{clients::KeyboardTrait, ProcessSettings, Service, ServiceError, ServiceNew},
-    util::signals::SignalNames,
+    util::{schedule, signals::SignalNames},
 };
 
 /// Service that tracks current keyboard layout
@@ -65,11 +65,18 @@ impl Service for Keyboard {
             return Err(ServiceError::RunWithNoEnv("Keyboard".to_string()));
         }
 
-        let mut signals = self.env.as_ref().unwrap().signals.borrow_mut();
-        signals.entry(SignalNames::Keyboard).or_default();
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::Keyboard)
+            .or_default()
+            .clone();
 
         *self.last_layout.borrow_mut() = Keyboard::get_main_keyboard()?.active_keymap;
-        signals[&SignalNames::Keyboard].emit(&self.last_layout.clone());
+        signal.emit(&self.last_layout.clone());
 
         Ok(())
     }
@@ -80,21 +87,29 @@ impl Service for Keyboard {
         }
 
         let mut last_update = self.last_update.borrow_mut();
-        if Local::now() - *last_update < Duration::milliseconds(self.settings.update_rate) {
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
             return Ok(());
         }
         *last_update = Local::now();
 
-        let signals = self.env.as_ref().unwrap().signals.borrow_mut();
         let mut last_layout = self.last_layout.borrow_mut();
         let current_layout = Keyboard::get_main_keyboard()?.active_keymap;
         if *last_layout != current_layout {
             *last_layout = current_layout;
-            signals[&SignalNames::Keyboard].emit(&last_layout.clone());
+            let signal =
+                self.env.as_ref().unwrap().signals.borrow()[&SignalNames::Keyboard].clone();
+            signal.emit(&last_layout.clone());
         }
 
         Ok(())
     }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
 }
 
 impl ServiceNew for Keyboard {
@@ -1,3 +1,22 @@
 //! Current module describes all of the hyprland communication
 
+#[cfg(feature = "keyboard")]
 pub mod keyboard;
+pub mod window_title;
+pub mod workspaces;
+
+use hyprland::dispatch::{Dispatch, DispatchType, WorkspaceIdentifierWithSpecial};
+
+use crate::services::{clients::Compositor, ServiceError};
+
+/// [Compositor] requests sent over hyprland IPC
+pub struct Hyprland;
+
+impl Compositor for Hyprland {
+    fn switch_workspace(&self, id: i32) -> Result<(), ServiceError> {
+        Dispatch::call(DispatchType::Workspace(WorkspaceIdentifierWithSpecial::Id(
+            id,
+        )))
+        .map_err(|e| ServiceError::Custom("Hyprland".to_string(), e.into()))
+    }
+}
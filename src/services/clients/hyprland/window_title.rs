@@ -0,0 +1,110 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use chrono::{DateTime, Local};
+use hyprland::{data::Client, shared::HyprDataActiveOptional};
+
+use crate::{
+    root::Environment,
+    services::{ProcessSettings, Service, ServiceError, ServiceNew},
+    util::{schedule, signals::SignalNames},
+};
+
+/// Service that tracks title of the focused window
+pub struct WindowTitle {
+    settings: ProcessSettings,
+
+    last_title: RefCell<Option<String>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl WindowTitle {
+    /// Title of the focused window. Empty if no window is focused
+    fn query() -> Result<String, ServiceError> {
+        let client = Client::get_active()
+            .map_err(|e| ServiceError::Custom("WindowTitle".to_string(), e.into()))?;
+
+        Ok(client.map(|client| client.title).unwrap_or_default())
+    }
+
+    fn emit(&self, title: String) {
+        let mut last_title = self.last_title.borrow_mut();
+        if last_title.as_ref() == Some(&title) {
+            return;
+        }
+
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::WindowTitle)
+            .or_default()
+            .clone();
+        signal.emit(&title);
+        *last_title = Some(title);
+    }
+}
+
+impl Service for WindowTitle {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::WindowTitle)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("WindowTitle".to_string()));
+        }
+
+        self.emit(WindowTitle::query()?);
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("WindowTitle".to_string()));
+        }
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
+            return Ok(());
+        }
+        *last_update = Local::now();
+
+        self.emit(WindowTitle::query()?);
+
+        Ok(())
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for WindowTitle {
+    type Settings = ProcessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(WindowTitle {
+            settings,
+            last_title: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
@@ -0,0 +1,125 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use chrono::{DateTime, Local};
+use hyprland::{
+    data::{Workspace, Workspaces as HyprWorkspaces},
+    shared::{HyprData, HyprDataActive, HyprDataVec},
+};
+
+use crate::{
+    root::Environment,
+    services::{clients::WorkspacesState, ProcessSettings, Service, ServiceError, ServiceNew},
+    util::{schedule, signals::SignalNames},
+};
+
+/// Service that tracks existing workspaces and the active one
+pub struct Workspaces {
+    settings: ProcessSettings,
+
+    last_state: RefCell<Option<WorkspacesState>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Workspaces {
+    fn query() -> Result<WorkspacesState, ServiceError> {
+        let map_err = |e: hyprland::shared::HyprError| {
+            ServiceError::Custom("Workspaces".to_string(), e.into())
+        };
+
+        let active = Workspace::get_active().map_err(map_err)?.id;
+
+        // Special workspaces have negative ids and are not shown
+        let mut workspaces: Vec<_> = HyprWorkspaces::get()
+            .map_err(map_err)?
+            .to_vec()
+            .into_iter()
+            .filter(|w| w.id > 0)
+            .map(|w| (w.id, w.name))
+            .collect();
+        workspaces.sort_by_key(|(id, _)| *id);
+
+        Ok(WorkspacesState { workspaces, active })
+    }
+}
+
+impl Service for Workspaces {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::Workspaces)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Workspaces".to_string()));
+        }
+
+        let state = Workspaces::query()?;
+        *self.last_state.borrow_mut() = Some(state.clone());
+
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::Workspaces)
+            .or_default()
+            .clone();
+        signal.emit(&state);
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Workspaces".to_string()));
+        }
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
+            return Ok(());
+        }
+        *last_update = Local::now();
+
+        let state = Workspaces::query()?;
+        let mut last_state = self.last_state.borrow_mut();
+        if last_state.as_ref() != Some(&state) {
+            let signal =
+                self.env.as_ref().unwrap().signals.borrow()[&SignalNames::Workspaces].clone();
+            signal.emit(&state);
+            *last_state = Some(state);
+        }
+
+        Ok(())
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for Workspaces {
+    type Settings = ProcessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(Workspaces {
+            settings,
+            last_state: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
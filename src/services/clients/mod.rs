@@ -1,14 +1,67 @@
 //! Current module describes all of capybars clients. Different compositors handle some stuff
 //! differently. All of the unique behaviours is described here.
 
-use super::Service;
+use super::ServiceError;
 
 #[cfg(feature = "hyprland")]
 pub mod hyprland;
+pub mod wayland;
 
 #[allow(dead_code)]
 #[cfg(feature = "keyboard")]
-trait KeyboardTrait: Service {}
+trait KeyboardTrait: super::Service {}
 
+/// Keyboard layout service. Hyprland one is used if `keyboard+hyprland` feature is enabled, the
+/// compositor independent one otherwise
 #[cfg(feature = "keyboard+hyprland")]
 pub use hyprland::keyboard::Keyboard;
+#[cfg(all(feature = "keyboard", not(feature = "keyboard+hyprland")))]
+pub use wayland::keyboard::Keyboard;
+
+#[cfg(feature = "hyprland")]
+pub use hyprland::{window_title::WindowTitle, workspaces::Workspaces, Hyprland};
+
+/// Workspaces of the running compositor. Emitted by a workspaces service via
+/// [SignalNames::Workspaces](crate::util::signals::SignalNames::Workspaces)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WorkspacesState {
+    /// Ids and names of existing workspaces sorted by id
+    pub workspaces: Vec<(i32, String)>,
+    /// Id of the focused workspace
+    pub active: i32,
+}
+
+/// Keyboard layouts of the keymap sent to the bar by the compositor. Emitted by
+/// [Root](crate::root::Root) via
+/// [SignalNames::KeyboardLayouts](crate::util::signals::SignalNames::KeyboardLayouts)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KeyboardLayouts {
+    /// Names of layouts ordered by their index, e.g. `"English (US)"`
+    pub names: Vec<String>,
+    /// Index of the active layout
+    pub active: usize,
+}
+
+impl KeyboardLayouts {
+    /// Name of the active layout. `None` if the keymap has no such layout
+    pub fn active_name(&self) -> Option<&str> {
+        self.names.get(self.active).map(String::as_str)
+    }
+}
+
+/// Requests that widgets can send to the running compositor. Every supported compositor
+/// implements it in its own client module.
+pub trait Compositor {
+    /// Focus workspace with provided id
+    fn switch_workspace(&self, id: i32) -> Result<(), ServiceError>;
+}
+
+/// Get [Compositor] enabled by cargo features. Returns `None` if capybar was built without support
+/// for any compositor.
+pub fn compositor() -> Option<Box<dyn Compositor>> {
+    #[cfg(feature = "hyprland")]
+    return Some(Box::new(Hyprland));
+
+    #[allow(unreachable_code)]
+    None
+}
@@ -0,0 +1,169 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use chrono::{DateTime, Local};
+
+#[cfg(feature = "keyboard")]
+use crate::services::clients::KeyboardTrait;
+use crate::{
+    root::Environment,
+    services::{clients::KeyboardLayouts, ProcessSettings, Service, ServiceError, ServiceNew},
+    util::{schedule, signals::SignalNames},
+};
+
+/// Names of layouts in a keymap in the XKB text format, as sent by the compositor. Layouts are
+/// ordered by their index, layouts without a name are empty.
+///
+/// # Examples
+/// ```
+/// use capybar::services::clients::wayland::keyboard::layout_names;
+///
+/// let keymap = r#"
+/// xkb_symbols "pc+us+de:2" {
+///     name[Group1]="English (US)";
+///     name[Group2]="German";
+/// };"#;
+/// assert_eq!(layout_names(keymap), vec!["English (US)", "German"]);
+/// ```
+pub fn layout_names(keymap: &str) -> Vec<String> {
+    let mut names = Vec::new();
+
+    for line in keymap.lines() {
+        let Some(rest) = line.trim().strip_prefix("name[") else {
+            continue;
+        };
+        let Some((group, rest)) = rest.split_once(']') else {
+            continue;
+        };
+
+        let group = group.trim();
+        let group = match group.get(..5) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("group") => &group[5..],
+            _ => group,
+        };
+        let Some(index) = group.parse::<usize>().ok().and_then(|i| i.checked_sub(1)) else {
+            continue;
+        };
+
+        let Some(name) = rest
+            .trim_start()
+            .strip_prefix('=')
+            .and_then(|value| value.trim().strip_prefix('"'))
+            .and_then(|value| value.split_once('"'))
+            .map(|(name, _)| name)
+        else {
+            continue;
+        };
+
+        if names.len() <= index {
+            names.resize(index + 1, String::new());
+        }
+        names[index] = name.to_string();
+    }
+
+    names
+}
+
+/// Service that tracks current keyboard layout using the keymap sent to the bar by the
+/// compositor. Works with any compositor, but compositors only tell the focused client which
+/// layout is active, so a layout switched while the bar is not focused is shown once the bar
+/// gets keyboard focus.
+pub struct Keyboard {
+    settings: ProcessSettings,
+
+    last_layout: RefCell<Option<String>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Keyboard {
+    /// Emit name of the active layout if it changed. Nothing is emitted until the keymap is
+    /// received
+    fn update(&self) {
+        let signals = &self.env.as_ref().unwrap().signals;
+
+        let layout = signals
+            .borrow()
+            .get(&SignalNames::KeyboardLayouts)
+            .and_then(|signal| signal.get_last_value_cloned::<KeyboardLayouts>())
+            .and_then(|layouts| layouts.active_name().map(str::to_string));
+        let Some(layout) = layout else {
+            return;
+        };
+
+        let mut last_layout = self.last_layout.borrow_mut();
+        if last_layout.as_ref() != Some(&layout) {
+            let signal = signals
+                .borrow_mut()
+                .entry(SignalNames::Keyboard)
+                .or_default()
+                .clone();
+            signal.emit(&layout);
+            *last_layout = Some(layout);
+        }
+    }
+}
+
+impl Service for Keyboard {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::Keyboard)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Keyboard".to_string()));
+        }
+
+        self.update();
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Keyboard".to_string()));
+        }
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
+            return Ok(());
+        }
+        *last_update = Local::now();
+
+        self.update();
+
+        Ok(())
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for Keyboard {
+    type Settings = ProcessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(Keyboard {
+            settings,
+            last_layout: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
+
+#[cfg(feature = "keyboard")]
+impl KeyboardTrait for Keyboard {}
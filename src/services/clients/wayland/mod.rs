@@ -0,0 +1,4 @@
+//! Current module describes services relying only on core Wayland protocols. They work with any
+//! compositor, but only know what the compositor sends to the bar itself.
+
+pub mod keyboard;
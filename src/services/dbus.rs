@@ -0,0 +1,28 @@
+//! Bus connections shared by DBus backed services
+
+use std::sync::OnceLock;
+
+use zbus::blocking::Connection;
+
+static CONNECTION: OnceLock<Connection> = OnceLock::new();
+static SYSTEM_CONNECTION: OnceLock<Connection> = OnceLock::new();
+
+/// Connect to the session bus or reuse the existing connection
+pub fn connection() -> zbus::Result<&'static Connection> {
+    if let Some(connection) = CONNECTION.get() {
+        return Ok(connection);
+    }
+
+    let connection = Connection::session()?;
+    Ok(CONNECTION.get_or_init(|| connection))
+}
+
+/// Connect to the system bus or reuse the existing connection
+pub fn system_connection() -> zbus::Result<&'static Connection> {
+    if let Some(connection) = SYSTEM_CONNECTION.get() {
+        return Ok(connection);
+    }
+
+    let connection = Connection::system()?;
+    Ok(SYSTEM_CONNECTION.get_or_init(|| connection))
+}
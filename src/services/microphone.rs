@@ -0,0 +1,160 @@
+use std::{cell::RefCell, process::Command, rc::Rc, time::Instant};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Local};
+
+use crate::{
+    root::Environment,
+    services::{
+        volume::{parse_pactl, parse_wpctl, run},
+        ProcessSettings, Service, ServiceError, ServiceNew,
+    },
+    util::{schedule, signals::SignalNames},
+};
+
+/// State of the default input device. Emitted by [Microphone] service via
+/// [SignalNames::Microphone]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MicrophoneState {
+    /// Whether there is an input device. Volume and mute state are not meaningful otherwise
+    pub available: bool,
+    /// Input level in percent. Can be higher than 100 if the device is amplified
+    pub volume: u32,
+    pub muted: bool,
+}
+
+impl MicrophoneState {
+    /// Mute or unmute the default input device. `wpctl` is tried first, `pactl` is used if
+    /// PipeWire tools are not available.
+    pub fn toggle_mute() -> anyhow::Result<()> {
+        let toggled = |command: &str, args: &[&str]| {
+            Command::new(command)
+                .args(args)
+                .status()
+                .is_ok_and(|status| status.success())
+        };
+
+        if toggled("wpctl", &["set-mute", "@DEFAULT_AUDIO_SOURCE@", "toggle"])
+            || toggled("pactl", &["set-source-mute", "@DEFAULT_SOURCE@", "toggle"])
+        {
+            return Ok(());
+        }
+
+        Err(anyhow!(
+            "Neither wpctl nor pactl could toggle the microphone"
+        ))
+    }
+}
+
+/// Service that tracks the default input device. Queries the same tools as
+/// [Volume](super::volume::Volume). If there is no input device, or the tools are missing, a
+/// state without a device is emitted instead of an error.
+pub struct Microphone {
+    settings: ProcessSettings,
+
+    last_state: RefCell<Option<MicrophoneState>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Microphone {
+    fn query() -> MicrophoneState {
+        let state = run("wpctl", &["get-volume", "@DEFAULT_AUDIO_SOURCE@"])
+            .and_then(|o| parse_wpctl(&o))
+            .or_else(|| {
+                run("pactl", &["get-source-volume", "@DEFAULT_SOURCE@"])
+                    .zip(run("pactl", &["get-source-mute", "@DEFAULT_SOURCE@"]))
+                    .and_then(|(volume, mute)| parse_pactl(&volume, &mute))
+            });
+
+        match state {
+            Some(state) => MicrophoneState {
+                available: true,
+                volume: state.volume,
+                muted: state.muted,
+            },
+            None => MicrophoneState::default(),
+        }
+    }
+
+    fn emit(&self, state: MicrophoneState) {
+        let mut last_state = self.last_state.borrow_mut();
+        if last_state.as_ref() == Some(&state) {
+            return;
+        }
+
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::Microphone)
+            .or_default()
+            .clone();
+        signal.emit(&state);
+        *last_state = Some(state);
+    }
+}
+
+impl Service for Microphone {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::Microphone)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Microphone".to_string()));
+        }
+
+        self.emit(Microphone::query());
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Microphone".to_string()));
+        }
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
+            return Ok(());
+        }
+        *last_update = Local::now();
+
+        self.emit(Microphone::query());
+
+        Ok(())
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for Microphone {
+    type Settings = ProcessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(Microphone {
+            settings,
+            last_state: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
@@ -4,22 +4,42 @@
 //! To communicate with frontend you can use [Signal](crate::util::signals::Signal)
 
 pub mod clients;
+#[cfg(any(
+    feature = "tray",
+    feature = "mpris",
+    feature = "notifications",
+    feature = "power_profile"
+))]
+pub mod dbus;
+pub mod microphone;
+#[cfg(feature = "mpris")]
+pub mod mpris;
+#[cfg(feature = "notifications")]
+pub mod notifications;
+#[cfg(feature = "power_profile")]
+pub mod power_profile;
+#[cfg(feature = "tray")]
+pub mod tray;
+pub mod volume;
+#[cfg(feature = "weather")]
+pub mod weather;
 
-use std::{fmt::Display, rc::Rc};
+use std::{fmt::Display, rc::Rc, time::Instant};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::root::Environment;
+use crate::{root::Environment, util::UpdateRate};
 
-fn default_update_rate() -> i64 {
-    1000
+const fn default_update_rate() -> UpdateRate {
+    UpdateRate::from_millis(1000)
 }
 
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ProcessSettings {
     #[serde(default = "default_update_rate")]
-    pub update_rate: i64,
+    pub update_rate: UpdateRate,
 }
 
 /// A **data structure** that can be used as a service inside a capybar.
@@ -32,6 +52,16 @@ pub trait Service {
 
     /// Run the [Service]
     fn run(&self) -> Result<(), ServiceError>;
+
+    /// Moment the [Service] should be run next time. `None` if it does not poll anything.
+    fn next_update(&self) -> Option<Instant> {
+        None
+    }
+
+    /// Release everything acquired by the [Service] before the bar exits
+    fn teardown(&self) -> Result<(), ServiceError> {
+        Ok(())
+    }
 }
 
 /// A [Service] that can be unifiedly created.
@@ -60,6 +90,15 @@ pub enum ServiceError {
 #[derive(Debug, Clone)]
 pub enum ServiceList {
     Keyboard,
+    Microphone,
+    Mpris,
+    Notifications,
+    PowerProfile,
+    Tray,
+    Volume,
+    Weather,
+    WindowTitle,
+    Workspaces,
     Custom(String),
 }
 
@@ -67,6 +106,15 @@ impl Display for ServiceList {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Keyboard => write!(f, "Keyboard"),
+            Self::Microphone => write!(f, "Microphone"),
+            Self::Mpris => write!(f, "Mpris"),
+            Self::Notifications => write!(f, "Notifications"),
+            Self::PowerProfile => write!(f, "PowerProfile"),
+            Self::Tray => write!(f, "Tray"),
+            Self::Volume => write!(f, "Volume"),
+            Self::Weather => write!(f, "Weather"),
+            Self::WindowTitle => write!(f, "WindowTitle"),
+            Self::Workspaces => write!(f, "Workspaces"),
             Self::Custom(name) => write!(f, "{name}"),
         }
     }
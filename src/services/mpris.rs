@@ -0,0 +1,223 @@
+//! Media players implementing [MPRIS](https://specifications.freedesktop.org/mpris-spec/latest/)
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Instant};
+
+use chrono::{DateTime, Local};
+use zbus::{
+    blocking::{fdo::DBusProxy, Proxy},
+    zvariant::OwnedValue,
+};
+
+use crate::{
+    root::Environment,
+    services::{dbus::connection, ProcessSettings, Service, ServiceError, ServiceNew},
+    util::{schedule, signals::SignalNames},
+};
+
+/// Prefix of bus names of all media players
+pub const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+const PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+
+/// Playback status of a player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlaybackStatus {
+    Playing,
+    Paused,
+    #[default]
+    Stopped,
+}
+
+impl PlaybackStatus {
+    /// Parse `PlaybackStatus` property of a player. Unknown statuses are treated as stopped
+    pub fn parse(status: &str) -> Self {
+        match status {
+            "Playing" => PlaybackStatus::Playing,
+            "Paused" => PlaybackStatus::Paused,
+            _ => PlaybackStatus::Stopped,
+        }
+    }
+}
+
+/// State of a single media player
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PlayerState {
+    /// Bus name of the player, e.g. `org.mpris.MediaPlayer2.spotify`
+    pub bus_name: String,
+    pub status: PlaybackStatus,
+    /// Artists of the track joined with a comma
+    pub artist: String,
+    pub title: String,
+}
+
+impl PlayerState {
+    /// Name of the player without [MPRIS_PREFIX], e.g. `spotify`
+    pub fn name(&self) -> &str {
+        self.bus_name
+            .strip_prefix(MPRIS_PREFIX)
+            .unwrap_or(&self.bus_name)
+    }
+
+    /// Toggle playback of the player
+    pub fn play_pause(&self) -> zbus::Result<()> {
+        player_proxy(&self.bus_name)?.call_noreply("PlayPause", &())
+    }
+
+    fn query(bus_name: &str) -> zbus::Result<PlayerState> {
+        let proxy = player_proxy(bus_name)?;
+        let status: String = proxy.get_property("PlaybackStatus")?;
+        let metadata: HashMap<String, OwnedValue> =
+            proxy.get_property("Metadata").unwrap_or_default();
+        let (artist, title) = parse_metadata(&metadata);
+
+        Ok(PlayerState {
+            bus_name: bus_name.to_string(),
+            status: PlaybackStatus::parse(&status),
+            artist,
+            title,
+        })
+    }
+}
+
+/// Extract artist and title from `Metadata` property of a player. Missing values are empty
+pub fn parse_metadata(metadata: &HashMap<String, OwnedValue>) -> (String, String) {
+    let artist = metadata
+        .get("xesam:artist")
+        .and_then(|v| Vec::<String>::try_from(v.try_clone().ok()?).ok())
+        .map(|artists| artists.join(", "))
+        .unwrap_or_default();
+
+    let title = metadata
+        .get("xesam:title")
+        .and_then(|v| String::try_from(v.try_clone().ok()?).ok())
+        .unwrap_or_default();
+
+    (artist, title)
+}
+
+fn player_proxy(bus_name: &str) -> zbus::Result<Proxy<'static>> {
+    zbus::blocking::proxy::Builder::new(connection()?)
+        .destination(bus_name.to_string())?
+        .path(MPRIS_PATH)?
+        .interface(PLAYER_INTERFACE)?
+        .cache_properties(zbus::proxy::CacheProperties::No)
+        .build()
+}
+
+/// All media players sorted by bus name. Emitted by [Mpris] service via [SignalNames::Mpris]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MprisState {
+    pub players: Vec<PlayerState>,
+}
+
+/// Service that tracks state of all media players on the session bus
+pub struct Mpris {
+    settings: ProcessSettings,
+
+    last_state: RefCell<Option<MprisState>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Mpris {
+    fn query() -> Result<MprisState, ServiceError> {
+        let map_err = |e: zbus::Error| ServiceError::Custom("Mpris".to_string(), e.into());
+
+        let mut names: Vec<String> = DBusProxy::new(connection().map_err(map_err)?)
+            .map_err(map_err)?
+            .list_names()
+            .map_err(|e| map_err(e.into()))?
+            .into_iter()
+            .map(|name| name.to_string())
+            .filter(|name| name.starts_with(MPRIS_PREFIX))
+            .collect();
+        names.sort();
+
+        // Players may quit between listing and querying, they are skipped
+        Ok(MprisState {
+            players: names
+                .iter()
+                .filter_map(|name| PlayerState::query(name).ok())
+                .collect(),
+        })
+    }
+}
+
+impl Service for Mpris {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::Mpris)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Mpris".to_string()));
+        }
+
+        let state = Mpris::query()?;
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::Mpris)
+            .or_default()
+            .clone();
+        signal.emit(&state);
+        *self.last_state.borrow_mut() = Some(state);
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Mpris".to_string()));
+        }
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
+            return Ok(());
+        }
+        *last_update = Local::now();
+
+        let state = Mpris::query()?;
+        let mut last_state = self.last_state.borrow_mut();
+        if last_state.as_ref() != Some(&state) {
+            let signal = self.env.as_ref().unwrap().signals.borrow()[&SignalNames::Mpris].clone();
+            signal.emit(&state);
+            *last_state = Some(state);
+        }
+
+        Ok(())
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for Mpris {
+    type Settings = ProcessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(Mpris {
+            settings,
+            last_state: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
@@ -0,0 +1,219 @@
+//! Pending notifications and do-not-disturb state of a notification daemon. Supported daemons are
+//! [SwayNotificationCenter](https://github.com/ErikReider/SwayNotificationCenter) and
+//! [dunst](https://dunst-project.org).
+
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use chrono::{DateTime, Local};
+use zbus::{
+    blocking::{fdo::DBusProxy, Proxy},
+    names::BusName,
+};
+
+use crate::{
+    root::Environment,
+    services::{dbus::connection, ProcessSettings, Service, ServiceError, ServiceNew},
+    util::{schedule, signals::SignalNames},
+};
+
+const SWAYNC_NAME: &str = "org.erikreider.swaync.cc";
+const SWAYNC_PATH: &str = "/org/erikreider/swaync/cc";
+const DUNST_NAME: &str = "org.freedesktop.Notifications";
+const DUNST_PATH: &str = "/org/freedesktop/Notifications";
+const DUNST_INTERFACE: &str = "org.dunstproject.cmd0";
+
+/// Notification daemon the state is received from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Daemon {
+    Swaync,
+    Dunst,
+}
+
+impl Daemon {
+    fn proxy(&self) -> zbus::Result<Proxy<'static>> {
+        let (destination, path, interface) = match self {
+            Daemon::Swaync => (SWAYNC_NAME, SWAYNC_PATH, SWAYNC_NAME),
+            Daemon::Dunst => (DUNST_NAME, DUNST_PATH, DUNST_INTERFACE),
+        };
+
+        zbus::blocking::proxy::Builder::new(connection()?)
+            .destination(destination)?
+            .path(path)?
+            .interface(interface)?
+            .cache_properties(zbus::proxy::CacheProperties::No)
+            .build()
+    }
+
+    /// Find a running daemon. Notifications of dunst are served under the standard name, so it
+    /// is recognised by it's own interface.
+    fn find() -> zbus::Result<Option<Daemon>> {
+        let dbus = DBusProxy::new(connection()?)?;
+
+        let swaync: BusName = SWAYNC_NAME.try_into()?;
+        if dbus.name_has_owner(swaync)? {
+            return Ok(Some(Daemon::Swaync));
+        }
+
+        let dunst: BusName = DUNST_NAME.try_into()?;
+        if dbus.name_has_owner(dunst)?
+            && Daemon::Dunst
+                .proxy()?
+                .get_property::<bool>("paused")
+                .is_ok()
+        {
+            return Ok(Some(Daemon::Dunst));
+        }
+
+        Ok(None)
+    }
+
+    fn query(&self) -> zbus::Result<NotificationsState> {
+        let proxy = self.proxy()?;
+        let (count, dnd) = match self {
+            Daemon::Swaync => (
+                proxy.call("NotificationCount", &())?,
+                proxy.call("GetDnd", &())?,
+            ),
+            Daemon::Dunst => {
+                let displayed: u32 = proxy.get_property("displayedLength")?;
+                let waiting: u32 = proxy.get_property("waitingLength")?;
+                (displayed + waiting, proxy.get_property("paused")?)
+            }
+        };
+
+        Ok(NotificationsState {
+            daemon: Some(*self),
+            count,
+            dnd,
+        })
+    }
+}
+
+/// State of the notification daemon. Emitted by [Notifications] service via
+/// [SignalNames::Notifications]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NotificationsState {
+    /// Daemon the state is received from. `None` if there is no supported daemon
+    pub daemon: Option<Daemon>,
+    /// Amount of pending notifications
+    pub count: u32,
+    /// Whether do-not-disturb mode is active
+    pub dnd: bool,
+}
+
+impl NotificationsState {
+    /// Toggle do-not-disturb mode of the daemon. Does nothing without a daemon
+    pub fn toggle_dnd(&self) -> zbus::Result<()> {
+        match self.daemon {
+            Some(daemon @ Daemon::Swaync) => daemon.proxy()?.call_noreply("ToggleDnd", &()),
+            Some(daemon @ Daemon::Dunst) => {
+                Ok(daemon.proxy()?.set_property("paused", !self.dnd)?)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Service that tracks pending notifications and do-not-disturb mode of a notification daemon.
+/// Without a supported daemon, or a session bus, an empty state is emitted instead of errors,
+/// the daemon is looked for again on every update.
+pub struct Notifications {
+    settings: ProcessSettings,
+
+    last_state: RefCell<Option<NotificationsState>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Notifications {
+    fn query(&self) -> NotificationsState {
+        let known = self.last_state.borrow().and_then(|state| state.daemon);
+
+        // Daemon may have been replaced since the last update
+        known
+            .and_then(|daemon| daemon.query().ok())
+            .or_else(|| Daemon::find().ok().flatten()?.query().ok())
+            .unwrap_or_default()
+    }
+
+    fn emit(&self, state: NotificationsState) {
+        let mut last_state = self.last_state.borrow_mut();
+        if last_state.as_ref() == Some(&state) {
+            return;
+        }
+
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::Notifications)
+            .or_default()
+            .clone();
+        signal.emit(&state);
+        *last_state = Some(state);
+    }
+}
+
+impl Service for Notifications {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::Notifications)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Notifications".to_string()));
+        }
+
+        self.emit(self.query());
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Notifications".to_string()));
+        }
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
+            return Ok(());
+        }
+        *last_update = Local::now();
+
+        self.emit(self.query());
+
+        Ok(())
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for Notifications {
+    type Settings = ProcessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(Notifications {
+            settings,
+            last_state: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
@@ -0,0 +1,233 @@
+//! Active power profile of [power-profiles-daemon](https://gitlab.freedesktop.org/upower/power-profiles-daemon)
+
+use std::{cell::RefCell, collections::HashMap, fmt::Display, rc::Rc, time::Instant};
+
+use chrono::{DateTime, Local};
+use zbus::{blocking::Proxy, zvariant::OwnedValue};
+
+use crate::{
+    root::Environment,
+    services::{dbus::system_connection, ProcessSettings, Service, ServiceError, ServiceNew},
+    util::{schedule, signals::SignalNames},
+};
+
+/// Bus names and paths of the daemon. Older versions only provide the second one
+const DAEMON_NAMES: [(&str, &str); 2] = [
+    (
+        "org.freedesktop.UPower.PowerProfiles",
+        "/org/freedesktop/UPower/PowerProfiles",
+    ),
+    ("net.hadess.PowerProfiles", "/net/hadess/PowerProfiles"),
+];
+
+/// Power profile supported by the daemon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    PowerSaver,
+    Balanced,
+    Performance,
+}
+
+impl Profile {
+    /// All profiles from the most power saving one
+    pub const ALL: [Profile; 3] = [Profile::PowerSaver, Profile::Balanced, Profile::Performance];
+
+    /// Parse name of a profile used by the daemon. `None` for unknown profiles
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "power-saver" => Some(Profile::PowerSaver),
+            "balanced" => Some(Profile::Balanced),
+            "performance" => Some(Profile::Performance),
+            _ => None,
+        }
+    }
+
+    /// Name of the profile used by the daemon
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Profile::PowerSaver => "power-saver",
+            Profile::Balanced => "balanced",
+            Profile::Performance => "performance",
+        }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Extract profile names from `Profiles` property of the daemon. Unknown profiles are skipped
+pub fn parse_profiles(profiles: &[HashMap<String, OwnedValue>]) -> Vec<Profile> {
+    profiles
+        .iter()
+        .filter_map(|profile| {
+            let name = String::try_from(profile.get("Profile")?.try_clone().ok()?).ok()?;
+            Profile::parse(&name)
+        })
+        .collect()
+}
+
+/// State of power-profiles-daemon. Emitted by [PowerProfile] service via
+/// [SignalNames::PowerProfile]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PowerProfileState {
+    /// Active profile. `None` if the daemon is not running
+    pub profile: Option<Profile>,
+    /// Profiles available on this machine
+    pub available: Vec<Profile>,
+}
+
+impl PowerProfileState {
+    /// Available profile following the active one, wrapping around to the most power saving one.
+    /// `None` if the daemon is not running
+    pub fn next(&self) -> Option<Profile> {
+        let active = self.profile?;
+        Profile::ALL
+            .iter()
+            .cycle()
+            .skip_while(|profile| **profile != active)
+            .skip(1)
+            .take(Profile::ALL.len())
+            .find(|profile| self.available.contains(profile))
+            .copied()
+    }
+
+    /// Switch the daemon to the [next](PowerProfileState::next) profile. Does nothing without a
+    /// daemon
+    pub fn cycle(&self) -> zbus::Result<()> {
+        let Some(next) = self.next() else {
+            return Ok(());
+        };
+
+        for (name, path) in DAEMON_NAMES {
+            if let Ok(proxy) = proxy(name, path) {
+                if proxy.get_property::<String>("ActiveProfile").is_ok() {
+                    return Ok(proxy.set_property("ActiveProfile", next.as_str())?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn proxy(name: &'static str, path: &'static str) -> zbus::Result<Proxy<'static>> {
+    zbus::blocking::proxy::Builder::new(system_connection()?)
+        .destination(name)?
+        .path(path)?
+        .interface(name)?
+        .cache_properties(zbus::proxy::CacheProperties::No)
+        .build()
+}
+
+/// Service that tracks the active power profile. Without a running daemon, or a system bus, a
+/// state without a profile is emitted instead of errors.
+pub struct PowerProfile {
+    settings: ProcessSettings,
+
+    last_state: RefCell<Option<PowerProfileState>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl PowerProfile {
+    fn query() -> PowerProfileState {
+        DAEMON_NAMES
+            .iter()
+            .find_map(|(name, path)| {
+                let proxy = proxy(name, path).ok()?;
+                let active: String = proxy.get_property("ActiveProfile").ok()?;
+                let profiles: Vec<HashMap<String, OwnedValue>> =
+                    proxy.get_property("Profiles").unwrap_or_default();
+
+                Some(PowerProfileState {
+                    profile: Profile::parse(&active),
+                    available: parse_profiles(&profiles),
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    fn emit(&self, state: PowerProfileState) {
+        let mut last_state = self.last_state.borrow_mut();
+        if last_state.as_ref() == Some(&state) {
+            return;
+        }
+
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::PowerProfile)
+            .or_default()
+            .clone();
+        signal.emit(&state);
+        *last_state = Some(state);
+    }
+}
+
+impl Service for PowerProfile {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::PowerProfile)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("PowerProfile".to_string()));
+        }
+
+        self.emit(PowerProfile::query());
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("PowerProfile".to_string()));
+        }
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
+            return Ok(());
+        }
+        *last_update = Local::now();
+
+        self.emit(PowerProfile::query());
+
+        Ok(())
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for PowerProfile {
+    type Settings = ProcessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(PowerProfile {
+            settings,
+            last_state: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
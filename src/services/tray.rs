@@ -0,0 +1,400 @@
+//! System tray implementing the host side of the
+//! [StatusNotifierItem](https://www.freedesktop.org/wiki/Specifications/StatusNotifierItem/)
+//! protocol. If no other program provides `org.kde.StatusNotifierWatcher`, [Tray] service serves
+//! the watcher itself.
+
+use std::{
+    cell::{Cell, RefCell},
+    process,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use chrono::{DateTime, Local};
+use zbus::{
+    blocking::{fdo::DBusProxy, Proxy},
+    interface,
+    message::Header,
+    names::BusName,
+};
+
+use crate::{
+    root::Environment,
+    services::{dbus::connection, ProcessSettings, Service, ServiceError, ServiceNew},
+    util::{schedule, signals::SignalNames, Color},
+};
+
+pub const WATCHER_NAME: &str = "org.kde.StatusNotifierWatcher";
+const WATCHER_PATH: &str = "/StatusNotifierWatcher";
+const ITEM_INTERFACE: &str = "org.kde.StatusNotifierItem";
+/// Object path of an item that registered with a bus name only
+pub const DEFAULT_ITEM_PATH: &str = "/StatusNotifierItem";
+
+/// Split a service string passed to `RegisterStatusNotifierItem` into a bus name and an object
+/// path. Items may register with a bus name, an object path, or both, e.g.
+/// `org.example.App/StatusNotifierItem`. `sender` is used as a bus name if only a path is provided.
+///
+/// # Examples
+/// ```
+/// use capybar::services::tray::parse_service;
+///
+/// assert_eq!(
+///     parse_service("/org/ayatana/NotificationItem/app", Some(":1.42")),
+///     Some((":1.42".to_string(), "/org/ayatana/NotificationItem/app".to_string())),
+/// );
+/// ```
+pub fn parse_service(service: &str, sender: Option<&str>) -> Option<(String, String)> {
+    match service.find('/') {
+        Some(0) => Some((sender?.to_string(), service.to_string())),
+        Some(i) => Some((service[..i].to_string(), service[i..].to_string())),
+        None if service.is_empty() => None,
+        None => Some((service.to_string(), DEFAULT_ITEM_PATH.to_string())),
+    }
+}
+
+/// Icon of a tray item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrayIcon {
+    pub width: usize,
+    pub height: usize,
+    /// Pixels in ARGB32 format with network byte order
+    pub argb: Vec<u8>,
+}
+
+impl TrayIcon {
+    /// Pick the smallest icon that is at least `size` pixels high, or the largest one if all of
+    /// them are smaller. Icons with inconsistent sizes are skipped.
+    pub fn best(icons: &[TrayIcon], size: usize) -> Option<&TrayIcon> {
+        let valid = icons
+            .iter()
+            .filter(|i| i.width > 0 && i.height > 0 && i.argb.len() == i.width * i.height * 4);
+
+        valid
+            .clone()
+            .filter(|i| i.height >= size)
+            .min_by_key(|i| i.height)
+            .or_else(|| valid.max_by_key(|i| i.height))
+    }
+
+    /// Color of a pixel of the icon
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let id = (x + y * self.width) * 4;
+        let [a, r, g, b]: [u8; 4] = self.argb.get(id..id + 4)?.try_into().ok()?;
+        Some(Color::from_rgba(r, g, b, a))
+    }
+}
+
+/// Item registered in the tray
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrayItem {
+    /// Bus name of the item
+    pub destination: String,
+    /// Object path of the item
+    pub path: String,
+
+    pub id: String,
+    pub title: String,
+    /// Name of the icon in the icon theme
+    pub icon_name: String,
+    /// Icon pixmaps in different sizes
+    pub icons: Vec<TrayIcon>,
+}
+
+impl TrayItem {
+    /// Method of the item called by a click with a linux input event code `button`. Left button
+    /// activates the item, right one asks for it's menu and middle one calls secondary action.
+    pub fn method_for(button: u32) -> Option<&'static str> {
+        match button {
+            0x110 => Some("Activate"),
+            0x111 => Some("ContextMenu"),
+            0x112 => Some("SecondaryActivate"),
+            _ => None,
+        }
+    }
+
+    /// Forward a click with `button` at position `(x, y)` to the item
+    pub fn click(&self, button: u32, x: i32, y: i32) -> zbus::Result<()> {
+        let Some(method) = TrayItem::method_for(button) else {
+            return Ok(());
+        };
+
+        self.proxy()?.call_noreply(method, &(x, y))
+    }
+
+    fn proxy(&self) -> zbus::Result<Proxy<'static>> {
+        zbus::blocking::proxy::Builder::new(connection()?)
+            .destination(self.destination.clone())?
+            .path(self.path.clone())?
+            .interface(ITEM_INTERFACE)?
+            .cache_properties(zbus::proxy::CacheProperties::No)
+            .build()
+    }
+
+    /// Query properties of an item
+    fn query(destination: &str, path: &str) -> zbus::Result<TrayItem> {
+        let mut item = TrayItem {
+            destination: destination.to_string(),
+            path: path.to_string(),
+            id: String::new(),
+            title: String::new(),
+            icon_name: String::new(),
+            icons: Vec::new(),
+        };
+
+        // Id is mandatory, failing to get it means the item is gone
+        let proxy = item.proxy()?;
+        item.id = proxy.get_property("Id")?;
+        item.title = proxy.get_property("Title").unwrap_or_default();
+        item.icon_name = proxy.get_property("IconName").unwrap_or_default();
+        item.icons = proxy
+            .get_property::<Vec<(i32, i32, Vec<u8>)>>("IconPixmap")
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(width, height, argb)| TrayIcon {
+                width: width.max(0) as usize,
+                height: height.max(0) as usize,
+                argb,
+            })
+            .collect();
+
+        Ok(item)
+    }
+}
+
+/// Items of the tray. Emitted by [Tray] service via [SignalNames::Tray]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TrayState {
+    pub items: Vec<TrayItem>,
+}
+
+/// `org.kde.StatusNotifierWatcher` served when no other program provides it
+struct Watcher {
+    /// Registered items as `bus name` + `object path`
+    items: Arc<Mutex<Vec<String>>>,
+}
+
+#[interface(name = "org.kde.StatusNotifierWatcher")]
+impl Watcher {
+    fn register_status_notifier_item(&self, service: &str, #[zbus(header)] header: Header<'_>) {
+        let sender = header.sender().map(|s| s.as_str());
+        let Some((destination, path)) = parse_service(service, sender) else {
+            return;
+        };
+
+        let item = format!("{destination}{path}");
+        let mut items = self.items.lock().unwrap();
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+
+    fn register_status_notifier_host(&self, _service: &str) {}
+
+    #[zbus(property)]
+    fn registered_status_notifier_items(&self) -> Vec<String> {
+        self.items.lock().unwrap().clone()
+    }
+
+    #[zbus(property)]
+    fn is_status_notifier_host_registered(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn protocol_version(&self) -> i32 {
+        0
+    }
+}
+
+/// Service that tracks items of the system tray
+pub struct Tray {
+    settings: ProcessSettings,
+
+    /// Items registered in the watcher served by the service
+    items: Arc<Mutex<Vec<String>>>,
+    /// Whether the service serves the watcher itself
+    own_watcher: Cell<bool>,
+
+    last_state: RefCell<Option<TrayState>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Tray {
+    fn error(e: zbus::Error) -> ServiceError {
+        ServiceError::Custom("Tray".to_string(), e.into())
+    }
+
+    /// Serve the watcher if there is none and register as a host
+    fn connect(&self) -> zbus::Result<()> {
+        let connection = connection()?;
+
+        let watcher: BusName = WATCHER_NAME.try_into()?;
+        if !DBusProxy::new(connection)?.name_has_owner(watcher)? {
+            connection.object_server().at(
+                WATCHER_PATH,
+                Watcher {
+                    items: Arc::clone(&self.items),
+                },
+            )?;
+            connection.request_name(WATCHER_NAME)?;
+            self.own_watcher.set(true);
+        }
+
+        let host = Tray::host_name();
+        connection.request_name(host.as_str())?;
+        self.watcher()?
+            .call_noreply("RegisterStatusNotifierHost", &(host.as_str(),))
+    }
+
+    /// Stop serving the watcher so items can register with another host, and release the host
+    /// name
+    fn disconnect(&self) -> zbus::Result<()> {
+        let connection = connection()?;
+
+        if self.own_watcher.replace(false) {
+            connection
+                .object_server()
+                .remove::<Watcher, _>(WATCHER_PATH)?;
+            connection.release_name(WATCHER_NAME)?;
+        }
+
+        connection.release_name(Tray::host_name())?;
+        Ok(())
+    }
+
+    fn host_name() -> String {
+        format!("org.kde.StatusNotifierHost-{}", process::id())
+    }
+
+    fn watcher(&self) -> zbus::Result<Proxy<'static>> {
+        zbus::blocking::proxy::Builder::new(connection()?)
+            .destination(WATCHER_NAME)?
+            .path(WATCHER_PATH)?
+            .interface(WATCHER_NAME)?
+            .cache_properties(zbus::proxy::CacheProperties::No)
+            .build()
+    }
+
+    fn query(&self) -> zbus::Result<TrayState> {
+        let registered: Vec<String> = match self.own_watcher.get() {
+            true => self.items.lock().unwrap().clone(),
+            false => self
+                .watcher()?
+                .get_property("RegisteredStatusNotifierItems")?,
+        };
+
+        let mut state = TrayState::default();
+        for service in registered {
+            let Some((destination, path)) = parse_service(&service, None) else {
+                continue;
+            };
+
+            match TrayItem::query(&destination, &path) {
+                Ok(item) => state.items.push(item),
+                // Items of other watchers are removed by them
+                Err(_) if self.own_watcher.get() => {
+                    self.items.lock().unwrap().retain(|i| *i != service);
+                }
+                Err(_) => {}
+            }
+        }
+
+        Ok(state)
+    }
+
+    fn emit(&self, state: TrayState) {
+        let mut last_state = self.last_state.borrow_mut();
+        if last_state.as_ref() == Some(&state) {
+            return;
+        }
+
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::Tray)
+            .or_default()
+            .clone();
+        signal.emit(&state);
+        *last_state = Some(state);
+    }
+}
+
+impl Service for Tray {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::Tray)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Tray".to_string()));
+        }
+
+        self.connect().map_err(Tray::error)?;
+        let state = self.query().map_err(Tray::error)?;
+        self.emit(state);
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Tray".to_string()));
+        }
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
+            return Ok(());
+        }
+        *last_update = Local::now();
+
+        let state = self.query().map_err(Tray::error)?;
+        self.emit(state);
+
+        Ok(())
+    }
+
+    fn teardown(&self) -> Result<(), ServiceError> {
+        self.disconnect().map_err(Tray::error)
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for Tray {
+    type Settings = ProcessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(Tray {
+            settings,
+            items: Arc::new(Mutex::new(Vec::new())),
+            own_watcher: Cell::new(false),
+            last_state: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
@@ -0,0 +1,171 @@
+use std::{cell::RefCell, process::Command, rc::Rc, time::Instant};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Local};
+
+use crate::{
+    root::Environment,
+    services::{ProcessSettings, Service, ServiceError, ServiceNew},
+    util::{schedule, signals::SignalNames},
+};
+
+/// Volume of the default output device. Emitted by [Volume] service via [SignalNames::Volume]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VolumeState {
+    /// Volume in percent. Can be higher than 100 if the device is amplified
+    pub volume: u32,
+    pub muted: bool,
+}
+
+/// Parse output of `wpctl get-volume @DEFAULT_AUDIO_SINK@`, e.g. `Volume: 0.45 [MUTED]`
+pub fn parse_wpctl(output: &str) -> Option<VolumeState> {
+    let mut words = output.split_whitespace();
+    if words.next()? != "Volume:" {
+        return None;
+    }
+
+    let volume: f64 = words.next()?.parse().ok()?;
+    Some(VolumeState {
+        volume: (volume * 100.0).round() as u32,
+        muted: words.any(|w| w == "[MUTED]"),
+    })
+}
+
+/// Parse outputs of `pactl get-sink-volume @DEFAULT_SINK@` and `pactl get-sink-mute
+/// @DEFAULT_SINK@`. Volume of the first channel is used.
+pub fn parse_pactl(volume: &str, mute: &str) -> Option<VolumeState> {
+    let volume = volume
+        .split_whitespace()
+        .find_map(|w| w.strip_suffix('%'))?
+        .parse()
+        .ok()?;
+
+    let muted = match mute.trim().strip_prefix("Mute:")?.trim() {
+        "yes" => true,
+        "no" => false,
+        _ => return None,
+    };
+
+    Some(VolumeState { volume, muted })
+}
+
+/// Stdout of a successful command. `None` if it could not be run or failed
+pub(super) fn run(command: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(command).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Service that tracks volume of the default output device. `wpctl` is queried first, `pactl` is
+/// used if PipeWire tools are not available.
+pub struct Volume {
+    settings: ProcessSettings,
+
+    last_state: RefCell<Option<VolumeState>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Volume {
+    fn query() -> Result<VolumeState, ServiceError> {
+        if let Some(state) =
+            run("wpctl", &["get-volume", "@DEFAULT_AUDIO_SINK@"]).and_then(|o| parse_wpctl(&o))
+        {
+            return Ok(state);
+        }
+
+        run("pactl", &["get-sink-volume", "@DEFAULT_SINK@"])
+            .zip(run("pactl", &["get-sink-mute", "@DEFAULT_SINK@"]))
+            .and_then(|(volume, mute)| parse_pactl(&volume, &mute))
+            .ok_or_else(|| {
+                ServiceError::Custom(
+                    "Volume".to_string(),
+                    anyhow!("Neither wpctl nor pactl reported the volume"),
+                )
+            })
+    }
+}
+
+impl Service for Volume {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::Volume)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Volume".to_string()));
+        }
+
+        let state = Volume::query()?;
+        *self.last_state.borrow_mut() = Some(state);
+
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::Volume)
+            .or_default()
+            .clone();
+        signal.emit(&state);
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Volume".to_string()));
+        }
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update < self.settings.update_rate.as_delta() {
+            return Ok(());
+        }
+        *last_update = Local::now();
+
+        let state = Volume::query()?;
+        let mut last_state = self.last_state.borrow_mut();
+        if *last_state != Some(state) {
+            *last_state = Some(state);
+
+            let signal = self.env.as_ref().unwrap().signals.borrow()[&SignalNames::Volume].clone();
+            signal.emit(&state);
+        }
+
+        Ok(())
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for Volume {
+    type Settings = ProcessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(Volume {
+            settings,
+            last_state: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
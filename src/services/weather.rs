@@ -0,0 +1,309 @@
+//! Current weather conditions fetched over HTTP. Responses of [wttr.in](https://wttr.in) in
+//! `j1` format and of [Open-Meteo](https://open-meteo.com) forecasts with current conditions are
+//! understood.
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration as StdDuration, Instant},
+};
+
+use chrono::{DateTime, Local};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::{
+    root::Environment,
+    services::{Service, ServiceError, ServiceNew},
+    util::{schedule, signals::SignalNames, UpdateRate},
+};
+
+/// How long a single request may take
+const TIMEOUT: StdDuration = StdDuration::from_secs(30);
+
+/// How often a finished request is checked for while it is running
+const POLL_RATE: StdDuration = StdDuration::from_millis(250);
+
+#[derive(Error, Debug)]
+pub enum WeatherError {
+    #[error(transparent)]
+    Http(#[from] ureq::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Response has neither wttr.in nor Open-Meteo format")]
+    UnknownFormat,
+}
+
+/// Weather condition, reduced to what can be told apart by an icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Condition {
+    Clear,
+    PartlyCloudy,
+    Cloudy,
+    Fog,
+    Drizzle,
+    Rain,
+    Snow,
+    Thunderstorm,
+    Unknown,
+}
+
+impl Condition {
+    /// Condition of a [WMO code](https://open-meteo.com/en/docs#weathervariables) used by
+    /// Open-Meteo
+    pub fn from_wmo(code: u64) -> Self {
+        match code {
+            0 => Condition::Clear,
+            1 | 2 => Condition::PartlyCloudy,
+            3 => Condition::Cloudy,
+            45 | 48 => Condition::Fog,
+            51..=57 => Condition::Drizzle,
+            61..=67 | 80..=82 => Condition::Rain,
+            71..=77 | 85 | 86 => Condition::Snow,
+            95..=99 => Condition::Thunderstorm,
+            _ => Condition::Unknown,
+        }
+    }
+
+    /// Condition of a WorldWeatherOnline code used by wttr.in
+    pub fn from_wwo(code: u64) -> Self {
+        match code {
+            113 => Condition::Clear,
+            116 => Condition::PartlyCloudy,
+            119 | 122 => Condition::Cloudy,
+            143 | 248 | 260 => Condition::Fog,
+            185 | 263 | 266 | 281 | 284 => Condition::Drizzle,
+            176 | 293..=314 | 353..=359 => Condition::Rain,
+            179 | 182 | 227 | 230 | 317..=350 | 362..=377 => Condition::Snow,
+            200 | 386..=395 => Condition::Thunderstorm,
+            _ => Condition::Unknown,
+        }
+    }
+
+    /// Human readable name of the condition
+    pub fn name(&self) -> &'static str {
+        match self {
+            Condition::Clear => "Clear",
+            Condition::PartlyCloudy => "Partly cloudy",
+            Condition::Cloudy => "Cloudy",
+            Condition::Fog => "Fog",
+            Condition::Drizzle => "Drizzle",
+            Condition::Rain => "Rain",
+            Condition::Snow => "Snow",
+            Condition::Thunderstorm => "Thunderstorm",
+            Condition::Unknown => "Unknown",
+        }
+    }
+}
+
+/// Current weather. Emitted by [Weather] service via [SignalNames::Weather] as
+/// `Option<WeatherState>`, `None` if the last request failed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeatherState {
+    /// Temperature in degrees Celsius
+    pub temperature: f64,
+    pub condition: Condition,
+}
+
+/// Number that may be sent as a string, as wttr.in does
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        value => value.as_f64(),
+    }
+}
+
+/// Parse a response of wttr.in or Open-Meteo. Open-Meteo temperatures requested in Fahrenheit
+/// are converted to Celsius.
+pub fn parse(body: &str) -> Result<WeatherState, WeatherError> {
+    let json: Value = serde_json::from_str(body)?;
+
+    if let Some(current) = json["current_condition"].get(0) {
+        let temperature = number(&current["temp_C"]).ok_or(WeatherError::UnknownFormat)?;
+        let code = number(&current["weatherCode"]).ok_or(WeatherError::UnknownFormat)?;
+
+        return Ok(WeatherState {
+            temperature,
+            condition: Condition::from_wwo(code as u64),
+        });
+    }
+
+    // `current` is returned for `current=temperature_2m,weather_code`, `current_weather` for the
+    // older `current_weather=true`
+    let (current, units, temperature, code) = if json["current"].is_object() {
+        ("current", "current_units", "temperature_2m", "weather_code")
+    } else {
+        (
+            "current_weather",
+            "current_weather_units",
+            "temperature",
+            "weathercode",
+        )
+    };
+
+    let temperature = json[current][temperature]
+        .as_f64()
+        .ok_or(WeatherError::UnknownFormat)?;
+    let code = json[current][code]
+        .as_u64()
+        .ok_or(WeatherError::UnknownFormat)?;
+
+    let temperature = match json[units]["temperature_2m"]
+        .as_str()
+        .or(json[units]["temperature"].as_str())
+    {
+        Some("°F") => (temperature - 32.0) * 5.0 / 9.0,
+        _ => temperature,
+    };
+
+    Ok(WeatherState {
+        temperature,
+        condition: Condition::from_wmo(code),
+    })
+}
+
+/// Fetch and parse the current weather from `url`. Blocks until the request is done
+pub fn fetch(url: &str) -> Result<WeatherState, WeatherError> {
+    let body = ureq::get(url)
+        .config()
+        .timeout_global(Some(TIMEOUT))
+        .build()
+        .call()?
+        .body_mut()
+        .read_to_string()?;
+
+    parse(&body)
+}
+
+/// Settings of a [Weather] service
+#[derive(Debug, Clone)]
+pub struct WeatherServiceSettings {
+    /// Url current weather is fetched from
+    pub url: String,
+    /// How often the weather is fetched
+    pub update_rate: UpdateRate,
+}
+
+/// Service that periodically fetches current weather. Requests are made on a separate thread so
+/// the bar is never blocked by the network, the result is emitted once it arrives.
+pub struct Weather {
+    settings: WeatherServiceSettings,
+
+    /// Result of a running request
+    pending: RefCell<Option<Receiver<Result<WeatherState, WeatherError>>>>,
+
+    last_state: RefCell<Option<Option<WeatherState>>>,
+    last_update: RefCell<DateTime<Local>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Weather {
+    fn request(&self) {
+        let (sender, receiver) = mpsc::channel();
+        let url = self.settings.url.clone();
+
+        // Result is dropped if the service is gone before the request is done
+        thread::spawn(move || {
+            let _ = sender.send(fetch(&url));
+        });
+
+        *self.pending.borrow_mut() = Some(receiver);
+        *self.last_update.borrow_mut() = Local::now();
+    }
+
+    fn emit(&self, state: Option<WeatherState>) {
+        let mut last_state = self.last_state.borrow_mut();
+        if last_state.as_ref() == Some(&state) {
+            return;
+        }
+
+        let signal = self
+            .env
+            .as_ref()
+            .unwrap()
+            .signals
+            .borrow_mut()
+            .entry(SignalNames::Weather)
+            .or_default()
+            .clone();
+        signal.emit(&state);
+        *last_state = Some(state);
+    }
+}
+
+impl Service for Weather {
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), ServiceError> {
+        self.env = Some(Rc::clone(&env));
+        env.signals
+            .borrow_mut()
+            .entry(SignalNames::Weather)
+            .or_default();
+
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Weather".to_string()));
+        }
+
+        self.request();
+
+        Ok(())
+    }
+
+    fn run(&self) -> Result<(), ServiceError> {
+        if self.env.is_none() {
+            return Err(ServiceError::RunWithNoEnv("Weather".to_string()));
+        }
+
+        let received = match self.pending.borrow().as_ref().map(Receiver::try_recv) {
+            Some(Ok(result)) => Some(result.ok()),
+            Some(Err(TryRecvError::Empty)) => return Ok(()),
+            Some(Err(TryRecvError::Disconnected)) => Some(None),
+            None => None,
+        };
+
+        if let Some(state) = received {
+            *self.pending.borrow_mut() = None;
+            self.emit(state);
+        }
+
+        if Local::now() - *self.last_update.borrow() >= self.settings.update_rate.as_delta() {
+            self.request();
+        }
+
+        Ok(())
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        if self.pending.borrow().is_some() {
+            return Some(Instant::now() + POLL_RATE);
+        }
+
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        ))
+    }
+}
+
+impl ServiceNew for Weather {
+    type Settings = WeatherServiceSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, ServiceError>
+    where
+        Self: Sized,
+    {
+        Ok(Weather {
+            settings,
+            pending: RefCell::new(None),
+            last_state: RefCell::new(None),
+            last_update: RefCell::new(DateTime::default()),
+            env,
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::{Duration, Instant};
+
+/// Value that can be linearly interpolated by [Animated]
+pub trait Interpolate: Copy {
+    /// Value between `self` (`t = 0`) and `other` (`t = 1`)
+    fn interpolate(self, other: Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for f64 {
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t as f64
+    }
+}
+
+/// Value gliding towards a target over a fixed duration. Retargeting mid-animation starts the new
+/// animation from the currently displayed value, so there are no jumps.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, Instant};
+/// use capybar::util::animation::Animated;
+///
+/// let mut value = Animated::new(0.0f32, Duration::from_millis(100));
+/// let start = Instant::now();
+/// value.set_at(10.0, start);
+///
+/// assert_eq!(value.value_at(start + Duration::from_millis(50)), 5.0);
+/// assert_eq!(value.value_at(start + Duration::from_millis(200)), 10.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Animated<T: Interpolate> {
+    from: T,
+    to: T,
+    /// Moment the current animation started. `None` while resting at initial value
+    start: Option<Instant>,
+    duration: Duration,
+}
+
+impl<T: Interpolate> Animated<T> {
+    /// Create an animated value resting at `value`
+    pub fn new(value: T, duration: Duration) -> Self {
+        Self {
+            from: value,
+            to: value,
+            start: None,
+            duration,
+        }
+    }
+
+    /// Start gliding towards `target` from now
+    pub fn set(&mut self, target: T) {
+        self.set_at(target, Instant::now());
+    }
+
+    /// Start gliding towards `target` from the provided moment
+    pub fn set_at(&mut self, target: T, now: Instant) {
+        self.from = self.value_at(now);
+        self.to = target;
+        self.start = Some(now);
+    }
+
+    /// Value the animation is heading to
+    pub fn target(&self) -> T {
+        self.to
+    }
+
+    /// Current value of the animation
+    pub fn value(&self) -> T {
+        self.value_at(Instant::now())
+    }
+
+    /// Value of the animation at provided moment
+    pub fn value_at(&self, now: Instant) -> T {
+        self.from.interpolate(self.to, self.progress(now))
+    }
+
+    /// Whether the animation reached it's target
+    pub fn is_finished(&self) -> bool {
+        self.progress(Instant::now()) >= 1.0
+    }
+
+    fn progress(&self, now: Instant) -> f32 {
+        let Some(start) = self.start else {
+            return 1.0;
+        };
+
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+
+        let elapsed = now.saturating_duration_since(start);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+}
@@ -1,9 +1,47 @@
-use std::fmt::Display;
+use std::{cell::RefCell, collections::BTreeMap, fmt::Display};
 
-use serde::Deserialize;
+use serde::{de::Visitor, Deserialize, Serialize};
+
+/// Named colors of a config, see [with_palette]
+pub type Palette = BTreeMap<String, Color>;
+
+thread_local! {
+    /// Palette colors are resolved with while deserializing
+    static PALETTE: RefCell<Option<Palette>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with names of `palette` accepted in place of colors, e.g. to deserialize a config
+/// after reading it's palette. Names are resolved while [Color] is deserialized, so
+/// deserialized values do not remember them.
+///
+/// # Examples
+/// ```
+/// use capybar::util::{with_palette, Color, Palette};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Settings {
+///     background: Color,
+/// }
+///
+/// let palette = Palette::from([("base".to_string(), Color::from_hex(0x1e1e2eff))]);
+/// let settings: Settings =
+///     with_palette(&palette, || toml::from_str(r#"background = "base""#)).unwrap();
+/// assert_eq!(settings.background, Color::from_hex(0x1e1e2eff));
+/// ```
+pub fn with_palette<T>(palette: &Palette, f: impl FnOnce() -> T) -> T {
+    let previous = PALETTE.with(|p| p.replace(Some(palette.clone())));
+    let result = f();
+    PALETTE.with(|p| *p.borrow_mut() = previous);
+    result
+}
 
 /// Color structure used in capy. Color is stored as an rgba value.
-#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+///
+/// In configs color can be written either as an integer (`0x1e1e2eff`), as a hex string
+/// (`"#1e1e2eff"`, `"#1e1e2e"` for full alpha) or as a name from the config's
+/// [palette](with_palette). Color is always serialized as a `#rrggbbaa` string.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Color(u32);
 
 impl Display for Color {
@@ -58,6 +96,19 @@ impl Color {
         ))
     }
 
+    /// Create color from bytes in `[r, g, b, a]` order. Unlike [Color::from_le_bytes] and
+    /// [Color::from_be_bytes] the order does not depend on the integer representation.
+    pub const fn from_rgba_bytes(bytes: [u8; 4]) -> Self {
+        Self::from_rgba(bytes[0], bytes[1], bytes[2], bytes[3])
+    }
+
+    /// Channels of the color in `[r, g, b, a]` order. Note that [crate::util::Drawer] writes
+    /// pixels to the wayland buffer in a different order, see [crate::util::drawer::Canvas].
+    pub const fn to_rgba_bytes(self) -> [u8; 4] {
+        let [r, g, b, a] = self.0.to_be_bytes();
+        [r, g, b, a]
+    }
+
     pub const fn to_be_bytes(self) -> [u8; 4] {
         self.0.to_be_bytes()
     }
@@ -135,4 +186,192 @@ impl Color {
             (a * 255.0).floor() as u8,
         )
     }
+
+    /// Create an opaque color from hue in degrees, saturation and lightness in `0.0..=1.0`. Hue
+    /// is wrapped around 360, saturation and lightness are clamped.
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Color {
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        Self::from_hue_chroma(h, chroma, l - chroma / 2.0)
+    }
+
+    /// Create an opaque color from hue in degrees, saturation and value in `0.0..=1.0`. Hue is
+    /// wrapped around 360, saturation and value are clamped.
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Color {
+        let s = s.clamp(0.0, 1.0);
+        let v = v.clamp(0.0, 1.0);
+
+        let chroma = v * s;
+        Self::from_hue_chroma(h, chroma, v - chroma)
+    }
+
+    fn from_hue_chroma(h: f32, chroma: f32, min: f32) -> Color {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let channel = |c: f32| ((c + min).clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color::from_rgba(channel(r), channel(g), channel(b), 255)
+    }
+
+    /// Hue in degrees, saturation and lightness in `0.0..=1.0`. Alpha is ignored
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let r = self.r() as f32 / 255.0;
+        let g = self.g() as f32 / 255.0;
+        let b = self.b() as f32 / 255.0;
+
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let l = (max + min) / 2.0;
+
+        if chroma == 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let h = if max == r {
+            60.0 * ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / chroma + 2.0)
+        } else {
+            60.0 * ((r - g) / chroma + 4.0)
+        };
+        let s = chroma / (1.0 - (2.0 * l - 1.0).abs());
+
+        (h, s, l)
+    }
+
+    /// Increase lightness of the color by `amount` in `0.0..=1.0`, keeping hue, saturation and
+    /// alpha. Result is clamped to white.
+    pub fn lighten(&self, amount: f32) -> Color {
+        let (h, s, l) = self.to_hsl();
+        let mut color = Color::from_hsl(h, s, l + amount);
+        color.set_a(self.a());
+        color
+    }
+
+    /// Decrease lightness of the color by `amount` in `0.0..=1.0`, keeping hue, saturation and
+    /// alpha. Result is clamped to black.
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+
+    /// Linearly interpolate every channel including alpha between `a` and `b`. `t` is clamped to
+    /// `0.0..=1.0`, where 0 gives `a` and 1 gives `b`.
+    pub fn lerp(a: &Color, b: &Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let a = a.to_rgba_bytes();
+        let b = b.to_rgba_bytes();
+
+        let channel = |i: usize| (a[i] as f32 + (b[i] as f32 - a[i] as f32) * t).round() as u8;
+        Color::from_rgba(channel(0), channel(1), channel(2), channel(3))
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&format!("#{:0>8x}", self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct ColorVisitor;
+
+        impl Visitor<'_> for ColorVisitor {
+            type Value = Color;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str(
+                    "rgba color as an integer, a \"#rrggbb\"/\"#rrggbbaa\" string or a palette name",
+                )
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match u32::try_from(v) {
+                    Ok(hex) => Ok(Color(hex)),
+                    Err(_) => Err(E::invalid_value(serde::de::Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match u32::try_from(v) {
+                    Ok(hex) => Ok(Color(hex)),
+                    Err(_) => Err(E::invalid_value(serde::de::Unexpected::Signed(v), &self)),
+                }
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                let invalid = || E::invalid_value(serde::de::Unexpected::Str(v), &self);
+
+                let Some(hex) = v.strip_prefix('#') else {
+                    return PALETTE.with(|palette| match &*palette.borrow() {
+                        Some(palette) => palette.get(v).copied().ok_or_else(|| {
+                            let names: Vec<&str> = palette.keys().map(String::as_str).collect();
+                            E::custom(format!(
+                                "unknown palette color `{v}`, palette has: [{}]",
+                                names.join(", ")
+                            ))
+                        }),
+                        None => Err(invalid()),
+                    });
+                };
+                if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(invalid());
+                }
+
+                match hex.len() {
+                    6 => Ok(Color(
+                        (u32::from_str_radix(hex, 16).map_err(|_| invalid())? << 8) | 0xFF,
+                    )),
+                    8 => Ok(Color(u32::from_str_radix(hex, 16).map_err(|_| invalid())?)),
+                    _ => Err(invalid()),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Color {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Color".into()
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "anyOf": [
+                { "type": "integer", "minimum": 0, "maximum": u32::MAX },
+                { "type": "string", "pattern": "^#([0-9a-fA-F]{6}|[0-9a-fA-F]{8})$" },
+                { "type": "string", "pattern": "^[^#]", "description": "Name of a palette color" }
+            ]
+        })
+    }
 }
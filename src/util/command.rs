@@ -0,0 +1,9 @@
+use std::{io, process::Command, thread};
+
+/// Launch a shell command in background via `sh -c`. Child process is waited for on a separate
+/// thread, so it does not stay a zombie after exiting.
+pub fn spawn(command: &str) -> io::Result<()> {
+    let mut child = Command::new("sh").arg("-c").arg(command).spawn()?;
+    thread::spawn(move || child.wait());
+    Ok(())
+}
@@ -3,8 +3,8 @@ use std::error::Error;
 
 use fontdue::{layout::GlyphPosition, Font};
 use smithay_client_toolkit::shm::{
-    slot::{Buffer, SlotPool},
-    Shm,
+    slot::{Buffer, CreateBufferError, SlotPool},
+    CreatePoolError, Shm,
 };
 use wayland_client::protocol::{wl_shm, wl_surface::WlSurface};
 
@@ -14,10 +14,25 @@ use super::Color;
 
 #[derive(Debug)]
 pub enum DrawerError {
+    /// Buffer of a size in bytes is too short to reach a byte at an index
     OutOfBounds(usize, usize),
+    /// Shared memory pool of a size in bytes could not be allocated
+    CreatePool(usize, CreatePoolError),
+    /// Buffer of a size in pixels could not be created in the pool
+    CreateBuffer((i32, i32), CreateBufferError),
+    /// Newly created buffer is not writable
+    BufferBusy,
 }
 
-impl Error for DrawerError {}
+impl Error for DrawerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::CreatePool(_, e) => Some(e),
+            Self::CreateBuffer(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 impl fmt::Display for DrawerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -25,86 +40,812 @@ impl fmt::Display for DrawerError {
             Self::OutOfBounds(size, idx) => {
                 write!(f, "Drawer out of bounds! Size is {size}, index is {idx}",)
             }
+            Self::CreatePool(size, e) => {
+                write!(f, "Could not allocate {size} bytes of shared memory: {e}")
+            }
+            Self::CreateBuffer((width, height), e) => {
+                write!(f, "Could not create a {width}x{height} buffer: {e}")
+            }
+            Self::BufferBusy => write!(f, "New buffer is still used by the compositor"),
         }
     }
 }
 
+/// Pool big enough for a single buffer of `width` x `height` pixels
+fn create_pool(shm: &mut Shm, width: i32, height: i32) -> Result<SlotPool, DrawerError> {
+    let size = (width.max(0) as usize) * (height.max(0) as usize) * 4;
+    SlotPool::new(size, shm).map_err(|e| DrawerError::CreatePool(size, e))
+}
+
+/// Whether pixel at `(x, y)` lies inside of a `width` x `height` rectangle with corners rounded
+/// by `radius`. Pixel centers are tested against quarter circles in the corners.
+fn rounded_rect_contains(x: usize, y: usize, width: usize, height: usize, radius: usize) -> bool {
+    if x >= width || y >= height {
+        return false;
+    }
+
+    let radius = radius.min(width / 2).min(height / 2) as f32;
+    let distance = |pos: usize, size: usize| {
+        let pos = pos as f32 + 0.5;
+        if pos < radius {
+            radius - pos
+        } else if pos > size as f32 - radius {
+            pos - (size as f32 - radius)
+        } else {
+            0.0
+        }
+    };
+
+    let (dx, dy) = (distance(x, width), distance(y, height));
+    dx * dx + dy * dy <= radius * radius
+}
+
+/// Pixel buffer in wayland's `Argb8888` format. Every pixel is stored as little endian
+/// `[b, g, r, a]` bytes with color channels premultiplied by alpha, as compositors expect.
+///
+/// Canvas does not depend on wayland, so it can be used to render into any byte buffer.
+///
+/// Widgets draw in logical pixels. On a canvas with a [scale](Canvas::set_scale) above 1 every
+/// logical pixel covers `scale` x `scale` pixels of the buffer, while glyphs are rasterized at
+/// the scaled size, so text stays sharp on HiDPI outputs.
+pub struct Canvas<'a> {
+    bytes: &'a mut [u8],
+    width: usize,
+    height: usize,
+
+    /// Alpha of every drawn color is multiplied by it, see [Canvas::set_opacity]
+    opacity: f32,
+
+    /// Buffer pixels per logical pixel, see [Canvas::set_scale]
+    scale: usize,
+}
+
+impl<'a> Canvas<'a> {
+    /// Wrap a buffer of `width * height * 4` bytes. Pixels missing from a shorter buffer are
+    /// skipped while drawing, see [Canvas::try_new] to reject it instead
+    pub fn new(bytes: &'a mut [u8], width: usize, height: usize) -> Self {
+        Canvas {
+            bytes,
+            width,
+            height,
+            opacity: 1.0,
+            scale: 1,
+        }
+    }
+
+    /// Same as [Canvas::new], but fails with [DrawerError::OutOfBounds] if the buffer is shorter
+    /// than `width * height * 4` bytes
+    pub fn try_new(bytes: &'a mut [u8], width: usize, height: usize) -> Result<Self, DrawerError> {
+        let size = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(4))
+            .unwrap_or(usize::MAX);
+        if bytes.len() < size {
+            return Err(DrawerError::OutOfBounds(bytes.len(), size));
+        }
+
+        Ok(Self::new(bytes, width, height))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// Draw every logical pixel as `scale` x `scale` pixels of the buffer. Positions and sizes
+    /// passed to drawing methods stay logical, while [Canvas::width], [Canvas::height] and
+    /// [Canvas::pixel] refer to pixels of the buffer. Scale of 0 is treated as 1
+    pub fn set_scale(&mut self, scale: usize) {
+        self.scale = scale.max(1);
+    }
+
+    /// Fade everything drawn afterwards: alpha of colors, including glyph coverage, is
+    /// multiplied by `opacity` before blending. Clamped to `0.0..=1.0`
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    /// Make the whole canvas fully transparent
+    pub fn clear(&mut self) {
+        self.bytes.fill(0);
+    }
+
+    /// Raw premultiplied `[b, g, r, a]` bytes of a pixel at global position
+    pub fn pixel(&self, x: usize, y: usize) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let id = (x + y * self.width) * 4;
+        self.bytes.get(id..id + 4)?.try_into().ok()
+    }
+
+    /// Blend color over a pixel at global position using premultiplied "over" operator
+    fn blend(&mut self, x: usize, y: usize, mut color: Color) {
+        if self.opacity < 1.0 {
+            color.set_a((color.a() as f32 * self.opacity).round() as u8);
+        }
+
+        if x >= self.width || y >= self.height || color.a() == 0 {
+            return;
+        }
+
+        let id = (x + y * self.width) * 4;
+        let Some(pixel) = self.bytes.get_mut(id..id + 4) else {
+            return;
+        };
+
+        let alpha = color.a() as u32;
+        let premultiply = |c: u8| (c as u32 * alpha + 127) / 255;
+        let source = [
+            premultiply(color.b()),
+            premultiply(color.g()),
+            premultiply(color.r()),
+            alpha,
+        ];
+
+        for (dst, src) in pixel.iter_mut().zip(source) {
+            *dst = (src + (*dst as u32 * (255 - alpha) + 127) / 255) as u8;
+        }
+    }
+
+    /// Put a single colored pixel in a relative space. Canvas converts local position in a widget
+    /// to global buffer position using provided `WidgetData`. Pixels outside of the canvas are
+    /// skipped.
+    pub fn draw_pixel(&mut self, data: &WidgetData, pos: (usize, usize), color: Color) {
+        let x = data.position.0.checked_add(pos.0);
+        let y = data.position.1.checked_add(pos.1);
+
+        let (Some(x), Some(y)) = (x, y) else {
+            return;
+        };
+        if self.scale == 1 {
+            self.blend(x, y, color);
+            return;
+        }
+
+        let (Some(x), Some(y)) = (x.checked_mul(self.scale), y.checked_mul(self.scale)) else {
+            return;
+        };
+        for dx in 0..self.scale {
+            for dy in 0..self.scale {
+                self.blend(x + dx, y + dy, color);
+            }
+        }
+    }
+
+    /// End of a `len` long span starting at `start` relative to the widget, clipped to the right
+    /// or bottom edge of the canvas. Keeps loops over huge or mispositioned shapes short
+    fn clip_end(&self, data: &WidgetData, start: usize, len: usize, horizontal: bool) -> usize {
+        let (offset, size) = match horizontal {
+            true => (data.position.0, self.width.div_ceil(self.scale)),
+            false => (data.position.1, self.height.div_ceil(self.scale)),
+        };
+
+        start.saturating_add(len).min(size.saturating_sub(offset))
+    }
+
+    /// Fill a `width` x `height` rectangle at widget's position with corners rounded by `radius`.
+    /// Radius is clamped to half of the shorter side, radius of 0 fills a plain rectangle.
+    pub fn draw_rounded_rect(
+        &mut self,
+        data: &WidgetData,
+        width: usize,
+        height: usize,
+        radius: usize,
+        color: Color,
+    ) {
+        self.fill_rounded_rect(data, width, height, radius, |_, _| color);
+    }
+
+    /// Same as [Canvas::draw_rounded_rect], but color of every pixel is provided by `color_at`
+    /// from it's position inside of the rectangle, e.g. to draw gradients.
+    pub fn fill_rounded_rect(
+        &mut self,
+        data: &WidgetData,
+        width: usize,
+        height: usize,
+        radius: usize,
+        color_at: impl Fn(usize, usize) -> Color,
+    ) {
+        for x in 0..self.clip_end(data, 0, width, true) {
+            for y in 0..self.clip_end(data, 0, height, false) {
+                if rounded_rect_contains(x, y, width, height, radius) {
+                    self.draw_pixel(data, (x, y), color_at(x, y));
+                }
+            }
+        }
+    }
+
+    /// Draw an outline of `thickness` pixels along the edge of a rounded rectangle, see
+    /// [Canvas::draw_rounded_rect]. Inner edge is rounded by `radius - thickness`.
+    pub fn draw_rounded_border(
+        &mut self,
+        data: &WidgetData,
+        width: usize,
+        height: usize,
+        radius: usize,
+        thickness: usize,
+        color: Color,
+    ) {
+        let inner_width = width.saturating_sub(2 * thickness);
+        let inner_height = height.saturating_sub(2 * thickness);
+        let inner_radius = radius.saturating_sub(thickness);
+
+        for x in 0..self.clip_end(data, 0, width, true) {
+            for y in 0..self.clip_end(data, 0, height, false) {
+                if !rounded_rect_contains(x, y, width, height, radius) {
+                    continue;
+                }
+
+                let inside = x >= thickness
+                    && y >= thickness
+                    && rounded_rect_contains(
+                        x - thickness,
+                        y - thickness,
+                        inner_width,
+                        inner_height,
+                        inner_radius,
+                    );
+                if !inside {
+                    self.draw_pixel(data, (x, y), color);
+                }
+            }
+        }
+    }
+
+    /// Fill a `width` x `height` rectangle with top left corner at `pos` relative to the widget
+    pub fn draw_rect_filled(
+        &mut self,
+        data: &WidgetData,
+        pos: (usize, usize),
+        width: usize,
+        height: usize,
+        color: Color,
+    ) {
+        for x in pos.0..self.clip_end(data, pos.0, width, true) {
+            for y in pos.1..self.clip_end(data, pos.1, height, false) {
+                self.draw_pixel(data, (x, y), color);
+            }
+        }
+    }
+
+    /// Draw an outline of `thickness` pixels along the inner edge of a `width` x `height`
+    /// rectangle with top left corner at `pos` relative to the widget
+    pub fn draw_rect_outline(
+        &mut self,
+        data: &WidgetData,
+        pos: (usize, usize),
+        width: usize,
+        height: usize,
+        thickness: usize,
+        color: Color,
+    ) {
+        let thickness = thickness.min(width / 2).min(height / 2);
+        if thickness == 0 {
+            return;
+        }
+
+        // Sides are shortened by the top and bottom edges, so corners are not blended twice
+        let side = height - 2 * thickness;
+        self.draw_rect_filled(data, pos, width, thickness, color);
+        let (right, bottom) = (
+            pos.0.saturating_add(width - thickness),
+            pos.1.saturating_add(height - thickness),
+        );
+        let top = pos.1.saturating_add(thickness);
+        self.draw_rect_filled(data, (pos.0, bottom), width, thickness, color);
+        self.draw_rect_filled(data, (pos.0, top), thickness, side, color);
+        self.draw_rect_filled(data, (right, top), thickness, side, color);
+    }
+
+    /// Draw a line of `thickness` pixels between centers of pixels `from` and `to` relative to
+    /// the widget. Ends of the line are rounded.
+    pub fn draw_line(
+        &mut self,
+        data: &WidgetData,
+        from: (usize, usize),
+        to: (usize, usize),
+        color: Color,
+        thickness: usize,
+    ) {
+        self.stroke_line(data, from, to, color, thickness, false);
+    }
+
+    /// Same as [Canvas::draw_line], but edges of the line are smoothed by blending partially
+    /// covered pixels.
+    pub fn draw_line_antialiased(
+        &mut self,
+        data: &WidgetData,
+        from: (usize, usize),
+        to: (usize, usize),
+        color: Color,
+        thickness: usize,
+    ) {
+        self.stroke_line(data, from, to, color, thickness, true);
+    }
+
+    fn stroke_line(
+        &mut self,
+        data: &WidgetData,
+        from: (usize, usize),
+        to: (usize, usize),
+        color: Color,
+        thickness: usize,
+        antialiased: bool,
+    ) {
+        if thickness == 0 {
+            return;
+        }
+
+        let half = thickness as f32 / 2.0;
+        let reach = half.ceil() as usize + 1;
+        let (x0, y0) = (from.0 as f32, from.1 as f32);
+        let (dx, dy) = (to.0 as f32 - x0, to.1 as f32 - y0);
+        let length = dx * dx + dy * dy;
+
+        let x_end = self.clip_end(data, from.0.max(to.0), reach + 1, true);
+        let y_end = self.clip_end(data, from.1.max(to.1), reach + 1, false);
+        for x in from.0.min(to.0).saturating_sub(reach)..x_end {
+            for y in from.1.min(to.1).saturating_sub(reach)..y_end {
+                let (px, py) = (x as f32 - x0, y as f32 - y0);
+                // Position of the closest point of the segment, from 0 at `from` to 1 at `to`
+                let t = match length {
+                    0.0 => 0.0,
+                    _ => ((px * dx + py * dy) / length).clamp(0.0, 1.0),
+                };
+                let distance = ((px - t * dx).powi(2) + (py - t * dy).powi(2)).sqrt();
+
+                let coverage = match antialiased {
+                    true => (half + 0.5 - distance).clamp(0.0, 1.0),
+                    false if distance <= half => 1.0,
+                    false => 0.0,
+                };
+                self.draw_pixel_coverage(data, (x, y), color, coverage);
+            }
+        }
+    }
+
+    /// Fill a circle of `radius` pixels around the center of pixel `center` relative to the
+    /// widget. Edge of the circle is anti-aliased.
+    pub fn draw_circle(
+        &mut self,
+        data: &WidgetData,
+        center: (usize, usize),
+        radius: f32,
+        color: Color,
+    ) {
+        let reach = radius.max(0.0).ceil() as usize + 1;
+        let x_end = self.clip_end(data, center.0, reach + 1, true);
+        let y_end = self.clip_end(data, center.1, reach + 1, false);
+        for x in center.0.saturating_sub(reach)..x_end {
+            for y in center.1.saturating_sub(reach)..y_end {
+                let dx = x as f32 - center.0 as f32;
+                let dy = y as f32 - center.1 as f32;
+                let coverage = (radius + 0.5 - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+                self.draw_pixel_coverage(data, (x, y), color, coverage);
+            }
+        }
+    }
+
+    /// Draw a `width` x `height` image of non-premultiplied `[r, g, b, a]` bytes, row by row,
+    /// with top left corner at the widget position. Every pixel is blended over the canvas.
+    pub fn draw_image(&mut self, data: &WidgetData, rgba: &[u8], width: usize, height: usize) {
+        if width == 0 {
+            return;
+        }
+
+        for (i, pixel) in rgba
+            .chunks_exact(4)
+            .take(width.saturating_mul(height))
+            .enumerate()
+        {
+            let color = Color::from_rgba(pixel[0], pixel[1], pixel[2], pixel[3]);
+            self.draw_pixel(data, (i % width, i / width), color);
+        }
+    }
+
+    /// Draw a pixel with color's alpha multiplied by `coverage` from 0 to 1
+    fn draw_pixel_coverage(
+        &mut self,
+        data: &WidgetData,
+        pos: (usize, usize),
+        mut color: Color,
+        coverage: f32,
+    ) {
+        if coverage <= 0.0 {
+            return;
+        }
+
+        color.set_a((color.a() as f32 * coverage).round() as u8);
+        self.draw_pixel(data, pos, color);
+    }
+
+    /// Draw a glyph from font. Canvas converts local position in a widget to global buffer
+    /// position using provided `WidgetData`. Glyph coverage is multiplied with color's alpha.
+    pub fn draw_glyph(
+        &mut self,
+        data: &WidgetData,
+        glyph: &GlyphPosition,
+        font: &Font,
+        color: Color,
+    ) {
+        self.draw_glyph_clipped(data, glyph, font, color, 0, usize::MAX);
+    }
+
+    /// Same as [Canvas::draw_glyph], but the glyph is moved left by `shift` pixels and only
+    /// columns from 0 to `clip_width` of the widget are drawn.
+    pub fn draw_glyph_clipped(
+        &mut self,
+        data: &WidgetData,
+        glyph: &GlyphPosition,
+        font: &Font,
+        mut color: Color,
+        shift: isize,
+        clip_width: usize,
+    ) {
+        // Layout already accounts for the advance of blank glyphs, there is nothing to draw
+        if glyph.char_data.is_whitespace() || glyph.width == 0 || glyph.height == 0 {
+            return;
+        }
+
+        let clip_width = clip_width.min(isize::MAX as usize) as isize;
+        let left = glyph.x as isize - shift;
+        if left >= clip_width || left + glyph.width as isize <= 0 {
+            return;
+        }
+
+        if self.scale > 1 {
+            self.draw_glyph_scaled(data, glyph, font, color, shift, clip_width);
+            return;
+        }
+
+        let (metrics, bitmap) = font.rasterize_indexed(glyph.key.glyph_index, glyph.key.px);
+
+        let alpha = color.a() as u32;
+        for x in 0..glyph.width.min(metrics.width) {
+            let local_x = left + x as isize;
+            if local_x < 0 || local_x >= clip_width {
+                continue;
+            }
+
+            for y in 0..glyph.height.min(metrics.height) {
+                let Some(&coverage) = bitmap.get(x + y * metrics.width) else {
+                    continue;
+                };
+                color.set_a(((coverage as u32 * alpha + 127) / 255) as u8);
+
+                // Glyphs above the top of the widget are clipped
+                let local_y = y as isize + glyph.y as isize;
+                if local_y < 0 {
+                    continue;
+                }
+                self.draw_pixel(data, (local_x as usize, local_y as usize), color);
+            }
+        }
+    }
+
+    /// Rasterize a glyph at [Canvas::scale] times it's size and blend it pixel by pixel of the
+    /// buffer. Glyph is placed at the scaled pen position and baseline of the layout
+    fn draw_glyph_scaled(
+        &mut self,
+        data: &WidgetData,
+        glyph: &GlyphPosition,
+        font: &Font,
+        mut color: Color,
+        shift: isize,
+        clip_width: isize,
+    ) {
+        let scale = self.scale as isize;
+        let logical = font.metrics_indexed(glyph.key.glyph_index, glyph.key.px);
+        let (metrics, bitmap) =
+            font.rasterize_indexed(glyph.key.glyph_index, glyph.key.px * self.scale as f32);
+
+        // Layout places bitmaps at `floor(pen + xmin)` and `baseline + floor(-height - ymin)` of
+        // their bounds. Pen position is only known up to the floored pixel, so it's middle is used
+        let bounds = logical.bounds;
+        let pen = glyph.x + 0.5 - bounds.xmin - shift as f32;
+        let baseline = glyph.y - (-bounds.height - bounds.ymin).floor();
+        let scaled = metrics.bounds;
+        let left = (pen * scale as f32 + scaled.xmin).floor() as isize;
+        let top = (baseline * scale as f32).round() as isize
+            + (-scaled.height - scaled.ymin).floor() as isize;
+
+        let to_buffer = |pos: usize| (pos.min(isize::MAX as usize) as isize).saturating_mul(scale);
+        let origin = (to_buffer(data.position.0), to_buffer(data.position.1));
+        let clip_width = clip_width.saturating_mul(scale);
+
+        let alpha = color.a() as u32;
+        for x in 0..metrics.width {
+            let local_x = left + x as isize;
+            if local_x < 0 || local_x >= clip_width {
+                continue;
+            }
+
+            for y in 0..metrics.height {
+                // Glyphs above the top of the widget are clipped
+                let local_y = top + y as isize;
+                if local_y < 0 {
+                    continue;
+                }
+
+                let Some(&coverage) = bitmap.get(x + y * metrics.width) else {
+                    continue;
+                };
+                color.set_a(((coverage as u32 * alpha + 127) / 255) as u8);
+                self.blend(
+                    origin.0.saturating_add(local_x) as usize,
+                    origin.1.saturating_add(local_y) as usize,
+                    color,
+                );
+            }
+        }
+    }
+}
+
+/// Where a [Drawer] renders to
+#[derive(Debug)]
+enum Target {
+    /// Shared memory buffers attached to a wayland surface
+    Shm {
+        pool: SlotPool,
+        buffer: Option<Buffer>,
+    },
+    /// Plain bytes, see [Drawer::headless]
+    Memory(Vec<u8>),
+}
+
 /// Utility structure used to simplify drawing the widgets.
+///
+/// Drawer renders to shared memory buffers of `Argb8888` format: every pixel is written as
+/// premultiplied `[b, g, r, a]` bytes (see [Canvas]), while [Color] stores channels as `[r, g, b,
+/// a]`. A headless drawer renders the same bytes into memory instead.
 #[derive(Debug)]
 pub struct Drawer {
-    pool: SlotPool,
-    buffer: Option<Buffer>,
+    target: Target,
 
+    /// Logical size, buffers are [scale](Drawer::scale) times larger
     width: i32,
     height: i32,
+    /// Scale of the output the surface is shown on
+    scale: i32,
+
+    /// Areas changed since the last commit as logical `(x, y, width, height)`
+    damage: Vec<(usize, usize, usize, usize)>,
+
+    /// Opacity of canvases handed out, see [Canvas::set_opacity]
+    opacity: f32,
 }
 
 impl Drawer {
-    pub fn new(shm: &mut Shm, width: i32, height: i32) -> Self {
+    /// Drawer rendering to shared memory of `shm`. Fails if the memory can not be allocated
+    pub fn new(shm: &mut Shm, width: i32, height: i32) -> Result<Self, DrawerError> {
+        Ok(Drawer {
+            target: Target::Shm {
+                pool: create_pool(shm, width, height)?,
+                buffer: None,
+            },
+
+            width,
+            height,
+            scale: 1,
+
+            damage: Vec::new(),
+
+            opacity: 1.0,
+        })
+    }
+
+    /// Drawer rendering into memory without wayland, e.g. to draw widgets in tests. Rendered
+    /// pixels are available with [Drawer::bytes] or [Canvas::pixel].
+    pub fn headless(width: i32, height: i32) -> Self {
         Drawer {
-            pool: SlotPool::new((width * height * 4) as usize, shm).unwrap(),
-            buffer: None,
+            target: Target::Memory(vec![0; (width * height * 4) as usize]),
 
             width,
             height,
+            scale: 1,
+
+            damage: Vec::new(),
+
+            opacity: 1.0,
+        }
+    }
+
+    /// Whether the drawer renders into memory, see [Drawer::headless]
+    pub fn is_headless(&self) -> bool {
+        matches!(self.target, Target::Memory(_))
+    }
+
+    /// Allocate buffers of a new logical size. Drawer is left unchanged if that fails
+    pub fn update_sizes(
+        &mut self,
+        shm: &mut Shm,
+        width: i32,
+        height: i32,
+    ) -> Result<(), DrawerError> {
+        if let Target::Shm { pool, buffer } = &mut self.target {
+            *pool = create_pool(shm, width * self.scale, height * self.scale)?;
+            *buffer = None;
         }
+        self.resize(width, height);
+
+        Ok(())
     }
 
-    pub fn update_sizes(&mut self, shm: &mut Shm, width: i32, height: i32) {
+    /// Change size of a headless drawer. Drawers of wayland surfaces need new buffers from
+    /// [Drawer::update_sizes] instead
+    pub fn resize(&mut self, width: i32, height: i32) {
         self.height = height;
         self.width = width;
-        self.buffer = None;
-        self.pool = SlotPool::new((width * height * 4) as usize, shm).unwrap();
+        if let Target::Memory(bytes) = &mut self.target {
+            let (width, height) = (width * self.scale, height * self.scale);
+            *bytes = vec![0; (width * height * 4) as usize];
+        }
+        self.damage_all();
+    }
+
+    /// Buffer pixels per logical pixel, see [Canvas::set_scale]
+    pub fn scale(&self) -> i32 {
+        self.scale
+    }
+
+    /// Allocate buffers for a new scale of the output, e.g. when the surface moves to a HiDPI
+    /// monitor. Drawer is left unchanged if that fails
+    pub fn update_scale(&mut self, shm: &mut Shm, scale: i32) -> Result<(), DrawerError> {
+        let old = std::mem::replace(&mut self.scale, scale.max(1));
+        if old == self.scale {
+            return Ok(());
+        }
+
+        self.update_sizes(shm, self.width, self.height)
+            .inspect_err(|_| self.scale = old)
+    }
+
+    /// Change scale of a headless drawer. Drawers of wayland surfaces need new buffers from
+    /// [Drawer::update_scale] instead
+    pub fn set_scale(&mut self, scale: i32) {
+        self.scale = scale.max(1);
+        self.resize(self.width, self.height);
+    }
+
+    pub fn width(&self) -> usize {
+        self.width as usize
+    }
+
+    pub fn height(&self) -> usize {
+        self.height as usize
     }
 
-    /// Commit buffer to a surface
-    pub fn commit(&self, surface: &WlSurface) {
-        if let Some(buffer) = &self.buffer {
+    /// Rendered pixels of a headless drawer in the format of [Canvas]. `None` for drawers of
+    /// wayland surfaces, as their buffers belong to the compositor once committed
+    pub fn bytes(&self) -> Option<&[u8]> {
+        match &self.target {
+            Target::Memory(bytes) => Some(bytes),
+            Target::Shm { .. } => None,
+        }
+    }
+
+    /// Mark an area as changed, so it is damaged on the next commit
+    pub fn damage(&mut self, area: (usize, usize, usize, usize)) {
+        if area.2 > 0 && area.3 > 0 {
+            self.damage.push(area);
+        }
+    }
+
+    /// Mark the whole buffer as changed
+    pub fn damage_all(&mut self) {
+        self.damage((0, 0, self.width as usize, self.height as usize));
+    }
+
+    /// Whether anything changed since the last commit
+    pub fn is_damaged(&self) -> bool {
+        !self.damage.is_empty()
+    }
+
+    /// Forget changed areas without committing them, e.g. after a frame of a headless drawer
+    pub fn clear_damage(&mut self) {
+        self.damage.clear();
+    }
+
+    /// Damage changed areas and commit buffer to a surface
+    pub fn commit(&mut self, surface: &WlSurface) {
+        let Target::Shm { buffer, .. } = &self.target else {
+            self.clear_damage();
+            return;
+        };
+
+        let scale = self.scale;
+        for (x, y, width, height) in self.damage.drain(..) {
+            surface.damage_buffer(
+                x as i32 * scale,
+                y as i32 * scale,
+                width as i32 * scale,
+                height as i32 * scale,
+            );
+        }
+
+        if let Some(buffer) = buffer {
+            surface.set_buffer_scale(scale);
             buffer.attach_to(surface).expect("buffer attach");
             surface.commit();
         }
     }
 
-    /// Put a single colored pixel in a relative space. Drawer converts local position in a widget
-    /// to global buffer position using provided `WidgetData`.
-    pub fn draw_pixel(&mut self, data: &WidgetData, pos: (usize, usize), color: Color) {
-        let buffer = self.buffer.get_or_insert_with(|| {
-            self.pool
-                .create_buffer(
-                    self.width,
-                    self.height,
-                    self.width * 4,
-                    wl_shm::Format::Argb8888,
-                )
-                .unwrap()
-                .0
-        });
-
-        let canvas = match self.pool.canvas(buffer) {
-            Some(canvas) => canvas,
-            None => {
-                let (second_buffer, canvas) = self
-                    .pool
-                    .create_buffer(
-                        self.width,
-                        self.height,
-                        self.width * 4,
-                        wl_shm::Format::Argb8888,
-                    )
-                    .expect("create buffer");
-                *buffer = second_buffer;
-                canvas
+    /// Get [Canvas] of a buffer that is currently not used by the compositor
+    pub fn canvas(&mut self) -> Result<Canvas<'_>, DrawerError> {
+        let (scale, opacity) = (self.scale, self.opacity);
+        let (width, height) = (self.width * scale, self.height * scale);
+        let (pool, buffer) = match &mut self.target {
+            Target::Shm { pool, buffer } => (pool, buffer),
+            Target::Memory(bytes) => {
+                let mut canvas = Canvas::try_new(bytes, width as usize, height as usize)?;
+                canvas.set_opacity(opacity);
+                canvas.set_scale(scale as usize);
+                return Ok(canvas);
             }
         };
 
-        let chunk_id = data.position.0 + pos.0 + (data.position.1 + pos.1) * self.width as usize;
+        let create_buffer = |pool: &mut SlotPool| {
+            pool.create_buffer(width, height, width * 4, wl_shm::Format::Argb8888)
+                .map(|(buffer, _)| buffer)
+                .map_err(|e| DrawerError::CreateBuffer((width, height), e))
+        };
+
+        let buffer = match buffer {
+            Some(buffer) => buffer,
+            None => buffer.insert(create_buffer(pool)?),
+        };
 
-        let chunk = canvas.chunks_exact_mut(4).nth(chunk_id);
-        if let Some(chunk) = chunk {
-            let array: &mut [u8; 4] = chunk.try_into().unwrap();
-            let c = Color::blend_colors(&Color::from_be_bytes(array), &color).to_be_bytes();
-            *array = [c[2], c[1], c[0], c[3]];
+        // Buffer is still used by the compositor, draw to a new one instead
+        if pool.canvas(buffer).is_none() {
+            *buffer = create_buffer(pool)?;
         }
+        let canvas = pool.canvas(buffer).ok_or(DrawerError::BufferBusy)?;
+
+        let mut canvas = Canvas::try_new(canvas, width as usize, height as usize)?;
+        canvas.set_opacity(opacity);
+        canvas.set_scale(scale as usize);
+        Ok(canvas)
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Fade everything drawn afterwards. See [Canvas::set_opacity]
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    /// Make the whole buffer fully transparent. Should be called before drawing a new frame so
+    /// areas without background are left for the compositor to blend.
+    pub fn clear(&mut self) -> Result<(), DrawerError> {
+        self.canvas()?.clear();
+        Ok(())
+    }
+
+    /// Put a single colored pixel in a relative space. Drawer converts local position in a widget
+    /// to global buffer position using provided `WidgetData`.
+    pub fn draw_pixel(
+        &mut self,
+        data: &WidgetData,
+        pos: (usize, usize),
+        color: Color,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?.draw_pixel(data, pos, color);
+        Ok(())
     }
 
     /// Draw a glyph from font. Drawer converts local position in a widget to global buf position
@@ -114,61 +855,150 @@ impl Drawer {
         data: &WidgetData,
         glyph: &GlyphPosition,
         font: &Font,
-        mut color: Color,
-    ) {
-        let buffer = self.buffer.get_or_insert_with(|| {
-            self.pool
-                .create_buffer(
-                    self.width,
-                    self.height,
-                    self.width * 4,
-                    wl_shm::Format::Argb8888,
-                )
-                .unwrap()
-                .0
-        });
-
-        let canvas = match self.pool.canvas(buffer) {
-            Some(canvas) => canvas,
-            None => {
-                let (second_buffer, canvas) = self
-                    .pool
-                    .create_buffer(
-                        self.width,
-                        self.height,
-                        self.width * 4,
-                        wl_shm::Format::Argb8888,
-                    )
-                    .expect("create buffer");
-                *buffer = second_buffer;
-                canvas
-            }
-        };
+        color: Color,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?.draw_glyph(data, glyph, font, color);
+        Ok(())
+    }
 
-        let bitmap = font
-            .rasterize_indexed(glyph.key.glyph_index, glyph.key.px)
-            .1;
-        if glyph.char_data.is_whitespace() {
-            return;
-        }
+    /// Draw a glyph moved left by `shift` pixels and clipped to `clip_width`. See
+    /// [Canvas::draw_glyph_clipped].
+    pub fn draw_glyph_clipped(
+        &mut self,
+        data: &WidgetData,
+        glyph: &GlyphPosition,
+        font: &Font,
+        color: Color,
+        shift: isize,
+        clip_width: usize,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?
+            .draw_glyph_clipped(data, glyph, font, color, shift, clip_width);
+        Ok(())
+    }
 
-        for x in 0..glyph.width {
-            for y in 0..glyph.height {
-                color.set_a(bitmap[x + y * glyph.width]);
+    /// Fill a rectangle at `pos` relative to the widget. See [Canvas::draw_rect_filled].
+    pub fn draw_rect_filled(
+        &mut self,
+        data: &WidgetData,
+        pos: (usize, usize),
+        width: usize,
+        height: usize,
+        color: Color,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?
+            .draw_rect_filled(data, pos, width, height, color);
+        Ok(())
+    }
 
-                let chunk_id = data.position.0
-                    + x
-                    + glyph.x as usize
-                    + (data.position.1 + y + glyph.y as usize) * self.width as usize;
+    /// Draw an outline of a rectangle at `pos` relative to the widget. See
+    /// [Canvas::draw_rect_outline].
+    pub fn draw_rect_outline(
+        &mut self,
+        data: &WidgetData,
+        pos: (usize, usize),
+        width: usize,
+        height: usize,
+        thickness: usize,
+        color: Color,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?
+            .draw_rect_outline(data, pos, width, height, thickness, color);
+        Ok(())
+    }
 
-                let chunk = canvas.chunks_exact_mut(4).nth(chunk_id);
-                if let Some(chunk) = chunk {
-                    let array: &mut [u8; 4] = chunk.try_into().unwrap();
+    /// Draw a line between two pixels relative to the widget. See [Canvas::draw_line].
+    pub fn draw_line(
+        &mut self,
+        data: &WidgetData,
+        from: (usize, usize),
+        to: (usize, usize),
+        color: Color,
+        thickness: usize,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?.draw_line(data, from, to, color, thickness);
+        Ok(())
+    }
 
-                    *array =
-                        Color::blend_colors(&Color::from_be_bytes(array), &color).to_be_bytes();
-                }
-            }
-        }
+    /// Draw an anti-aliased line between two pixels relative to the widget. See
+    /// [Canvas::draw_line_antialiased].
+    pub fn draw_line_antialiased(
+        &mut self,
+        data: &WidgetData,
+        from: (usize, usize),
+        to: (usize, usize),
+        color: Color,
+        thickness: usize,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?
+            .draw_line_antialiased(data, from, to, color, thickness);
+        Ok(())
+    }
+
+    /// Fill a circle around a pixel relative to the widget. See [Canvas::draw_circle].
+    pub fn draw_circle(
+        &mut self,
+        data: &WidgetData,
+        center: (usize, usize),
+        radius: f32,
+        color: Color,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?.draw_circle(data, center, radius, color);
+        Ok(())
+    }
+
+    /// Draw an image of non-premultiplied RGBA bytes at the widget position. See
+    /// [Canvas::draw_image].
+    pub fn draw_image(
+        &mut self,
+        data: &WidgetData,
+        rgba: &[u8],
+        width: usize,
+        height: usize,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?.draw_image(data, rgba, width, height);
+        Ok(())
+    }
+
+    pub fn draw_rounded_rect(
+        &mut self,
+        data: &WidgetData,
+        width: usize,
+        height: usize,
+        radius: usize,
+        color: Color,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?
+            .draw_rounded_rect(data, width, height, radius, color);
+        Ok(())
+    }
+
+    /// Fill a rounded rectangle with colors provided by `color_at`. See
+    /// [Canvas::fill_rounded_rect].
+    pub fn fill_rounded_rect(
+        &mut self,
+        data: &WidgetData,
+        width: usize,
+        height: usize,
+        radius: usize,
+        color_at: impl Fn(usize, usize) -> Color,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?
+            .fill_rounded_rect(data, width, height, radius, color_at);
+        Ok(())
+    }
+
+    pub fn draw_rounded_border(
+        &mut self,
+        data: &WidgetData,
+        width: usize,
+        height: usize,
+        radius: usize,
+        thickness: usize,
+        color: Color,
+    ) -> Result<(), DrawerError> {
+        self.canvas()?
+            .draw_rounded_border(data, width, height, radius, thickness, color);
+        Ok(())
     }
 }
@@ -1,5 +1,6 @@
 use std::{
     collections::HashMap,
+    path::{Path, PathBuf},
     sync::{LazyLock, Mutex, MutexGuard},
 };
 
@@ -7,77 +8,154 @@ use anyhow::Result;
 use fontconfig::Fontconfig;
 use thiserror::Error;
 
-/// Wrapper for fontconfig and fontdue. Only one exists per capybar instance and shared safly
-/// between difrent [crate::root::Root] instance
-pub struct FontsMap {
-    fontconfig: Fontconfig,
+/// Fontconfig is only used to find font files, so a single instance is shared by every
+/// [FontsMap]
+static FONTCONFIG: LazyLock<Option<Fontconfig>> = LazyLock::new(Fontconfig::new);
+
+/// Global map used before fonts were moved to [Environment](crate::root::Environment)
+static FONTS: LazyLock<FontsMap> = LazyLock::new(FontsMap::new);
 
+/// Fonts loaded with fontdue. Every [crate::root::Root] has it's own map shared by it's bars
+/// through [Environment::fonts](crate::root::Environment::fonts), so font ids only depend on the
+/// order fonts are added to it.
+#[derive(Default)]
+pub struct FontsMap {
     fonts_map: Mutex<HashMap<String, usize>>,
 
     fonts_vec: Mutex<Vec<fontdue::Font>>,
 }
 
-static FONTS: LazyLock<FontsMap> = LazyLock::new(|| FontsMap::new().unwrap());
-
 #[derive(Error, Debug)]
 pub enum FontsError {
     #[error(transparent)]
     IO(#[from] std::io::Error),
     #[error("Font {0} was not found")]
     FontNotFound(String),
+    #[error("Font {0} could not be parsed: {1}")]
+    InvalidFont(String, &'static str),
+}
+
+/// Find a file of a font by it's name using fontconfig
+pub fn find_by_name(name: &str) -> Result<PathBuf, FontsError> {
+    FONTCONFIG
+        .as_ref()
+        .and_then(|fc| fc.find(name, None))
+        .map(|font| font.path)
+        .ok_or_else(|| FontsError::FontNotFound(name.to_string()))
 }
 
 impl FontsMap {
-    fn new() -> Option<Self> {
-        let fc = Fontconfig::new()?;
-
-        Some(FontsMap {
-            fontconfig: fc,
-            fonts_map: Mutex::new(HashMap::new()),
-            fonts_vec: Mutex::new(Vec::new()),
-        })
+    /// Empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fonts map contains map of font name to index in vector
+    pub fn fonts_map(&self) -> MutexGuard<'_, HashMap<String, usize>> {
+        self.fonts_map.lock().unwrap()
+    }
+
+    /// Fonts vector contains all loaded fonts
+    pub fn fonts_vec(&self) -> MutexGuard<'_, Vec<fontdue::Font>> {
+        self.fonts_vec.lock().unwrap()
+    }
+
+    /// Id of a font added under `key`, i.e. it's name or path
+    pub fn id(&self, key: &str) -> Option<usize> {
+        self.fonts_map().get(key).copied()
+    }
+
+    /// Number of loaded fonts
+    pub fn len(&self) -> usize {
+        self.fonts_vec().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds font found by fontconfig and returns it's id in fonts vector. Font name is case
+    /// insensitive. Font gets added to fonts vector and map
+    pub fn add_font_by_name(&self, name: &str) -> Result<usize, FontsError> {
+        let path = find_by_name(name)?;
+        let bytes = std::fs::read(path)?;
+
+        self.add_font(name, bytes)
+    }
+
+    /// Adds font from a file and returns it's id in fonts vector. Font is stored in fonts map
+    /// under it's path, loading the same path again returns the same id.
+    pub fn add_font_by_path(&self, path: &Path) -> Result<usize, FontsError> {
+        let key = path.display().to_string();
+        if let Some(id) = self.id(&key) {
+            return Ok(id);
+        }
+
+        let bytes = std::fs::read(path)?;
+        self.add_font(&key, bytes)
+    }
+
+    fn add_font(&self, key: &str, bytes: Vec<u8>) -> Result<usize, FontsError> {
+        let font = fontdue::Font::from_bytes(
+            bytes,
+            fontdue::FontSettings {
+                ..Default::default()
+            },
+        )
+        .map_err(|e| FontsError::InvalidFont(key.to_string(), e))?;
+
+        let mut fonts_map = self.fonts_map();
+        let mut fonts_vec = self.fonts_vec();
+        let id = fonts_vec.len();
+        fonts_map.insert(key.to_string(), id);
+        fonts_vec.push(font);
+
+        Ok(id)
+    }
+
+    /// Replace all fonts with ones of `other`. Returns the replaced fonts
+    pub fn replace(&self, other: FontsMap) -> FontsMap {
+        let fonts_map = std::mem::replace(
+            &mut *self.fonts_map(),
+            other.fonts_map.into_inner().unwrap(),
+        );
+        let fonts_vec = std::mem::replace(
+            &mut *self.fonts_vec(),
+            other.fonts_vec.into_inner().unwrap(),
+        );
+
+        FontsMap {
+            fonts_map: Mutex::new(fonts_map),
+            fonts_vec: Mutex::new(fonts_vec),
+        }
     }
 }
 
+#[deprecated(note = "fonts are stored in `Environment::fonts`, use it's `FontsMap` instead")]
 pub fn get() -> &'static LazyLock<FontsMap> {
     &FONTS
 }
 
-/// Fonts map contains map of font name to index in vector
+/// Fonts map of the global [FontsMap]
+#[deprecated(note = "fonts are stored in `Environment::fonts`, use it's `FontsMap` instead")]
 pub fn fonts_map() -> MutexGuard<'static, HashMap<String, usize>> {
-    FONTS.fonts_map.lock().unwrap()
+    FONTS.fonts_map()
 }
 
-/// Fonts vector contains all loaded fonts
+/// Fonts vector of the global [FontsMap]
+#[deprecated(note = "fonts are stored in `Environment::fonts`, use it's `FontsMap` instead")]
 pub fn fonts_vec() -> MutexGuard<'static, Vec<fontdue::Font>> {
-    FONTS.fonts_vec.lock().unwrap()
+    FONTS.fonts_vec()
 }
 
-/// Adds font to current FontsMap instance. Font name is case insensitive. Font gets added to fonts
-/// vector and map
+/// Adds font to the global [FontsMap]. Widgets do not use it anymore
+#[deprecated(note = "use `FontsMap::add_font_by_name` of `Environment::fonts`")]
 pub fn add_font_by_name(name: &str) -> Result<(), FontsError> {
-    let font = match FONTS.fontconfig.find(name, None) {
-        Some(f) => f,
-        None => return Err(FontsError::FontNotFound(name.to_string())),
-    };
-
-    let bytes = match std::fs::read(font.path.as_path()) {
-        Ok(b) => b,
-        Err(e) => return Err(FontsError::IO(e)),
-    };
-
-    let font = fontdue::Font::from_bytes(
-        bytes,
-        fontdue::FontSettings {
-            ..Default::default()
-        },
-    )
-    .unwrap();
-
-    let mut fonts_map = FONTS.fonts_map.lock().unwrap();
-    let mut fonts_vec = FONTS.fonts_vec.lock().unwrap();
-    fonts_map.insert(name.to_string(), fonts_vec.len());
-    fonts_vec.push(font);
-
-    Ok(())
+    FONTS.add_font_by_name(name).map(|_| ())
+}
+
+/// Adds font from a file to the global [FontsMap]. Widgets do not use it anymore
+#[deprecated(note = "use `FontsMap::add_font_by_path` of `Environment::fonts`")]
+pub fn add_font_by_path(path: &Path) -> Result<usize, FontsError> {
+    FONTS.add_font_by_path(path)
 }
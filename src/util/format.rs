@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+/// Formatting rules for numeric values displayed by sensor widgets.
+///
+/// # Examples
+/// ```
+/// use capybar::util::format::NumberFormat;
+///
+/// let format = NumberFormat {
+///     decimals: 1,
+///     unit: "%".to_string(),
+///     unit_space: true,
+/// };
+/// assert_eq!(format.format(42.0), "42.0 %");
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NumberFormat {
+    /// Amount of digits displayed after the decimal point
+    #[serde(default)]
+    pub decimals: usize,
+
+    /// Unit appended to the value
+    #[serde(default)]
+    pub unit: String,
+
+    /// Whether to separate value and unit with a space
+    #[serde(default)]
+    pub unit_space: bool,
+}
+
+impl NumberFormat {
+    /// Whole percents without a space, e.g. `42%`
+    pub fn percent() -> Self {
+        Self {
+            decimals: 0,
+            unit: "%".to_string(),
+            unit_space: false,
+        }
+    }
+
+    /// `decimals` digits after the decimal point without a unit, e.g. for values followed by a
+    /// unit in a format string
+    pub fn decimals(decimals: usize) -> Self {
+        Self {
+            decimals,
+            ..Self::default()
+        }
+    }
+
+    /// Format value rounding it half away from zero to [NumberFormat::decimals] digits
+    pub fn format(&self, value: f64) -> String {
+        let factor = 10f64.powi(self.decimals as i32);
+        let rounded = (value * factor).round() / factor;
+
+        let separator = if self.unit_space && !self.unit.is_empty() {
+            " "
+        } else {
+            ""
+        };
+
+        format!("{:.*}{separator}{}", self.decimals, rounded, self.unit)
+    }
+}
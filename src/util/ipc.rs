@@ -0,0 +1,222 @@
+//! Unix socket for controlling a running bar. Clients send one command per line, e.g. `reload`,
+//! and get a line back for every command: `ok` or `error: <description>`. Commands with output,
+//! e.g. `dump`, send it's lines before `ok`.
+
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, ErrorKind, Read, Write},
+    os::{
+        fd::{AsFd, BorrowedFd},
+        unix::net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use thiserror::Error;
+
+/// Name of the socket inside of `$XDG_RUNTIME_DIR`
+pub const SOCKET_NAME: &str = "capybar.sock";
+
+/// Default path of the socket. `None` if `XDG_RUNTIME_DIR` is not set
+pub fn socket_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_RUNTIME_DIR").map(|dir| PathBuf::from(dir).join(SOCKET_NAME))
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum IpcError {
+    #[error("Empty command")]
+    Empty,
+    #[error("Unknown command `{0}`")]
+    UnknownCommand(String),
+    #[error("Command `{0}` does not take arguments")]
+    UnexpectedArguments(String),
+}
+
+/// Command accepted by the socket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Stop the bar
+    Quit,
+    /// Reload the config from the file the bar was started with
+    Reload,
+    /// Print computed geometry of all widgets, see [Widget::debug_tree](crate::widgets::Widget::debug_tree)
+    Dump,
+}
+
+impl FromStr for Command {
+    type Err = IpcError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut words = line.split_whitespace();
+        let name = words.next().ok_or(IpcError::Empty)?;
+
+        let command = match name {
+            "quit" => Command::Quit,
+            "reload" => Command::Reload,
+            "dump" => Command::Dump,
+            _ => return Err(IpcError::UnknownCommand(name.to_string())),
+        };
+
+        match words.next() {
+            Some(_) => Err(IpcError::UnexpectedArguments(name.to_string())),
+            None => Ok(command),
+        }
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::Quit => write!(f, "quit"),
+            Command::Reload => write!(f, "reload"),
+            Command::Dump => write!(f, "dump"),
+        }
+    }
+}
+
+/// Client connected to a [Server] with it's unfinished line
+struct Client {
+    stream: UnixStream,
+    buffer: Vec<u8>,
+}
+
+/// Listening socket. Never blocks, so it can be polled alongside the Wayland connection. The
+/// socket file is removed when the server is dropped.
+pub struct Server {
+    listener: UnixListener,
+    path: PathBuf,
+    clients: Vec<Client>,
+}
+
+impl Server {
+    /// Listen at `path`. A socket left by a bar that was not stopped cleanly is replaced, but
+    /// the one of a running bar is not.
+    pub fn bind(path: &Path) -> io::Result<Server> {
+        if path.exists() {
+            if UnixStream::connect(path).is_ok() {
+                return Err(io::Error::new(
+                    ErrorKind::AddrInUse,
+                    format!("Another bar is listening at {}", path.display()),
+                ));
+            }
+            fs::remove_file(path)?;
+        }
+
+        let listener = UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+
+        Ok(Server {
+            listener,
+            path: path.to_path_buf(),
+            clients: Vec::new(),
+        })
+    }
+
+    /// Descriptors to wait on for new connections and commands
+    pub fn fds(&self) -> Vec<BorrowedFd<'_>> {
+        std::iter::once(self.listener.as_fd())
+            .chain(self.clients.iter().map(|c| c.stream.as_fd()))
+            .collect()
+    }
+
+    /// Accept new clients and run every complete command they sent with `run`. Output returned
+    /// by `run` and it's result or a parsing error is sent back to the client.
+    pub fn handle(&mut self, mut run: impl FnMut(Command) -> Result<String, String>) {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    if stream.set_nonblocking(true).is_ok() {
+                        self.clients.push(Client {
+                            stream,
+                            buffer: Vec::new(),
+                        });
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            }
+        }
+
+        self.clients.retain_mut(|client| client.handle(&mut run));
+    }
+}
+
+impl Client {
+    /// Read available data and run complete commands. Returns `false` once the client is gone
+    fn handle(&mut self, run: &mut impl FnMut(Command) -> Result<String, String>) -> bool {
+        let mut chunk = [0; 1024];
+        let open = loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => break false,
+                Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break true,
+                Err(_) => return false,
+            }
+        };
+
+        // Last command does not need a line break if the client closed it's side
+        let mut end = self.buffer.len();
+        if open {
+            match self.buffer.iter().rposition(|b| *b == b'\n') {
+                Some(i) => end = i + 1,
+                None => return true,
+            }
+        }
+
+        let lines: Vec<u8> = self.buffer.drain(..end).collect();
+        for line in String::from_utf8_lossy(&lines).lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let reply = match line.parse::<Command>() {
+                Ok(command) => run(command),
+                Err(e) => Err(e.to_string()),
+            };
+            let reply = match reply {
+                Ok(output) if output.is_empty() => "ok\n".to_string(),
+                Ok(output) => format!("{}\nok\n", output.trim_end_matches('\n')),
+                Err(e) => format!("error: {e}\n"),
+            };
+
+            // Replies are short, a client that does not read them is dropped
+            if self.stream.write_all(reply.as_bytes()).is_err() {
+                return false;
+            }
+        }
+
+        open
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Send `command` to the bar listening at `path` and wait for the reply. Returns output of the
+/// command, or an error with the description sent by the bar if the command failed.
+pub fn send(path: &Path, command: &Command) -> anyhow::Result<String> {
+    let mut stream = UnixStream::connect(path)?;
+    stream.write_all(format!("{command}\n").as_bytes())?;
+    stream.shutdown(std::net::Shutdown::Write)?;
+
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply)?;
+    let reply = reply.trim_end();
+    let (output, last) = match reply.rsplit_once('\n') {
+        Some((output, last)) => (output, last),
+        None => ("", reply),
+    };
+    match last {
+        "ok" => Ok(output.to_string()),
+        _ => Err(anyhow::anyhow!(reply
+            .strip_prefix("error: ")
+            .unwrap_or(reply)
+            .to_string())),
+    }
+}
@@ -1,9 +1,30 @@
 mod color;
-pub use color::Color;
+pub use color::{with_palette, Color, Palette};
+
+pub mod animation;
+
+pub mod command;
 
 pub mod drawer;
 pub use drawer::Drawer;
 
 pub mod fonts;
 
+pub mod format;
+
+pub mod ipc;
+
+pub mod resize;
+
+pub mod schedule;
+
+pub mod sighup;
+
 pub mod signals;
+
+pub mod terminate;
+
+pub mod visibility;
+
+mod update_rate;
+pub use update_rate::{UpdateRate, UpdateRateError};
@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+/// Decides when a surface following the size of it's content is resized. Growth is applied at
+/// once, so content is never clipped. Shrinking waits until content kept the smaller size for
+/// a while, so content jumping between two sizes, e.g. `9%` and `10%`, does not make the
+/// compositor reconfigure the surface every frame.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, Instant};
+/// use capybar::util::resize::ResizeDebounce;
+///
+/// let mut debounce = ResizeDebounce::new(Duration::from_secs(1));
+/// let now = Instant::now();
+/// assert_eq!(debounce.next_size((100, 20), (120, 20), now), Some((120, 20)));
+/// assert_eq!(debounce.next_size((120, 20), (100, 20), now), None);
+/// assert_eq!(
+///     debounce.next_size((120, 20), (100, 20), now + Duration::from_secs(1)),
+///     Some((100, 20))
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResizeDebounce {
+    delay: Duration,
+    /// Smaller size content wants and the moment it started wanting it
+    shrink: Option<((u32, u32), Instant)>,
+}
+
+impl ResizeDebounce {
+    /// Shrink surfaces once content kept the smaller size for `delay`
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            shrink: None,
+        }
+    }
+
+    /// Size a surface of `current` size should be changed to for content of `wanted` size, if it
+    /// should be changed at all. Dimensions that grow are applied at once, smaller ones only
+    /// after `wanted` did not change for the delay.
+    pub fn next_size(
+        &mut self,
+        current: (u32, u32),
+        wanted: (u32, u32),
+        now: Instant,
+    ) -> Option<(u32, u32)> {
+        if wanted == current {
+            self.shrink = None;
+            return None;
+        }
+
+        let grown = (current.0.max(wanted.0), current.1.max(wanted.1));
+        if grown == wanted {
+            self.shrink = None;
+            return Some(grown);
+        }
+
+        let since = match self.shrink {
+            Some((size, since)) if size == wanted => since,
+            _ => now,
+        };
+        if now.saturating_duration_since(since) >= self.delay {
+            self.shrink = None;
+            return Some(wanted);
+        }
+        self.shrink = Some((wanted, since));
+
+        (grown != current).then_some(grown)
+    }
+
+    /// Moment a pending shrink is applied, if content is smaller than it's surface
+    pub fn deadline(&self) -> Option<Instant> {
+        self.shrink.map(|(_, since)| since + self.delay)
+    }
+}
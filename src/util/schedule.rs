@@ -0,0 +1,42 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local, TimeDelta, Timelike};
+
+/// Moment `rate` after `last`, converted to a monotonic [Instant]. Moments in the past are
+/// returned as now.
+pub fn instant_after(last: DateTime<Local>, rate: TimeDelta) -> Instant {
+    let remaining = (last + rate - Local::now()).to_std().unwrap_or_default();
+    Instant::now() + remaining
+}
+
+/// Time left until the local time is a whole multiple of `step` since midnight, e.g. until the
+/// start of the next minute for a step of one minute.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use chrono::{Local, TimeDelta, TimeZone};
+/// use capybar::util::schedule::until_next;
+///
+/// let now = Local.with_ymd_and_hms(2025, 1, 1, 12, 30, 45).unwrap();
+/// assert_eq!(until_next(now, TimeDelta::minutes(1)), Duration::from_secs(15));
+/// ```
+pub fn until_next(now: DateTime<Local>, step: TimeDelta) -> Duration {
+    let Some(step) = step.num_nanoseconds().filter(|step| *step > 0) else {
+        return Duration::ZERO;
+    };
+
+    // Leap seconds are reported as nanoseconds above a second
+    let nanos = now.num_seconds_from_midnight() as i64 * 1_000_000_000
+        + now.nanosecond().min(999_999_999) as i64;
+
+    Duration::from_nanos((step - nanos % step) as u64)
+}
+
+/// Earliest of two optional deadlines. Missing deadlines are ignored.
+pub fn earliest(a: Option<Instant>, b: Option<Instant>) -> Option<Instant> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
@@ -0,0 +1,26 @@
+//! Process wide `SIGHUP` flag used to request config reload
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static RECEIVED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn handle(_: libc::c_int) {
+    RECEIVED.store(true, Ordering::SeqCst);
+}
+
+/// Install `SIGHUP` handler. Installing it several times is harmless
+pub fn install() -> std::io::Result<()> {
+    let handler = handle as extern "C" fn(libc::c_int) as libc::sighandler_t;
+
+    // SAFETY: handler only stores to an atomic, which is async-signal-safe
+    if unsafe { libc::signal(libc::SIGHUP, handler) } == libc::SIG_ERR {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `SIGHUP` was received since the last call
+pub fn take() -> bool {
+    RECEIVED.swap(false, Ordering::SeqCst)
+}
@@ -1,9 +1,16 @@
 use std::{
-    any::Any,
-    cell::{Ref, RefCell},
+    any::{type_name, Any},
+    cell::{Cell, Ref, RefCell},
+    marker::PhantomData,
+    rc::Rc,
 };
 
-type Callback = Box<dyn Fn(&dyn Any)>;
+use serde::{Deserialize, Serialize};
+
+type Callback = Rc<dyn Fn(&dyn Any)>;
+
+/// Identifier of a callback connected to a [Signal]. Unique within the signal it was returned by
+pub type ConnectionId = usize;
 
 /// Reactive communication channel for decoupled component interaction
 ///
@@ -18,7 +25,12 @@ type Callback = Box<dyn Fn(&dyn Any)>;
 ///
 /// ### Behavior Details
 /// - **Downcasting responsibility**: Receivers must validate and downcast values
-/// - **Callback persistence**: Handlers remain registered until signal destruction
+/// - **Callback persistence**: Handlers remain registered until they are
+///   [disconnected](Signal::disconnect) or the signal is dropped
+/// - **Reentrancy**: Callbacks may emit, connect and disconnect, including on the signal that
+///   called them. Every emission calls the callbacks that were connected when it started
+/// - **Shared state**: Clones share callbacks and the last value. Clone a signal out of
+///   `signals` before emitting, so callbacks can borrow the map
 ///
 /// ### Usage Notes
 /// - Prefer `emit` for cloneable types requiring history
@@ -46,19 +58,17 @@ type Callback = Box<dyn Fn(&dyn Any)>;
 /// signal.emit(&42i32);
 /// assert_eq!(*tracker.borrow(), 42);
 /// ```
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Signal {
-    listeners: RefCell<Vec<Callback>>,
-    last_value: RefCell<Option<Box<dyn Any>>>,
+    listeners: Rc<RefCell<Vec<(ConnectionId, Callback)>>>,
+    last_value: Rc<RefCell<Option<Rc<dyn Any>>>>,
+    next_id: Rc<Cell<ConnectionId>>,
 }
 
 impl Signal {
     /// Creates a new, empty Signal instance
     pub fn new() -> Self {
-        Signal {
-            listeners: RefCell::new(Vec::new()),
-            last_value: RefCell::new(None),
-        }
+        Signal::default()
     }
     /// Registers a callback to be invoked on signal emissions
     ///
@@ -69,16 +79,36 @@ impl Signal {
     /// # Arguments
     /// * `callback` - Handler function that receives emitted data as `&dyn Any`
     ///
-    /// Note: Callbacks persist until the Signal is dropped
-    pub fn connect<F>(&self, callback: F)
+    /// Returns an id that can be passed to [disconnect](Signal::disconnect)
+    pub fn connect<F>(&self, callback: F) -> ConnectionId
     where
         F: Fn(&dyn Any) + 'static,
     {
-        if let Some(value) = &*self.last_value.borrow() {
-            callback(&**value);
+        // Value is not borrowed while the callback runs, so it can emit on this signal
+        let last_value = self.last_value.borrow().clone();
+        if let Some(value) = last_value {
+            callback(&*value);
         }
 
-        self.listeners.borrow_mut().push(Box::new(callback));
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.listeners.borrow_mut().push((id, Rc::new(callback)));
+        id
+    }
+
+    /// Removes all connected callbacks. Last emitted value is kept
+    pub fn disconnect_all(&self) {
+        self.listeners.borrow_mut().clear();
+    }
+
+    /// Ids of connected callbacks in the order they were connected
+    pub fn connection_ids(&self) -> Vec<ConnectionId> {
+        self.listeners.borrow().iter().map(|(id, _)| *id).collect()
+    }
+
+    /// Removes a callback registered with [connect](Signal::connect). Unknown ids are ignored.
+    pub fn disconnect(&self, id: ConnectionId) {
+        self.listeners.borrow_mut().retain(|(i, _)| *i != id);
     }
 
     /// Emits a value to all connected callbacks
@@ -93,8 +123,8 @@ impl Signal {
     /// - Your type is cheap to clone
     pub fn emit<T: Any + Clone>(&self, value: &T) {
         let cloned = (*value).clone();
-        *self.last_value.borrow_mut() = Some(Box::new(cloned));
-        for callback in &*self.listeners.borrow_mut() {
+        *self.last_value.borrow_mut() = Some(Rc::new(cloned));
+        for callback in self.callbacks() {
             callback(value);
         }
     }
@@ -111,11 +141,21 @@ impl Signal {
     /// - The value can't be cloned
     /// - Callbacks don't need persistent access to the value
     pub fn emit_unclonable<T: Any>(&self, value: &T) {
-        for callback in &*self.listeners.borrow_mut() {
+        for callback in self.callbacks() {
             callback(value);
         }
     }
 
+    /// Snapshot of connected callbacks. Listeners are not borrowed while callbacks run, so they
+    /// can change the signal
+    fn callbacks(&self) -> Vec<Callback> {
+        self.listeners
+            .borrow()
+            .iter()
+            .map(|(_, callback)| Rc::clone(callback))
+            .collect()
+    }
+
     /// Returns a read-only reference to the internal last_value storage
     ///
     /// Example usage:
@@ -130,7 +170,7 @@ impl Signal {
     ///     }
     /// };
     /// ```
-    pub fn last_value_ref(&self) -> Ref<'_, Option<Box<dyn Any>>> {
+    pub fn last_value_ref(&self) -> Ref<'_, Option<Rc<dyn Any>>> {
         self.last_value.borrow()
     }
 
@@ -152,9 +192,8 @@ impl Signal {
     where
         F: FnOnce(Option<&dyn Any>) -> R,
     {
-        let last_value = self.last_value_ref();
-        let any_ref = last_value.as_ref().map(|boxed| &**boxed as &dyn Any);
-        f(any_ref)
+        let last_value = self.last_value.borrow().clone();
+        f(last_value.as_deref())
     }
 
     /// Retrieves a cloned copy of the last value if available and of type T
@@ -172,10 +211,124 @@ impl Signal {
     pub fn get_last_value_cloned<T: Any + Clone>(&self) -> Option<T> {
         self.with_last_value(|opt| opt.and_then(|any| any.downcast_ref::<T>().cloned()))
     }
+
+    /// View of the signal that only emits and receives values of type `T`, see [TypedSignal]
+    pub fn typed<T: Any + Clone>(&self) -> TypedSignal<'_, T> {
+        TypedSignal::new(self)
+    }
+}
+
+/// [Signal] that carries values of a single type `T`. Values are downcasted internally, so
+/// callbacks receive `&T` directly. Values of other types emitted through the untyped signal are
+/// skipped, in debug builds they panic instead.
+///
+/// # Examples
+/// ```
+/// use capybar::util::signals::Signal;
+/// use std::{cell::Cell, rc::Rc};
+///
+/// let signal = Signal::new();
+/// let volume = signal.typed::<u32>();
+/// let tracker = Rc::new(Cell::new(0));
+///
+/// let track = Rc::clone(&tracker);
+/// volume.connect(move |value| track.set(*value));
+///
+/// volume.emit(&42);
+/// assert_eq!(tracker.get(), 42);
+/// assert_eq!(volume.last_value(), Some(42));
+/// ```
+pub struct TypedSignal<'a, T> {
+    signal: &'a Signal,
+    _type: PhantomData<fn(&T)>,
+}
+
+impl<'a, T: Any + Clone> TypedSignal<'a, T> {
+    pub fn new(signal: &'a Signal) -> Self {
+        TypedSignal {
+            signal,
+            _type: PhantomData,
+        }
+    }
+
+    /// Same as [Signal::connect] with the value downcasted to `T`
+    pub fn connect<F>(&self, callback: F) -> ConnectionId
+    where
+        F: Fn(&T) + 'static,
+    {
+        self.signal.connect(move |data| {
+            let value = data.downcast_ref::<T>();
+            debug_assert!(
+                value.is_some(),
+                "Signal of {} received a value of another type",
+                type_name::<T>()
+            );
+
+            if let Some(value) = value {
+                callback(value);
+            }
+        })
+    }
+
+    /// Same as [Signal::emit]
+    pub fn emit(&self, value: &T) {
+        self.signal.emit(value);
+    }
+
+    /// Same as [Signal::disconnect]
+    pub fn disconnect(&self, id: ConnectionId) {
+        self.signal.disconnect(id);
+    }
+
+    /// Last emitted value. `None` if nothing was emitted or the value has another type
+    pub fn last_value(&self) -> Option<T> {
+        self.signal.get_last_value_cloned()
+    }
+
+    /// Underlying untyped signal
+    pub fn untyped(&self) -> &'a Signal {
+        self.signal
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub enum SignalNames {
+    /// Emitted by a keyboard layout service with the name of the active layout as a `String` on
+    /// every change. Index of the layout is emitted with [SignalNames::KeyboardLayouts]
     Keyboard,
+    /// Emitted by [crate::root::Root] with
+    /// [KeyboardLayouts](crate::services::clients::KeyboardLayouts) when the keymap or the active
+    /// layout changes
+    KeyboardLayouts,
+    /// Emitted by [crate::root::Root] with keyboard
+    /// [Modifiers](smithay_client_toolkit::seat::keyboard::Modifiers) on every change
+    Modifiers,
+    /// Emitted by [Microphone](crate::services::microphone::Microphone) service with
+    /// [MicrophoneState](crate::services::microphone::MicrophoneState) on every change
+    Microphone,
+    /// Emitted by `Mpris` service with `MprisState` on every change. Requires `mpris` feature
+    Mpris,
+    /// Emitted by `Notifications` service with `NotificationsState` on every change. Requires
+    /// `notifications` feature
+    Notifications,
+    /// Emitted by `PowerProfile` service with `PowerProfileState` on every change. Requires
+    /// `power_profile` feature
+    PowerProfile,
+    /// Emitted by `Tray` service with `TrayState` on every change. Requires `tray` feature
+    Tray,
+    /// Emitted by [Volume](crate::services::volume::Volume) service with
+    /// [VolumeState](crate::services::volume::VolumeState) on every change
+    Volume,
+    /// Emitted by `Weather` service with `Option<WeatherState>` when a request finishes with a
+    /// different result. Requires `weather` feature
+    Weather,
+    /// Emitted by a window title service with the title of the focused window as a `String` on
+    /// every change. Title is empty if no window is focused
+    WindowTitle,
+    /// Emitted by a workspaces service with
+    /// [WorkspacesState](crate::services::clients::WorkspacesState) on every change
+    Workspaces,
     Custom(String),
 }
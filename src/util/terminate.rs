@@ -0,0 +1,28 @@
+//! Process wide flag set by `SIGTERM` and `SIGINT` used to stop the bar gracefully
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static RECEIVED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn handle(_: libc::c_int) {
+    RECEIVED.store(true, Ordering::SeqCst);
+}
+
+/// Install `SIGTERM` and `SIGINT` handlers. Installing them several times is harmless
+pub fn install() -> std::io::Result<()> {
+    let handler = handle as extern "C" fn(libc::c_int) as libc::sighandler_t;
+
+    for signal in [libc::SIGTERM, libc::SIGINT] {
+        // SAFETY: handler only stores to an atomic, which is async-signal-safe
+        if unsafe { libc::signal(signal, handler) } == libc::SIG_ERR {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `SIGTERM` or `SIGINT` was received since the last call
+pub fn take() -> bool {
+    RECEIVED.swap(false, Ordering::SeqCst)
+}
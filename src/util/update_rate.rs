@@ -0,0 +1,165 @@
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use chrono::TimeDelta;
+use serde::{de::Visitor, Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpdateRateError {
+    #[error("Update rate `{0}` is not a number followed by `ms`, `s`, `m` or `h`")]
+    Invalid(String),
+}
+
+/// How often a widget or a service is updated. Deserialized either from an integer amount of
+/// milliseconds or from a string with a unit: `"500ms"`, `"2s"`, `"1.5m"` or `"1h"`. Zero means
+/// on every redraw for widgets that allow it.
+///
+/// # Examples
+/// ```
+/// use capybar::util::UpdateRate;
+///
+/// assert_eq!("2s".parse(), Ok(UpdateRate::from_millis(2000)));
+/// assert_eq!("1.5m".parse(), Ok(UpdateRate::from_secs(90)));
+/// assert!("fast".parse::<UpdateRate>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct UpdateRate(pub Duration);
+
+impl UpdateRate {
+    pub const ZERO: UpdateRate = UpdateRate(Duration::ZERO);
+
+    pub const fn from_millis(millis: u64) -> Self {
+        UpdateRate(Duration::from_millis(millis))
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        UpdateRate(Duration::from_secs(secs))
+    }
+
+    pub const fn from_mins(mins: u64) -> Self {
+        UpdateRate(Duration::from_secs(mins * 60))
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub const fn as_duration(&self) -> Duration {
+        self.0
+    }
+
+    /// Rate as a [TimeDelta] to compare with local time. Saturates at [TimeDelta::MAX]
+    pub fn as_delta(&self) -> TimeDelta {
+        TimeDelta::from_std(self.0).unwrap_or(TimeDelta::MAX)
+    }
+}
+
+impl From<Duration> for UpdateRate {
+    fn from(duration: Duration) -> Self {
+        UpdateRate(duration)
+    }
+}
+
+impl FromStr for UpdateRate {
+    type Err = UpdateRateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || UpdateRateError::Invalid(s.to_string());
+
+        let s = s.trim();
+        let split = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(s.len());
+        let (amount, unit) = s.split_at(split);
+
+        let seconds = match unit.trim_start() {
+            "" | "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return Err(invalid()),
+        };
+
+        let amount: f64 = amount.parse().map_err(|_| invalid())?;
+        Duration::try_from_secs_f64(amount * seconds)
+            .map(UpdateRate)
+            .map_err(|_| invalid())
+    }
+}
+
+impl Display for UpdateRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}ms", self.0.as_millis())
+    }
+}
+
+// Serialized as milliseconds, so configs written by older versions stay the same
+impl Serialize for UpdateRate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u64(self.0.as_millis().try_into().unwrap_or(u64::MAX))
+    }
+}
+
+impl<'de> Deserialize<'de> for UpdateRate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct UpdateRateVisitor;
+
+        impl Visitor<'_> for UpdateRateVisitor {
+            type Value = UpdateRate;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter
+                    .write_str("milliseconds as an integer or a string like \"500ms\" or \"2s\"")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(UpdateRate::from_millis(v))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match u64::try_from(v) {
+                    Ok(millis) => Ok(UpdateRate::from_millis(millis)),
+                    Err(_) => Err(E::invalid_value(serde::de::Unexpected::Signed(v), &self)),
+                }
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                v.parse()
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_any(UpdateRateVisitor)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for UpdateRate {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "UpdateRate".into()
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "anyOf": [
+                { "type": "integer", "minimum": 0 },
+                { "type": "string", "pattern": "^\\s*[0-9.]+\\s*(ms|s|m|h)?\\s*$" }
+            ]
+        })
+    }
+}
@@ -0,0 +1,116 @@
+//! Whether bars can be seen and what an idle tick of the event loop does about it. Hidden bars
+//! are not drawn and services are not run while every bar is hidden.
+
+/// Tracks whether a bar can be seen. It's surface has to be shown on at least one output and the
+/// output it is created for has to be powered on. A bar is considered visible until it's surface
+/// enters an output for the first time, so the first frame is drawn.
+///
+/// # Examples
+/// ```
+/// use capybar::util::visibility::Visibility;
+///
+/// let mut visibility = Visibility::new();
+/// assert!(visibility.is_visible());
+///
+/// visibility.enter("DP-1");
+/// visibility.set_powered(false);
+/// assert!(!visibility.is_visible());
+/// assert!(visibility.set_powered(true));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Visibility<O> {
+    /// Outputs the surface is shown on. `None` until it enters one
+    entered: Option<Vec<O>>,
+    powered: bool,
+}
+
+impl<O: PartialEq> Default for Visibility<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O: PartialEq> Visibility<O> {
+    pub fn new() -> Self {
+        Visibility {
+            entered: None,
+            powered: true,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.powered
+            && self
+                .entered
+                .as_ref()
+                .is_none_or(|entered| !entered.is_empty())
+    }
+
+    /// Surface is shown on `output`. Returns whether the bar became visible
+    pub fn enter(&mut self, output: O) -> bool {
+        let was_visible = self.is_visible();
+        let entered = self.entered.get_or_insert_with(Vec::new);
+        if !entered.contains(&output) {
+            entered.push(output);
+        }
+        !was_visible && self.is_visible()
+    }
+
+    /// Surface is no longer shown on `output`, e.g. it left it or the output is gone
+    pub fn leave(&mut self, output: &O) {
+        if let Some(entered) = &mut self.entered {
+            entered.retain(|o| o != output);
+        }
+    }
+
+    /// Output the bar is created for was powered on or off. Returns whether the bar became
+    /// visible
+    pub fn set_powered(&mut self, powered: bool) -> bool {
+        let was_visible = self.is_visible();
+        self.powered = powered;
+        !was_visible && self.is_visible()
+    }
+}
+
+/// State of a bar considered by an idle tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarState {
+    /// Surface of the bar got it's first configure
+    pub configured: bool,
+    /// Frame was committed and the compositor has not asked for the next one yet
+    pub frame_pending: bool,
+    pub visible: bool,
+}
+
+impl BarState {
+    /// Whether the bar is drawn outside of frame callbacks
+    pub fn is_idle(&self) -> bool {
+        self.visible && self.configured && !self.frame_pending
+    }
+}
+
+/// Work of an idle tick, see [idle_work]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdleWork<K> {
+    /// Services are run once
+    pub run_services: bool,
+    /// Bars to draw
+    pub draw: Vec<K>,
+}
+
+/// Decide what an idle tick does with `bars`. Services are run if any bar is visible. Bars are
+/// drawn if they are visible, configured and not waiting for a frame callback, bars that skipped
+/// the last commit do not get frame callbacks until they commit again.
+pub fn idle_work<K>(bars: impl IntoIterator<Item = (K, BarState)>) -> IdleWork<K> {
+    let mut run_services = false;
+    let mut draw = Vec::new();
+
+    for (key, bar) in bars {
+        run_services |= bar.visible;
+        if bar.is_idle() {
+            draw.push(key);
+        }
+    }
+
+    IdleWork { run_services, draw }
+}
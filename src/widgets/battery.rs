@@ -1,14 +1,18 @@
 use std::{
-    cell::{Ref, RefCell, RefMut},
+    cell::RefCell,
     ops::Add,
+    time::{Duration, Instant},
 };
 
 use anyhow::Result;
 use battery::{Manager, State};
-use serde::Deserialize;
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::util::{schedule, Color, UpdateRate};
 
 use super::{
-    icon_text::{IconText, IconTextSettings},
+    icon_text::{default_icon_fontid, IconText, IconTextSettings},
     text::TextSettings,
     Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
 };
@@ -21,8 +25,25 @@ const fn battery_charging_default() -> [char; 11] {
     ['󰢟', '󰢜', '󰂆', '󰂇', '󰂈', '󰢝', '󰂉', '󰢞', '󰂊', '󰂋', '󰂅']
 }
 
+const fn default_warning_threshold() -> i8 {
+    15
+}
+
+const fn default_warning_color() -> Color {
+    Color::RED
+}
+
+const fn default_update_rate() -> UpdateRate {
+    UpdateRate::from_millis(5000)
+}
+
+fn default_time_format() -> String {
+    "{hours}h{minutes}m".to_string()
+}
+
 /// Settings of a [Battery] widget
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BatterySettings {
     /// Array of all symbols for percentages of battery when it is not charging. Symbols are changed
     /// every 10% including 0%, therefor needs 11 symbols.  
@@ -43,6 +64,31 @@ pub struct BatterySettings {
 
     #[serde(default, flatten)]
     pub style: Style,
+
+    /// Append an estimate of time until the battery is full or empty to the percentage
+    #[serde(default)]
+    pub show_time: bool,
+
+    /// Format of the time estimate. `{hours}` is replaced with whole hours and `{minutes}` with
+    /// remaining minutes padded to 2 digits
+    #[serde(default = "default_time_format")]
+    pub time_format: String,
+
+    /// Charge percentage at and below which the battery is drawn with
+    /// [BatterySettings::warning_color] while it is not charging
+    #[serde(default = "default_warning_threshold")]
+    pub warning_threshold: i8,
+
+    #[serde(default = "default_warning_color")]
+    pub warning_color: Color,
+
+    /// How often to query battery status
+    #[serde(default = "default_update_rate")]
+    pub update_rate: UpdateRate,
+
+    /// Id of font in [Environment::fonts](crate::root::Environment::fonts) the icon is drawn with
+    #[serde(default = "default_icon_fontid")]
+    pub icon_fontid: usize,
 }
 
 impl Default for BatterySettings {
@@ -56,23 +102,77 @@ impl Default for BatterySettings {
             default_data: WidgetData::default(),
 
             style: Style::default(),
+
+            show_time: false,
+            time_format: default_time_format(),
+
+            warning_threshold: default_warning_threshold(),
+            warning_color: default_warning_color(),
+
+            update_rate: default_update_rate(),
+            icon_fontid: default_icon_fontid(),
+        }
+    }
+}
+
+impl BatterySettings {
+    /// Symbol for provided charge percentage. Percentage is clamped to `0..=100`, since some
+    /// drivers report values slightly out of range.
+    pub fn icon(&self, percentage: i8, charging: bool) -> char {
+        let symbols = match charging {
+            true => &self.battery_charging,
+            false => &self.battery_not_charging,
+        };
+
+        symbols[(percentage.clamp(0, 100) / 10) as usize]
+    }
+
+    /// Color of the icon and the text for provided charge percentage and battery state
+    pub fn color(&self, percentage: i8, state: State) -> Color {
+        if percentage <= self.warning_threshold && state != State::Charging {
+            self.warning_color
+        } else {
+            self.text_settings.font_color
+        }
+    }
+
+    /// Format time estimate according to [BatterySettings::time_format]
+    pub fn format_time(&self, time: Duration) -> String {
+        let minutes = time.as_secs() / 60;
+
+        self.time_format
+            .replace("{hours}", &(minutes / 60).to_string())
+            .replace("{minutes}", &format!("{:02}", minutes % 60))
+    }
+
+    /// Text displayed for the battery info
+    pub fn text(&self, info: &BatteryInfo) -> String {
+        let percentage = ((info.percentage() * 100.0).round() as i8).clamp(0, 100);
+
+        match info.time_remaining() {
+            Some(time) if self.show_time => format!("{percentage}% {}", self.format_time(time)),
+            _ => format!("{percentage}%"),
         }
     }
 }
 
+/// Combined state of all batteries. Energy is in joules, rate in watts.
 #[derive(Default, Debug, Clone)]
 pub struct BatteryInfo {
     energy: f32,
     full: f32,
+    rate: f32,
     state: State,
 }
 
+/// Estimates are not summed, they are recomputed from the combined energy and rate instead
 impl Add for BatteryInfo {
     type Output = BatteryInfo;
     fn add(self, rhs: Self) -> Self::Output {
         BatteryInfo {
             energy: self.energy + rhs.energy,
             full: self.full + rhs.full,
+            rate: self.rate + rhs.rate,
             state: {
                 if self.state == State::Charging || rhs.state == State::Charging {
                     State::Charging
@@ -93,9 +193,38 @@ impl Add for BatteryInfo {
 }
 
 impl BatteryInfo {
+    pub fn new(energy: f32, full: f32, rate: f32, state: State) -> Self {
+        Self {
+            energy,
+            full,
+            rate,
+            state,
+        }
+    }
+
     pub fn percentage(&self) -> f32 {
         self.energy / self.full
     }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Estimated time until the battery is full while charging or empty while discharging. Not
+    /// available in other states or if the rate is not reported.
+    pub fn time_remaining(&self) -> Option<Duration> {
+        if self.rate <= 0.0 {
+            return None;
+        }
+
+        let energy_left = match self.state {
+            State::Charging => self.full - self.energy,
+            State::Discharging => self.energy,
+            _ => return None,
+        };
+
+        Duration::try_from_secs_f32(energy_left.max(0.0) / self.rate).ok()
+    }
 }
 
 /// Widget displaying current battery status.
@@ -107,7 +236,11 @@ pub struct Battery {
     data: RefCell<WidgetData>,
     is_ready: RefCell<bool>,
 
-    prev_charge: RefCell<i8>,
+    /// Icon and text displayed last time
+    prev: RefCell<(String, String)>,
+
+    last_update: RefCell<DateTime<Local>>,
+    update_rate: TimeDelta,
 }
 
 impl Battery {
@@ -124,6 +257,7 @@ impl Battery {
                         Some(BatteryInfo {
                             energy: charge_rate * full,
                             full,
+                            rate: battery.energy_rate().value.abs(),
                             state: battery.state(),
                         })
                     }
@@ -133,12 +267,45 @@ impl Battery {
                     BatteryInfo {
                         energy: 0.0,
                         full: 0.0,
+                        rate: 0.0,
                         state: battery::State::Unknown,
                     },
                     |acc, x| acc + x,
                 ),
         )
     }
+
+    /// Query battery status and update displayed icon, text and color
+    fn update(&self) {
+        let (icon, text, color) = match self.get_info() {
+            Some(i) => {
+                let percentage: i8 = ((i.percentage() * 100.0).round() as i8).clamp(0, 100);
+                let icon = self.settings.icon(percentage, i.state == State::Charging);
+
+                (
+                    icon.to_string(),
+                    self.settings.text(&i),
+                    self.settings.color(percentage, i.state),
+                )
+            }
+            None => (
+                "".to_string(),
+                "ERR".to_string(),
+                self.settings.text_settings.font_color,
+            ),
+        };
+
+        let mut prev = self.prev.borrow_mut();
+        let mut it = self.icon_text.borrow_mut();
+        it.change_color(color);
+        if prev.0 != icon {
+            it.change_icon(&icon);
+        }
+        if prev.1 != text {
+            it.change_text(&text);
+        }
+        *prev = (icon, text);
+    }
 }
 
 impl Widget for Battery {
@@ -150,12 +317,8 @@ impl Widget for Battery {
         Some(self)
     }
 
-    fn data(&self) -> Ref<'_, WidgetData> {
-        self.data.borrow()
-    }
-
-    fn data_mut(&self) -> RefMut<'_, WidgetData> {
-        self.data.borrow_mut()
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
     }
 
     fn env(&self) -> Option<std::rc::Rc<crate::root::Environment>> {
@@ -205,37 +368,10 @@ impl Widget for Battery {
 
         self.draw_style()?;
 
-        let info = self.get_info();
-
-        let mut prev_charge = self.prev_charge.borrow_mut();
-        {
-            let mut it = self.icon_text.borrow_mut();
-            match info {
-                Some(i) => {
-                    let percentage: i8 = (i.percentage() * 100.0).round() as i8;
-
-                    if percentage != *prev_charge {
-                        it.change_icon(
-                            format!(
-                                "{}",
-                                match i.state {
-                                    State::Charging => self.settings.battery_charging,
-                                    _ => self.settings.battery_not_charging,
-                                }[(percentage / 10) as usize],
-                            )
-                            .as_str(),
-                        );
-                        it.change_text(format!("{percentage}%").as_str());
-                    }
-                }
-                None => {
-                    if *prev_charge != -1 {
-                        it.change_icon("");
-                        it.change_text("ERR");
-                    }
-                    *prev_charge = -1;
-                }
-            };
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update >= self.update_rate {
+            self.update();
+            *last_update = Local::now();
         }
 
         {
@@ -249,6 +385,13 @@ impl Widget for Battery {
 
         self.icon_text.borrow().draw()
     }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.update_rate,
+        ))
+    }
 }
 
 impl WidgetNew for Battery {
@@ -276,13 +419,18 @@ impl WidgetNew for Battery {
                 IconTextSettings {
                     icon_settings: settings.text_settings.clone(),
                     text_settings: settings.text_settings.clone(),
+                    icon_fontid: settings.icon_fontid,
                     ..IconTextSettings::default()
                 },
             )?),
 
             data: RefCell::new(settings.default_data),
+            prev: RefCell::new((String::new(), String::new())),
+
+            last_update: RefCell::new(DateTime::default()),
+            update_rate: settings.update_rate.as_delta(),
+
             settings,
-            prev_charge: RefCell::new(0),
         })
     }
 }
@@ -0,0 +1,259 @@
+use std::{
+    cell::RefCell,
+    fs, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::Instant,
+};
+
+use anyhow::Result;
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    util::{schedule, UpdateRate},
+};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+/// Directory with backlight devices exposed by the kernel
+pub const BACKLIGHT_PATH: &str = "/sys/class/backlight";
+
+fn default_icons() -> Vec<String> {
+    vec!["󰃞".to_string(), "󰃟".to_string(), "󰃠".to_string()]
+}
+
+/// Settings of a [Brightness] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BrightnessSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Name of a backlight device in [BACKLIGHT_PATH], e.g. `"intel_backlight"`. The first
+    /// device in alphabetical order is used if not set.
+    #[serde(default)]
+    pub device: Option<String>,
+
+    /// How often to update brightness
+    #[serde(default)]
+    pub update_rate: UpdateRate,
+
+    /// Icons for brightness levels from the dimmest to the brightest. Range from 0% to 100% is
+    /// split evenly between them.
+    #[serde(default = "default_icons")]
+    pub icons: Vec<String>,
+}
+
+impl Default for BrightnessSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            device: None,
+            update_rate: UpdateRate::ZERO,
+            icons: default_icons(),
+        }
+    }
+}
+
+impl BrightnessSettings {
+    /// Icon matching brightness in percent
+    pub fn icon(&self, percent: u32) -> &str {
+        if self.icons.is_empty() {
+            return "";
+        }
+
+        let index = percent as usize * self.icons.len() / 100;
+        &self.icons[index.min(self.icons.len() - 1)]
+    }
+
+    /// Directory of the configured backlight device inside of `base`, or of the first one if no
+    /// device is configured
+    pub fn find_device(&self, base: &Path) -> io::Result<PathBuf> {
+        if let Some(device) = &self.device {
+            return Ok(base.join(device));
+        }
+
+        let mut devices = fs::read_dir(base)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect::<Vec<_>>();
+        devices.sort();
+
+        devices
+            .into_iter()
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No backlight devices found"))
+    }
+
+    /// Read brightness in percent of a device from [BrightnessSettings::find_device]
+    pub fn read(&self, base: &Path) -> io::Result<u32> {
+        let device = self.find_device(base)?;
+        let read = |file: &str| -> io::Result<u64> {
+            fs::read_to_string(device.join(file))?
+                .trim()
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        };
+
+        let brightness = read("brightness")?;
+        match read("max_brightness")? {
+            0 => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Maximum brightness is zero",
+            )),
+            max => Ok((brightness.min(max) as f64 / max as f64 * 100.0).round() as u32),
+        }
+    }
+}
+
+/// Widget displaying brightness of a backlight device from [BACKLIGHT_PATH]
+pub struct Brightness {
+    data: RefCell<WidgetData>,
+    settings: BrightnessSettings,
+    is_ready: RefCell<bool>,
+
+    icon_text: RefCell<IconText>,
+
+    last_update: RefCell<DateTime<Local>>,
+    update_rate: TimeDelta,
+}
+
+impl Widget for Brightness {
+    fn name(&self) -> WidgetList {
+        WidgetList::Brightness
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.icon_text.borrow().env()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        self.apply_style()?;
+
+        self.icon_text.borrow_mut().change_text("ERR");
+        self.icon_text
+            .borrow_mut()
+            .change_icon(self.settings.icon(100));
+        self.icon_text.borrow().init()?;
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env().is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Brightness));
+        }
+
+        self.draw_style()?;
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update >= self.update_rate {
+            let mut it = self.icon_text.borrow_mut();
+            match self.settings.read(Path::new(BACKLIGHT_PATH)) {
+                Ok(percent) => {
+                    it.change_icon(self.settings.icon(percent));
+                    it.change_text(&format!("{percent}%"));
+                }
+                Err(_) => it.change_text("ERR"),
+            }
+
+            *last_update = Local::now();
+        }
+
+        {
+            let it = self.icon_text.borrow();
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.icon_text.borrow().draw()
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.update_rate,
+        ))
+    }
+}
+
+impl WidgetNew for Brightness {
+    type Settings = BrightnessSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+
+            is_ready: RefCell::new(false),
+
+            icon_text: RefCell::new(IconText::new(
+                env,
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?),
+
+            update_rate: settings.update_rate.as_delta(),
+            last_update: RefCell::new(DateTime::default()),
+
+            settings,
+        })
+    }
+}
+
+impl WidgetStyled for Brightness {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
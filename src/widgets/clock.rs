@@ -1,20 +1,22 @@
-use std::{
-    cell::{Ref, RefCell, RefMut},
-    rc::Rc,
-};
+use std::{cell::RefCell, fmt::Display, rc::Rc, time::Instant};
 
-use anyhow::Result;
-use chrono::Local;
-use serde::Deserialize;
+use anyhow::{anyhow, Result};
+use chrono::{
+    format::{Fixed, Item, Numeric, StrftimeItems},
+    DateTime, Local, Locale, TimeDelta, TimeZone,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     root::Environment,
-    util::Color,
+    util::{command, schedule, Color},
     widgets::{text::Text, Widget},
 };
 
 use super::{
-    text::TextSettings, Margin, Style, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+    text::TextSettings, Interactive, Margin, Style, WidgetData, WidgetError, WidgetList, WidgetNew,
+    WidgetStyled,
 };
 
 fn default_format() -> String {
@@ -22,7 +24,8 @@ fn default_format() -> String {
 }
 
 /// Settings of a [Clock] widget
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClockSettings {
     /// Default font size
     #[serde(default)]
@@ -32,6 +35,16 @@ pub struct ClockSettings {
     #[serde(default = "default_format")]
     pub format: String,
 
+    /// Locale used for names of months, weekdays and AM/PM, e.g. `"de_DE"`. Format is not
+    /// localised if not set.
+    #[serde(default)]
+    pub locale: Option<String>,
+
+    /// IANA name of the timezone the time is shown in, e.g. `"UTC"` or `"America/New_York"`.
+    /// Local time is shown if not set.
+    #[serde(default)]
+    pub timezone: Option<String>,
+
     #[serde(default)]
     pub font_color: Color,
 
@@ -40,6 +53,10 @@ pub struct ClockSettings {
 
     #[serde(default, flatten)]
     pub style: Style,
+
+    /// Shell command launched when the clock is clicked
+    #[serde(default)]
+    pub on_click: Option<String>,
 }
 
 impl Default for ClockSettings {
@@ -47,30 +64,125 @@ impl Default for ClockSettings {
         Self {
             size: 25.0,
             format: default_format(),
+            locale: None,
+            timezone: None,
 
             font_color: Color::BLACK,
 
             default_data: WidgetData::default(),
 
             style: Style::default(),
+
+            on_click: None,
+        }
+    }
+}
+
+impl ClockSettings {
+    /// Check that the format, the locale and the timezone are valid. Returns parsed locale if it
+    /// is set
+    pub fn validate(&self) -> Result<Option<Locale>, WidgetError> {
+        if StrftimeItems::new(&self.format).parse().is_err() {
+            return Err(WidgetError::Custom(anyhow!(
+                "Invalid clock format \"{}\"",
+                self.format
+            )));
+        }
+
+        self.timezone()?;
+
+        self.locale
+            .as_deref()
+            .map(|locale| {
+                Locale::try_from(locale)
+                    .map_err(|_| WidgetError::Custom(anyhow!("Unknown locale \"{locale}\"")))
+            })
+            .transpose()
+    }
+
+    /// Parsed timezone. `None` if it is not set
+    pub fn timezone(&self) -> Result<Option<Tz>, WidgetError> {
+        self.timezone
+            .as_deref()
+            .map(|timezone| {
+                timezone
+                    .parse::<Tz>()
+                    .map_err(|_| WidgetError::Custom(anyhow!("Unknown timezone \"{timezone}\"")))
+            })
+            .transpose()
+    }
+
+    /// Smallest unit of time shown by the format: one second if it displays seconds, one minute
+    /// otherwise. Clock is redrawn when the local time crosses a multiple of it.
+    pub fn tick(&self) -> TimeDelta {
+        let shows_seconds = StrftimeItems::new(&self.format).any(|item| {
+            matches!(
+                item,
+                Item::Numeric(
+                    Numeric::Second | Numeric::Timestamp | Numeric::Nanosecond,
+                    _
+                ) | Item::Fixed(
+                    Fixed::Nanosecond
+                        | Fixed::Nanosecond3
+                        | Fixed::Nanosecond6
+                        | Fixed::Nanosecond9
+                        | Fixed::Internal(_)
+                        | Fixed::RFC2822
+                        | Fixed::RFC3339
+                )
+            )
+        });
+
+        match shows_seconds {
+            true => TimeDelta::seconds(1),
+            false => TimeDelta::minutes(1),
         }
     }
 }
 
+fn format_time<Z: TimeZone>(time: &DateTime<Z>, format: &str, locale: Option<Locale>) -> String
+where
+    Z::Offset: Display,
+{
+    match locale {
+        Some(locale) => time.format_localized(format, locale).to_string(),
+        None => time.format(format).to_string(),
+    }
+}
+
+fn format_in(
+    time: &DateTime<Local>,
+    format: &str,
+    locale: Option<Locale>,
+    timezone: Option<Tz>,
+) -> String {
+    match timezone {
+        Some(timezone) => format_time(&time.with_timezone(&timezone), format, locale),
+        None => format_time(time, format, locale),
+    }
+}
+
 /// Widget displaying current time. Supports C's strftime formating.
 pub struct Clock {
     text: RefCell<Text>,
     settings: ClockSettings,
+    locale: Option<Locale>,
+    timezone: Option<Tz>,
 
     data: RefCell<WidgetData>,
     is_ready: RefCell<bool>,
 }
 
 impl Clock {
+    /// Format `time` according to the clock settings, in the configured timezone if it is set
+    pub fn format(&self, time: &DateTime<Local>) -> String {
+        format_in(time, &self.settings.format, self.locale, self.timezone)
+    }
+
     /// Force update current time  
     pub fn update(&self) -> &Self {
         let mut text = self.text.borrow_mut();
-        text.change_text(&Local::now().format(&self.settings.format).to_string());
+        text.change_text(&self.format(&Local::now()));
         text.data_mut().position = self.data.borrow_mut().position;
 
         self
@@ -86,12 +198,15 @@ impl Widget for Clock {
         Some(self)
     }
 
-    fn data(&self) -> Ref<'_, WidgetData> {
-        self.data.borrow()
+    fn as_interactive(&self) -> Option<&dyn Interactive> {
+        match self.settings.on_click {
+            Some(_) => Some(self),
+            None => None,
+        }
     }
 
-    fn data_mut(&self) -> RefMut<'_, WidgetData> {
-        self.data.borrow_mut()
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
     }
 
     fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
@@ -141,6 +256,10 @@ impl Widget for Clock {
         self.update();
         self.text.borrow_mut().draw()
     }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(Instant::now() + schedule::until_next(Local::now(), self.settings.tick()))
+    }
 }
 
 impl WidgetNew for Clock {
@@ -150,25 +269,20 @@ impl WidgetNew for Clock {
     where
         Self: Sized,
     {
+        let locale = settings.validate()?;
+        let timezone = settings.timezone()?;
+
         let text = RefCell::new(Text::new(
             env,
             TextSettings {
-                text: Local::now().format(&settings.format).to_string(),
+                text: format_in(&Local::now(), &settings.format, locale, timezone),
                 font_color: settings.font_color,
                 size: settings.size,
 
-                default_data: WidgetData {
-                    width: (settings.size * 6.0) as usize,
-                    ..WidgetData::default()
-                },
+                default_data: WidgetData::with_width((settings.size * 6.0) as usize),
 
                 style: Style {
-                    margin: Margin {
-                        left: 2,
-                        right: 2,
-                        up: 0,
-                        down: 0,
-                    },
+                    margin: Margin::symmetric(2, 0),
                     ..Style::default()
                 },
 
@@ -177,6 +291,8 @@ impl WidgetNew for Clock {
         )?);
         Ok(Clock {
             text,
+            locale,
+            timezone,
             data: RefCell::new(settings.default_data),
             settings,
             is_ready: RefCell::new(false),
@@ -184,6 +300,16 @@ impl WidgetNew for Clock {
     }
 }
 
+impl Interactive for Clock {
+    fn click(&self, _button: u32) -> Result<(), WidgetError> {
+        if let Some(command) = &self.settings.on_click {
+            command::spawn(command).map_err(|e| WidgetError::Custom(e.into()))?;
+        }
+
+        Ok(())
+    }
+}
+
 impl WidgetStyled for Clock {
     fn style(&self) -> &Style {
         &self.settings.style
@@ -1,15 +1,23 @@
 use std::{
-    cell::{Ref, RefCell, RefMut},
+    cell::RefCell,
     rc::Rc,
+    time::{Duration, Instant},
 };
 
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::{
     root::Environment,
     services::Service,
-    widgets::{Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled},
+    util::{schedule, Color},
+    widgets::{
+        debug_tree, draw_with_opacity, next_update_of,
+        text::{TextAlign, TextSettings},
+        Animation, Background, Margin, ScrollEvent, Style, Widget, WidgetData, WidgetError,
+        WidgetList, WidgetNew, WidgetStyled,
+    },
 };
 
 use super::{
@@ -17,8 +25,176 @@ use super::{
     Container,
 };
 
+/// Lowest allowed delay between redraws in milliseconds. Prevents busy looping.
+pub const MIN_REFRESH_RATE_MS: u64 = 10;
+
+/// Namespace of layer surfaces of bars without [BarSettings::namespace]
+pub const DEFAULT_NAMESPACE: &str = "Bar";
+
+const fn default_refresh_rate() -> u64 {
+    100
+}
+
+const fn default_exclusive() -> bool {
+    true
+}
+
+const fn default_tooltip_delay() -> u64 {
+    500
+}
+
+const fn default_tooltip_color() -> Color {
+    Color::WHITE
+}
+
+const fn default_tooltip_size() -> f32 {
+    14.0
+}
+
+const fn default_tooltip_style() -> Style {
+    Style {
+        background: Some(Background::Solid(Color::from_hex(0x1e1e2eff))),
+        border: None,
+        margin: Margin::default(),
+        padding: Margin::symmetric(6, 3),
+        border_radius: 0,
+        tooltip: None,
+        opacity: 1.0,
+        animation: Animation::None,
+    }
+}
+
+/// Appearance of tooltips shown over widgets of a bar, see [Style::tooltip]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TooltipSettings {
+    /// How long the pointer has to rest over a widget before it's tooltip is shown, in
+    /// milliseconds
+    #[serde(default = "default_tooltip_delay")]
+    pub delay_ms: u64,
+
+    #[serde(default = "default_tooltip_color")]
+    pub font_color: Color,
+
+    #[serde(default = "default_tooltip_size")]
+    pub size: f32,
+
+    /// Id of font in [Environment::fonts](crate::root::Environment::fonts) of the bar
+    #[serde(default)]
+    pub fontid: usize,
+
+    /// Fonts used for characters missing in [TooltipSettings::fontid]
+    #[serde(default)]
+    pub fallback_fonts: Vec<usize>,
+
+    #[serde(default = "default_tooltip_style")]
+    pub style: Style,
+}
+
+impl Default for TooltipSettings {
+    fn default() -> Self {
+        Self::default()
+    }
+}
+
+impl TooltipSettings {
+    pub const fn default() -> Self {
+        Self {
+            delay_ms: default_tooltip_delay(),
+            font_color: default_tooltip_color(),
+            size: default_tooltip_size(),
+            fontid: 0,
+            fallback_fonts: Vec::new(),
+            style: default_tooltip_style(),
+        }
+    }
+
+    /// Delay before a tooltip is shown
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    /// Settings of a [Text](crate::widgets::text::Text) showing `text` in a tooltip
+    pub fn text_settings(&self, text: &str) -> TextSettings {
+        TextSettings {
+            default_data: WidgetData::default(),
+            text: text.to_string(),
+            font_color: self.font_color,
+            size: self.size,
+            fontid: self.fontid,
+            fallback_fonts: self.fallback_fonts.clone(),
+            horizontal_align: TextAlign::Left,
+            max_width: 0,
+            scroll: false,
+            ellipsis: false,
+            underline: false,
+            strikethrough: false,
+            decoration_color: None,
+            decoration_thickness: 0,
+            style: self.style.clone(),
+        }
+    }
+}
+
+/// Edge of an output the bar is attached to
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BarAnchor {
+    #[default]
+    Top,
+    Bottom,
+}
+
+impl BarAnchor {
+    pub const fn default() -> Self {
+        BarAnchor::Top
+    }
+}
+
+/// Layer of the shell the bar surface is placed on, from the lowest to the highest
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BarLayer {
+    /// Below windows, e.g. on the desktop
+    Background,
+    Bottom,
+    #[default]
+    Top,
+    /// Above fullscreen windows
+    Overlay,
+}
+
+impl BarLayer {
+    pub const fn default() -> Self {
+        BarLayer::Top
+    }
+}
+
+/// Whether the bar surface can receive keyboard focus
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum KeyboardMode {
+    /// Bar never takes keyboard focus
+    None,
+    /// Bar is focused when clicked, like a regular window
+    #[default]
+    OnDemand,
+    /// Bar takes all keyboard input while it is shown on the top or overlay layer
+    Exclusive,
+}
+
+impl KeyboardMode {
+    pub const fn default() -> Self {
+        KeyboardMode::OnDemand
+    }
+}
+
 /// Settings of a [Bar] containert
-#[derive(Default, Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BarSettings {
     #[serde(flatten, default)]
     pub default_data: WidgetData,
@@ -39,6 +215,66 @@ pub struct BarSettings {
 
     #[serde(flatten)]
     pub style: Style,
+
+    /// Lowest delay between redraws in milliseconds. Bar is redrawn only when a widget has to
+    /// update or an event is received. Can not be lower than [MIN_REFRESH_RATE_MS]
+    #[serde(default = "default_refresh_rate")]
+    pub refresh_rate_ms: u64,
+
+    /// Distance between the bar surface and the edges of an output. Stored as a tuple of (top,
+    /// right, bottom, left). Margin of the anchored edge is added to the exclusive zone by the
+    /// compositor, so windows are not placed in the gap.
+    #[serde(default)]
+    pub margins: (i32, i32, i32, i32),
+
+    /// Reserve space for the bar, so windows are not placed under it. Margin of the edge facing
+    /// windows is reserved as well, leaving a gap between the bar and windows.
+    #[serde(default = "default_exclusive")]
+    pub exclusive: bool,
+
+    /// Size the bar to it's content instead of the configured width. Center row stays centered,
+    /// so the bar is as wide as the center row plus twice the widest side row. Surface is placed
+    /// by the compositor according to the anchor, e.g. centered horizontally for a top bar.
+    #[serde(default)]
+    pub fit_content: bool,
+
+    /// Height of the bar in pixels including borders. Rows are centered vertically inside of it.
+    /// Bar is as tall as it's tallest row if set to 0, it also grows if the content does not fit.
+    #[serde(default)]
+    pub height: usize,
+
+    /// Edge of an output the bar is attached to
+    #[serde(default)]
+    pub anchor: BarAnchor,
+
+    /// Layer of the shell the bar is placed on
+    #[serde(default)]
+    pub layer: BarLayer,
+
+    /// Whether the bar can take keyboard focus
+    #[serde(default)]
+    pub keyboard_interactivity: KeyboardMode,
+
+    /// Names of outputs the bar is shown on, e.g. `["DP-1", "HDMI-A-1"]`. Bar is shown on every
+    /// output if not set.
+    #[serde(default)]
+    pub outputs: Option<Vec<String>>,
+
+    /// Appearance and delay of tooltips
+    #[serde(default)]
+    pub tooltip: TooltipSettings,
+
+    /// Namespace of the layer surface, compositors match their layer rules against it.
+    /// [DEFAULT_NAMESPACE] if not set.
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+impl Default for BarSettings {
+    fn default() -> Self {
+        // Inherent const fn takes precedence over the trait method, so this is not recursive
+        BarSettings::default()
+    }
 }
 
 impl BarSettings {
@@ -50,8 +286,63 @@ impl BarSettings {
             center_settings: RowSettings::default(),
             right_settings: RowSettings::default(),
             style: Style::default(),
+            refresh_rate_ms: default_refresh_rate(),
+            margins: (0, 0, 0, 0),
+            exclusive: default_exclusive(),
+            fit_content: false,
+            height: 0,
+            anchor: BarAnchor::default(),
+            layer: BarLayer::default(),
+            keyboard_interactivity: KeyboardMode::default(),
+            outputs: None,
+            tooltip: TooltipSettings::default(),
+            namespace: None,
         }
     }
+
+    /// Namespace of the layer surface of the bar
+    pub fn namespace(&self) -> &str {
+        self.namespace.as_deref().unwrap_or(DEFAULT_NAMESPACE)
+    }
+
+    /// Whether the bar should be shown on an output with the given name. Outputs without a name
+    /// only get a bar if no filter is set.
+    pub fn shown_on(&self, output: Option<&str>) -> bool {
+        match (&self.outputs, output) {
+            (None, _) => true,
+            (Some(outputs), Some(name)) => outputs.iter().any(|o| o == name),
+            (Some(_), None) => false,
+        }
+    }
+
+    /// Exclusive zone of a bar with the given height. Windows may overlap a bar that is not
+    /// exclusive.
+    pub fn exclusive_zone(&self, height: i32) -> i32 {
+        if !self.exclusive {
+            return 0;
+        }
+
+        let (top, _, bottom, _) = self.margins;
+        match self.anchor {
+            BarAnchor::Top => height + bottom,
+            BarAnchor::Bottom => height + top,
+        }
+    }
+
+    /// Lowest delay between redraws clamped to [MIN_REFRESH_RATE_MS]
+    pub fn refresh_rate(&self) -> Duration {
+        Duration::from_millis(self.refresh_rate_ms.max(MIN_REFRESH_RATE_MS))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum BarError {
+    /// Arguments are a name of the row, width it requires, available width and size of overflow
+    #[error(
+        "Widgets in the {0} row of the bar need {1}px, but only {2}px are available \
+        ({3}px overflow)"
+    )]
+    WidthOverflow(&'static str, usize, usize, usize),
 }
 
 /// Main widget in capybar. Stores 3 alligned [Row] containers.
@@ -67,6 +358,10 @@ pub struct Bar {
 }
 
 impl Bar {
+    pub fn settings(&self) -> &BarSettings {
+        &self.settings
+    }
+
     pub fn add_center(&self, widget: Box<dyn Widget>) -> Result<()> {
         self.center.borrow_mut().add_widget(widget);
 
@@ -78,9 +373,12 @@ impl Bar {
         W: WidgetNew + Widget + 'static,
         F: FnOnce(Option<Rc<Environment>>, W::Settings) -> Result<W, WidgetError>,
     {
-        self.left
-            .borrow_mut()
-            .add_widget(Box::new(f(self.env.clone(), settings)?));
+        let widget = f(self.env.clone(), settings)?;
+        if self.env.is_some() {
+            widget.init()?;
+        }
+
+        self.left.borrow_mut().add_widget(Box::new(widget));
         Ok(())
     }
 
@@ -89,9 +387,12 @@ impl Bar {
         W: WidgetNew + Widget + 'static,
         F: FnOnce(Option<Rc<Environment>>, W::Settings) -> Result<W, WidgetError>,
     {
-        self.center
-            .borrow_mut()
-            .add_widget(Box::new(f(self.env.clone(), settings)?));
+        let widget = f(self.env.clone(), settings)?;
+        if self.env.is_some() {
+            widget.init()?;
+        }
+
+        self.center.borrow_mut().add_widget(Box::new(widget));
         Ok(())
     }
 
@@ -100,9 +401,12 @@ impl Bar {
         W: WidgetNew + Widget + 'static,
         F: FnOnce(Option<Rc<Environment>>, W::Settings) -> Result<W, WidgetError>,
     {
-        self.right
-            .borrow_mut()
-            .add_widget(Box::new(f(self.env.clone(), settings)?));
+        let widget = f(self.env.clone(), settings)?;
+        if self.env.is_some() {
+            widget.init()?;
+        }
+
+        self.right.borrow_mut().add_widget(Box::new(widget));
         Ok(())
     }
 
@@ -118,6 +422,96 @@ impl Bar {
         &mut self.right
     }
 
+    /// Distance from the left and right edges of the bar to it's rows. Rows are inset by the
+    /// margin, border and padding of the bar.
+    fn insets(&self) -> (usize, usize) {
+        let style = &self.settings.style;
+        let border = style.border.map_or(0, |(width, _)| width);
+
+        (
+            style.margin.left + border + style.padding.left,
+            style.margin.right + border + style.padding.right,
+        )
+    }
+
+    /// Size rows with [WidgetData::width_percent] by the width between the inner edges of the bar
+    fn resolve_percent_widths(&self) {
+        let (inset_left, inset_right) = self.insets();
+        let inner = self.data().width.saturating_sub(inset_left + inset_right);
+
+        for row in [&self.left, &self.center, &self.right] {
+            let row = row.borrow();
+            let mut data = row.data_mut();
+            if let Some(width) = data.percent_width(inner) {
+                data.width = width;
+            }
+        }
+    }
+
+    /// Width of a bar with [BarSettings::fit_content] enabled for rows of provided widths
+    fn fit_width(&self, left: usize, center: usize, right: usize) -> usize {
+        let (inset_left, inset_right) = self.insets();
+
+        center + 2 * usize::max(left, right) + inset_left + inset_right
+    }
+
+    /// Height of the bar without borders for rows as tall as `content`
+    fn inner_height(&self, content: usize) -> usize {
+        let border = match self.settings.style.border {
+            Some(a) => a.0,
+            None => 0,
+        };
+
+        match self.settings.height {
+            0 => content,
+            height => height.saturating_sub(2 * border).max(content),
+        }
+    }
+
+    /// Check that rows with provided widths fit into the bar. Left and right rows are anchored to
+    /// the edges and center row is centered, therefore it can not be wider than the space left
+    /// between the widest side row and it's mirror. Bar of zero `width` is not checked.
+    fn check_width(
+        &self,
+        width: usize,
+        left: usize,
+        center: usize,
+        right: usize,
+    ) -> Result<(), BarError> {
+        if width == 0 {
+            return Ok(());
+        }
+
+        let (inset_left, inset_right) = self.insets();
+        let inner = width.saturating_sub(inset_left + inset_right);
+
+        if left > inner {
+            return Err(BarError::WidthOverflow("left", left, inner, left - inner));
+        }
+
+        let available = inner - left;
+        if right > available {
+            return Err(BarError::WidthOverflow(
+                "right",
+                right,
+                available,
+                right - available,
+            ));
+        }
+
+        let available = inner.saturating_sub(2 * usize::max(left, right));
+        if center > available {
+            return Err(BarError::WidthOverflow(
+                "center",
+                center,
+                available,
+                center - available,
+            ));
+        }
+
+        Ok(())
+    }
+
     fn align_widgets(&self) -> anyhow::Result<()> {
         let mut data = self.data.borrow_mut();
         let border = match self.settings.style.border {
@@ -126,24 +520,46 @@ impl Bar {
         };
 
         let left = self.left.borrow_mut();
-        let mut ld = left.data_mut();
+        let mut ld = left.try_data_mut()?;
+        let center = self.center.borrow_mut();
+        let mut cd = center.try_data_mut()?;
+        let right = self.right.borrow_mut();
+        let mut rd = right.try_data_mut()?;
 
-        ld.position.0 = data.position.0 + border.0;
-        ld.position.1 = data.position.1 + border.0;
+        if self.settings.fit_content {
+            data.width = self.fit_width(ld.width, cd.width, rd.width);
+        }
 
-        let center = self.center.borrow_mut();
-        let mut cd = center.data_mut();
+        // Content may have grown since init, e.g. a longer text. Overflowing rows would be
+        // placed outside of the bar
+        self.check_width(data.width, ld.width, cd.width, rd.width)?;
+
+        let height = self.inner_height(ld.height.max(cd.height).max(rd.height));
+        let style = &self.settings.style;
+        let top = data.position.1 + style.margin.up + border.0 + style.padding.up;
+        // Rows are aligned to the top unless the height is configured
+        let offset = |row: usize| match self.settings.height {
+            0 => 0,
+            _ => (height - row) / 2,
+        };
 
-        cd.position.0 = data.position.0 + (data.width - cd.width) / 2;
-        cd.position.1 = data.position.1 + border.0;
+        // Width is final at this point, rows are placed between the inner edges of the bar
+        let (inset_left, inset_right) = self.insets();
+        let inner_left = data.position.0 + inset_left;
+        let inner_right = (data.position.0 + data.width).saturating_sub(inset_right);
+        let inner = inner_right.saturating_sub(inner_left);
 
-        let right = self.right.borrow_mut();
-        let mut rd = right.data_mut();
+        ld.position.0 = inner_left;
+        ld.position.1 = top + offset(ld.height);
 
-        rd.position.0 = data.position.0 + data.width - border.0;
-        rd.position.1 = data.position.1 + border.0;
+        cd.position.0 = inner_left + inner.saturating_sub(cd.width) / 2;
+        cd.position.1 = top + offset(cd.height);
 
-        data.height = ld.height.max(cd.height).max(rd.height);
+        // Right row grows to the left from it's position
+        rd.position.0 = inner_right;
+        rd.position.1 = top + offset(rd.height);
+
+        data.height = height;
 
         Ok(())
     }
@@ -158,12 +574,8 @@ impl Widget for Bar {
         Some(self)
     }
 
-    fn data(&self) -> Ref<'_, WidgetData> {
-        self.data.borrow()
-    }
-
-    fn data_mut(&self) -> RefMut<'_, WidgetData> {
-        self.data.borrow_mut()
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
     }
 
     fn bind(
@@ -188,6 +600,7 @@ impl Widget for Bar {
     }
 
     fn prepare(&self) -> Result<(), WidgetError> {
+        self.resolve_percent_widths();
         self.left.borrow().prepare()?;
         self.center.borrow().prepare()?;
         self.right.borrow().prepare()?;
@@ -205,13 +618,91 @@ impl Widget for Bar {
 
         self.draw_style()?;
 
-        self.left.borrow_mut().draw()?;
-        self.center.borrow_mut().draw()?;
-        self.right.borrow_mut().draw()?;
+        let mut rows = [
+            self.left.borrow(),
+            self.center.borrow(),
+            self.right.borrow(),
+        ];
+        rows.sort_by_key(|row| row.data().z);
+
+        for row in rows.iter() {
+            draw_with_opacity(&**row)?;
+        }
 
         Ok(())
     }
 
+    fn click_at(&self, x: usize, y: usize, button: u32) -> Result<bool, WidgetError> {
+        let mut rows = [
+            self.left.borrow(),
+            self.center.borrow(),
+            self.right.borrow(),
+        ];
+        rows.sort_by_key(|row| row.data().z);
+
+        for row in rows.iter().rev() {
+            if row.click_at(x, y, button)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn scroll_at(&self, x: usize, y: usize, event: ScrollEvent) -> Result<bool, WidgetError> {
+        let mut rows = [
+            self.left.borrow(),
+            self.center.borrow(),
+            self.right.borrow(),
+        ];
+        rows.sort_by_key(|row| row.data().z);
+
+        for row in rows.iter().rev() {
+            if row.scroll_at(x, y, event)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn tooltip_at(&self, x: usize, y: usize) -> Option<String> {
+        let mut rows = [
+            self.left.borrow(),
+            self.center.borrow(),
+            self.right.borrow(),
+        ];
+        rows.sort_by_key(|row| row.data().z);
+
+        rows.iter()
+            .rev()
+            .find_map(|row| row.tooltip_at(x, y))
+            .or_else(|| {
+                let tooltip = self.settings.style.tooltip.clone()?;
+                self.data().contains(x, y).then_some(tooltip)
+            })
+    }
+
+    fn debug_tree(&self) -> String {
+        debug_tree(
+            self.name(),
+            &self.data(),
+            [&self.left, &self.center, &self.right]
+                .iter()
+                .map(|row| row.borrow().debug_tree()),
+        )
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        let services = self.services.borrow();
+
+        [&self.left, &self.center, &self.right]
+            .iter()
+            .map(|row| next_update_of(&*row.borrow()))
+            .chain(services.iter().map(|s| s.next_update()))
+            .fold(None, schedule::earliest)
+    }
+
     fn init(&self) -> Result<(), WidgetError> {
         let left = self.left.borrow_mut();
         let center = self.center.borrow_mut();
@@ -219,23 +710,54 @@ impl Widget for Bar {
         left.init()?;
         center.init()?;
         right.init()?;
-        right.data_mut().position.0 = self.data().width;
+
+        left.prepare_widgets()?;
+        center.prepare_widgets()?;
+        right.prepare_widgets()?;
+        let widths = (
+            left.content_width(),
+            center.content_width(),
+            right.content_width(),
+        );
+
+        if self.settings.fit_content {
+            self.data.borrow_mut().width = self.fit_width(widths.0, widths.1, widths.2);
+        }
+
+        let width = self.data.borrow().width;
+        self.check_width(width, widths.0, widths.1, widths.2)
+            .map_err(|e| WidgetError::Custom(e.into()))?;
+
+        // Right row grows to the left from it's position, anchor it before the first prepare
+        {
+            let data = self.data.borrow();
+            right.data_mut().position.0 =
+                (data.position.0 + data.width).saturating_sub(self.insets().1);
+        }
 
         let border = match self.settings.style.border {
             Some(a) => (a.0, Some(a.1)),
             None => (0, None),
         };
 
-        let mut data = self.data_mut();
-        data.height = *[
+        let content = *[
             left.data_mut().height,
             center.data_mut().height,
             right.data_mut().height,
         ]
         .iter()
         .max_by(|a, b| a.cmp(b))
-        .unwrap()
-            + 2 * border.0;
+        .unwrap();
+
+        let height = self.settings.height;
+        if height != 0 && content + 2 * border.0 > height {
+            println!(
+                "Widgets need {}px, bar height is increased from {height}px",
+                content + 2 * border.0
+            );
+        }
+
+        self.data_mut().height = self.inner_height(content) + 2 * border.0;
 
         Ok(())
     }
@@ -258,7 +780,7 @@ impl WidgetNew for Bar {
                 env.clone(),
                 RowSettings {
                     alignment: Alignment::GrowthHorizontalRight(settings.padding.0),
-                    ..settings.left_settings
+                    ..settings.left_settings.clone()
                 },
             )?),
 
@@ -266,7 +788,7 @@ impl WidgetNew for Bar {
                 env.clone(),
                 RowSettings {
                     alignment: Alignment::GrowthCenteringHorizontalRight(settings.padding.1),
-                    ..settings.center_settings
+                    ..settings.center_settings.clone()
                 },
             )?),
 
@@ -274,7 +796,7 @@ impl WidgetNew for Bar {
                 env.clone(),
                 RowSettings {
                     alignment: Alignment::GrowthHorizontalLeft(settings.padding.2),
-                    ..settings.right_settings
+                    ..settings.right_settings.clone()
                 },
             )?),
             services: RefCell::new(Vec::new()),
@@ -289,6 +811,21 @@ impl WidgetStyled for Bar {
     fn style(&self) -> &Style {
         &self.settings.style
     }
+
+    /// Bar is as wide as it's surface, so the style only adds to the height. Rows are inset
+    /// horizontally when they are aligned instead.
+    fn apply_style(&self) -> Result<(), WidgetError> {
+        let style = &self.settings.style;
+        let border = style.border.map_or(0, |(width, _)| width);
+
+        self.data.borrow_mut().height += 2 * border
+            + style.padding.up
+            + style.padding.down
+            + style.margin.up
+            + style.margin.down;
+
+        Ok(())
+    }
 }
 
 impl Container for Bar {
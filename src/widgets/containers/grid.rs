@@ -0,0 +1,462 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    root::Environment,
+    services::Service,
+    util::schedule,
+    widgets::{
+        debug_tree, draw_with_opacity, next_update_of, ScrollEvent, Style, Widget, WidgetData,
+        WidgetError, WidgetList, WidgetNew, WidgetStyled,
+    },
+};
+
+use super::{Container, ContainerSingle};
+
+const fn default_size() -> usize {
+    1
+}
+
+/// Placement of a widget along one axis of it's cell
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CellAlign {
+    /// Left or top edge of the cell
+    #[default]
+    Start,
+    Center,
+    /// Right or bottom edge of the cell
+    End,
+}
+
+impl CellAlign {
+    /// Offset of an item of `size` inside of `space`
+    fn offset(&self, size: usize, space: usize) -> usize {
+        match self {
+            CellAlign::Start => 0,
+            CellAlign::Center => space.saturating_sub(size) / 2,
+            CellAlign::End => space.saturating_sub(size),
+        }
+    }
+}
+
+/// Placement of a widget inside of it's cell
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CellAlignment {
+    #[serde(default)]
+    pub horizontal: CellAlign,
+    #[serde(default)]
+    pub vertical: CellAlign,
+}
+
+/// Alignment of a single cell of a [Grid]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CellSettings {
+    pub row: usize,
+    pub column: usize,
+    #[serde(flatten)]
+    pub align: CellAlignment,
+}
+
+/// Settings of a [Grid] container
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GridSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    #[serde(default = "default_size")]
+    pub rows: usize,
+
+    #[serde(default = "default_size")]
+    pub columns: usize,
+
+    /// Distance between rows in pixels
+    #[serde(default)]
+    pub row_gap: usize,
+
+    /// Distance between columns in pixels
+    #[serde(default)]
+    pub column_gap: usize,
+
+    /// Alignment of widgets inside of their cells
+    #[serde(default)]
+    pub align: CellAlignment,
+
+    /// Alignment of separate cells, overrides [GridSettings::align]
+    #[serde(default)]
+    pub cells: Vec<CellSettings>,
+}
+
+impl Default for GridSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            style: Style::default(),
+            rows: default_size(),
+            columns: default_size(),
+            row_gap: 0,
+            column_gap: 0,
+            align: CellAlignment::default(),
+            cells: Vec::new(),
+        }
+    }
+}
+
+impl GridSettings {
+    /// Alignment of the cell at `row` and `column`
+    pub fn alignment(&self, row: usize, column: usize) -> CellAlignment {
+        self.cells
+            .iter()
+            .find(|cell| cell.row == row && cell.column == column)
+            .map_or(self.align, |cell| cell.align)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GridError {
+    /// Arguments are amount of widgets and amount of cells
+    #[error("Grid has {0} widgets, but only {1} cells")]
+    Overflow(usize, usize),
+
+    #[error(transparent)]
+    Widget(#[from] WidgetError),
+}
+
+/// Container that places widgets into cells of a grid. Widgets fill cells row by row in the order
+/// they were added. Every column is as wide as it's widest widget and every row is as tall as it's
+/// tallest one.
+pub struct Grid {
+    settings: GridSettings,
+    data: RefCell<WidgetData>,
+
+    widgets: RefCell<Vec<Box<dyn Widget>>>,
+    env: Option<Rc<Environment>>,
+    services: RefCell<Vec<Box<dyn Service>>>,
+
+    is_ready: RefCell<bool>,
+}
+
+impl Grid {
+    pub fn widgets_mut(&mut self) -> &mut Vec<Box<dyn Widget>> {
+        self.widgets.get_mut()
+    }
+
+    pub fn add_widget(&mut self, widget: Box<dyn Widget>) {
+        self.widgets.get_mut().push(widget);
+    }
+
+    /// Cell of the widget with the index `i`
+    fn cell(&self, i: usize) -> (usize, usize) {
+        let columns = self.settings.columns.max(1);
+        (i / columns, i % columns)
+    }
+
+    /// Size of the cell contents without border, padding and margins. Widgets should be prepared
+    /// beforehand.
+    fn content_size(&self, columns: &[usize], rows: &[usize]) -> (usize, usize) {
+        let gaps = |sizes: &[usize], gap: usize| gap * sizes.len().saturating_sub(1);
+
+        (
+            columns.iter().sum::<usize>() + gaps(columns, self.settings.column_gap),
+            rows.iter().sum::<usize>() + gaps(rows, self.settings.row_gap),
+        )
+    }
+
+    /// Widths of columns and heights of rows
+    fn track_sizes(&self) -> Result<(Vec<usize>, Vec<usize>), GridError> {
+        let widgets = self.widgets.borrow();
+        let (rows, columns) = (self.settings.rows, self.settings.columns);
+        if widgets.len() > rows * columns {
+            return Err(GridError::Overflow(widgets.len(), rows * columns));
+        }
+
+        let mut widths = vec![0; columns];
+        let mut heights = vec![0; rows];
+        for (i, widget) in widgets.iter().enumerate() {
+            let (row, column) = self.cell(i);
+            let data = widget.try_data()?;
+            widths[column] = widths[column].max(data.width);
+            heights[row] = heights[row].max(data.height);
+        }
+
+        Ok((widths, heights))
+    }
+
+    /// Place widgets into their cells and set the size of the grid
+    fn align_widgets(&self) -> Result<(), GridError> {
+        let (widths, heights) = self.track_sizes()?;
+        let (width, height) = self.content_size(&widths, &heights);
+
+        let style = &self.settings.style;
+        let border = match style.border {
+            Some(a) => a.0,
+            None => 0,
+        };
+
+        let mut data = self.data.borrow_mut();
+        let origin = (
+            data.position.0 + border + style.margin.left + style.padding.left,
+            data.position.1 + border + style.margin.up + style.padding.up,
+        );
+
+        for (i, widget) in self.widgets.borrow().iter().enumerate() {
+            let (row, column) = self.cell(i);
+            let align = self.settings.alignment(row, column);
+            let x: usize = widths[..column]
+                .iter()
+                .map(|w| w + self.settings.column_gap)
+                .sum();
+            let y: usize = heights[..row]
+                .iter()
+                .map(|h| h + self.settings.row_gap)
+                .sum();
+
+            let mut widget = widget.try_data_mut()?;
+            widget.position.0 =
+                origin.0 + x + align.horizontal.offset(widget.width, widths[column]);
+            widget.position.1 = origin.1 + y + align.vertical.offset(widget.height, heights[row]);
+        }
+
+        data.width = width + 2 * border;
+        data.height = height;
+
+        Ok(())
+    }
+}
+
+impl Widget for Grid {
+    fn name(&self) -> WidgetList {
+        WidgetList::Grid
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(Rc::clone(&env));
+
+        for widget in self.widgets.borrow_mut().iter_mut() {
+            widget.bind(Rc::clone(&env))?;
+        }
+
+        for service in self.services.borrow_mut().iter_mut() {
+            if let Err(e) = service.bind(Rc::clone(&env)) {
+                return Err(WidgetError::Custom(e.into()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        for widget in self.widgets.borrow().iter() {
+            widget.init()?;
+            widget.prepare()?;
+        }
+
+        self.align_widgets()
+            .map_err(|e| WidgetError::Custom(e.into()))?;
+        self.apply_style()
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        for widget in self.widgets.borrow().iter() {
+            widget.prepare()?;
+        }
+
+        self.align_widgets()
+            .map_err(|e| WidgetError::Custom(e.into()))?;
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Grid));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+        *self.is_ready.borrow_mut() = false;
+
+        self.draw_style()?;
+
+        let widgets = self.widgets.borrow();
+        let mut ordered: Vec<&dyn Widget> = widgets.iter().map(|w| w.as_ref()).collect();
+        ordered.sort_by_key(|w| w.data().z);
+
+        for widget in ordered {
+            draw_with_opacity(widget)?;
+        }
+
+        Ok(())
+    }
+
+    fn click_at(&self, x: usize, y: usize, button: u32) -> Result<bool, WidgetError> {
+        let widgets = self.widgets.borrow();
+        let mut ordered: Vec<&dyn Widget> = widgets.iter().map(|w| w.as_ref()).collect();
+        ordered.sort_by_key(|w| w.data().z);
+
+        for widget in ordered.into_iter().rev() {
+            if widget.click_at(x, y, button)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn scroll_at(&self, x: usize, y: usize, event: ScrollEvent) -> Result<bool, WidgetError> {
+        let widgets = self.widgets.borrow();
+        let mut ordered: Vec<&dyn Widget> = widgets.iter().map(|w| w.as_ref()).collect();
+        ordered.sort_by_key(|w| w.data().z);
+
+        for widget in ordered.into_iter().rev() {
+            if widget.scroll_at(x, y, event)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn tooltip_at(&self, x: usize, y: usize) -> Option<String> {
+        let widgets = self.widgets.borrow();
+        let mut ordered: Vec<&dyn Widget> = widgets.iter().map(|w| w.as_ref()).collect();
+        ordered.sort_by_key(|w| w.data().z);
+
+        ordered
+            .into_iter()
+            .rev()
+            .find_map(|w| w.tooltip_at(x, y))
+            .or_else(|| {
+                let tooltip = self.settings.style.tooltip.clone()?;
+                self.data().contains(x, y).then_some(tooltip)
+            })
+    }
+
+    fn debug_tree(&self) -> String {
+        let widgets = self.widgets.borrow();
+        debug_tree(
+            self.name(),
+            &self.data(),
+            widgets.iter().map(|w| w.debug_tree()),
+        )
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        let widgets = self.widgets.borrow();
+        let services = self.services.borrow();
+
+        widgets
+            .iter()
+            .map(|w| next_update_of(&**w))
+            .chain(services.iter().map(|s| s.next_update()))
+            .fold(None, schedule::earliest)
+    }
+
+    fn run_services(&self) -> Result<(), WidgetError> {
+        Container::run(self).map_err(WidgetError::Custom)
+    }
+}
+
+impl WidgetNew for Grid {
+    type Settings = GridSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+            settings,
+            env,
+            widgets: RefCell::new(Vec::new()),
+            services: RefCell::new(Vec::new()),
+            is_ready: RefCell::new(false),
+        })
+    }
+}
+
+impl WidgetStyled for Grid {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
+
+impl Container for Grid {
+    fn create_service<W, F>(&mut self, f: F, settings: W::Settings) -> Result<()>
+    where
+        W: crate::services::ServiceNew + crate::services::Service + 'static,
+        F: FnOnce(Option<Rc<Environment>>, W::Settings) -> Result<W, crate::services::ServiceError>,
+    {
+        self.services
+            .borrow_mut()
+            .push(Box::new(f(self.env.clone(), settings)?));
+        Ok(())
+    }
+
+    fn run(&self) -> Result<()> {
+        for service in self.services.borrow_mut().iter() {
+            service.run()?;
+        }
+
+        for widget in self.widgets.borrow().iter() {
+            widget.run_services()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ContainerSingle for Grid {
+    fn create_widget<W, F>(&mut self, f: F, settings: W::Settings) -> Result<(), WidgetError>
+    where
+        W: WidgetNew + Widget + 'static,
+        F: FnOnce(Option<Rc<Environment>>, W::Settings) -> Result<W, WidgetError>,
+    {
+        let widget = f(self.env.clone(), settings)?;
+        if self.env.is_some() {
+            widget.init()?;
+        }
+
+        self.add_widget(Box::new(widget));
+
+        Ok(())
+    }
+
+    fn create_boxed_widget<F>(&mut self, f: F) -> Result<(), WidgetError>
+    where
+        F: FnOnce(Option<Rc<Environment>>) -> Result<Box<dyn Widget>, WidgetError>,
+    {
+        let widget = f(self.env.clone())?;
+        if self.env.is_some() {
+            widget.init()?;
+        }
+
+        self.add_widget(widget);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 pub mod bar;
+pub mod grid;
 pub mod row;
 
 use std::rc::Rc;
@@ -31,4 +32,10 @@ pub trait ContainerSingle: Container {
     where
         W: WidgetNew + Widget + 'static,
         F: FnOnce(Option<Rc<Environment>>, W::Settings) -> Result<W, WidgetError>;
+
+    /// Same as [ContainerSingle::create_widget] for widgets whose type is not known at compile
+    /// time, e.g. [custom](crate::widgets::custom) widgets
+    fn create_boxed_widget<F>(&mut self, f: F) -> Result<(), WidgetError>
+    where
+        F: FnOnce(Option<Rc<Environment>>) -> Result<Box<dyn Widget>, WidgetError>;
 }
@@ -1,22 +1,23 @@
-use std::{
-    cell::{Ref, RefCell, RefMut},
-    rc::Rc,
-};
+use std::{cell::RefCell, rc::Rc, time::Instant};
 
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     root::Environment,
     services::Service,
-    util::Color,
-    widgets::{Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled},
+    util::{schedule, Color},
+    widgets::{
+        debug_tree, draw_with_opacity, next_update_of, ScrollEvent, Style, Widget, WidgetData,
+        WidgetError, WidgetList, WidgetNew, WidgetStyled, WidgetsSettingsList,
+    },
 };
 
 use super::{Container, ContainerSingle};
 
-#[derive(Deserialize, Debug, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", content = "padding")]
 pub enum Alignment {
     CenteringHorizontal,
@@ -44,7 +45,8 @@ impl Alignment {
 }
 
 /// Settings of a [Row] container
-#[derive(Default, Deserialize, Debug, Clone, Copy)]
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RowSettings {
     #[serde(default)]
     pub alignment: Alignment,
@@ -65,11 +67,29 @@ impl RowSettings {
     }
 }
 
+/// Settings of a [Row] nested into another container from config
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RowWidgetSettings {
+    #[serde(default, flatten)]
+    pub row: RowSettings,
+
+    /// Widgets of the row in the order they are aligned
+    #[serde(default)]
+    pub widgets: Vec<WidgetsSettingsList>,
+}
+
 #[derive(Error, Debug)]
 pub enum RowError {
     #[error("Row is not wide enough to display all of it's widgets")]
     WidthOverflow,
 
+    #[error("Row is not high enough to display all of it's widgets")]
+    HeightOverflow,
+
+    #[error(transparent)]
+    Widget(#[from] WidgetError),
+
     #[error("anyhow error: {0}")]
     Other(#[from] anyhow::Error),
 }
@@ -95,12 +115,8 @@ impl Widget for Row {
         Some(self)
     }
 
-    fn data(&self) -> Ref<'_, WidgetData> {
-        self.data.borrow()
-    }
-
-    fn data_mut(&self) -> RefMut<'_, WidgetData> {
-        self.data.borrow_mut()
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
     }
 
     fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
@@ -149,11 +165,13 @@ impl Widget for Row {
     }
 
     fn prepare(&self) -> Result<(), WidgetError> {
-        for widget in self.widgets.borrow_mut().iter() {
-            widget.prepare()?;
+        self.prepare_widgets()?;
+        if let Some(width) = self.target_width() {
+            self.resolve_percent_widths(width);
         }
 
         self.align_widgets()?;
+        self.pad_widgets();
         self.apply_style()?;
 
         *self.is_ready.borrow_mut() = true;
@@ -172,12 +190,84 @@ impl Widget for Row {
 
         self.draw_style()?;
 
-        for widget in self.widgets.borrow_mut().iter() {
-            widget.draw()?;
+        let widgets = self.widgets.borrow();
+        let mut ordered: Vec<&dyn Widget> = widgets.iter().map(|w| w.as_ref()).collect();
+        ordered.sort_by_key(|w| w.data().z);
+
+        for widget in ordered {
+            draw_with_opacity(widget)?;
         }
 
         Ok(())
     }
+
+    fn click_at(&self, x: usize, y: usize, button: u32) -> Result<bool, WidgetError> {
+        let widgets = self.widgets.borrow();
+        let mut ordered: Vec<&dyn Widget> = widgets.iter().map(|w| w.as_ref()).collect();
+        ordered.sort_by_key(|w| w.data().z);
+
+        // Widgets drawn last are on top, so they get the click first
+        for widget in ordered.into_iter().rev() {
+            if widget.click_at(x, y, button)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn scroll_at(&self, x: usize, y: usize, event: ScrollEvent) -> Result<bool, WidgetError> {
+        let widgets = self.widgets.borrow();
+        let mut ordered: Vec<&dyn Widget> = widgets.iter().map(|w| w.as_ref()).collect();
+        ordered.sort_by_key(|w| w.data().z);
+
+        for widget in ordered.into_iter().rev() {
+            if widget.scroll_at(x, y, event)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn tooltip_at(&self, x: usize, y: usize) -> Option<String> {
+        let widgets = self.widgets.borrow();
+        let mut ordered: Vec<&dyn Widget> = widgets.iter().map(|w| w.as_ref()).collect();
+        ordered.sort_by_key(|w| w.data().z);
+
+        ordered
+            .into_iter()
+            .rev()
+            .find_map(|w| w.tooltip_at(x, y))
+            .or_else(|| {
+                let tooltip = self.settings.style.tooltip.clone()?;
+                self.data().contains(x, y).then_some(tooltip)
+            })
+    }
+
+    fn debug_tree(&self) -> String {
+        let widgets = self.widgets.borrow();
+        debug_tree(
+            self.name(),
+            &self.data(),
+            widgets.iter().map(|w| w.debug_tree()),
+        )
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        let widgets = self.widgets.borrow();
+        let services = self.services.borrow();
+
+        widgets
+            .iter()
+            .map(|w| next_update_of(&**w))
+            .chain(services.iter().map(|s| s.next_update()))
+            .fold(None, schedule::earliest)
+    }
+
+    fn run_services(&self) -> Result<(), WidgetError> {
+        Container::run(self).map_err(WidgetError::Custom)
+    }
 }
 
 impl Row {
@@ -201,16 +291,176 @@ impl Row {
         self.widgets.get_mut().push(widget);
     }
 
-    fn get_max_height(widgets: &mut Vec<Box<dyn Widget>>) -> usize {
+    /// Prepare all child widgets without aligning them
+    pub fn prepare_widgets(&self) -> Result<(), WidgetError> {
+        for widget in self.widgets.borrow().iter() {
+            widget.prepare()?;
+        }
+
+        Ok(())
+    }
+
+    /// Width the row needs to fit all of it's widgets including paddings, border and margins.
+    /// Widgets should be prepared beforehand.
+    pub fn content_width(&self) -> usize {
+        let widgets = self.widgets.borrow();
         if widgets.is_empty() {
             return 0;
         }
 
+        let border = match self.settings.style.border {
+            Some((i, _)) => i,
+            None => 0,
+        };
+        let margin = self.settings.style.margin.left + self.settings.style.margin.right;
+        let padding = self.settings.style.padding.left + self.settings.style.padding.right;
+
+        let widths = widgets.iter().map(|w| w.data().width);
+        let content = match self.settings.alignment {
+            Alignment::CenteringHorizontal => widths.sum(),
+            Alignment::CenteringVertical
+            | Alignment::GrowthCenteringVerticalRight(_)
+            | Alignment::GrowthCenteringVerticalLeft(_)
+            | Alignment::GrowthVerticalUp(_)
+            | Alignment::GrowthVerticalDown(_) => widths.max().unwrap_or(0),
+            Alignment::GrowthCenteringHorizontalRight(padding)
+            | Alignment::GrowthCenteringHorizontalLeft(padding)
+            | Alignment::GrowthHorizontalRight(padding)
+            | Alignment::GrowthHorizontalLeft(padding) => {
+                widths.sum::<usize>() + padding * (widgets.len() - 1)
+            }
+        };
+
+        content + 2 * border + margin + padding
+    }
+
+    /// Width [WidgetData::width_percent] of widgets is resolved against. Either the width the
+    /// parent gave to the row for it's own percentage or the configured width, `None` for rows
+    /// sized by their content.
+    fn target_width(&self) -> Option<usize> {
+        let data = self.data.borrow();
+        match (data.width_percent, self.settings.default_data.width) {
+            (Some(_), _) => Some(data.width),
+            (None, 0) => None,
+            (None, width) => Some(width),
+        }
+    }
+
+    /// Set widths of widgets with [WidgetData::width_percent] from the `width` of the row.
+    /// Horizontal rows first give fixed width widgets and paddings their space, percentages are
+    /// taken of what is left and scaled down if they add up to more than 100%. Every widget of a
+    /// vertical row gets it's percentage of the whole inner width.
+    fn resolve_percent_widths(&self, width: usize) {
+        let widgets = self.widgets.borrow();
+        if widgets.iter().all(|w| w.data().width_percent.is_none()) {
+            return;
+        }
+
+        let style = &self.settings.style;
+        let border = style.border.map_or(0, |(i, _)| i);
+        let inner = width.saturating_sub(
+            2 * border
+                + style.margin.left
+                + style.margin.right
+                + style.padding.left
+                + style.padding.right,
+        );
+
+        let padding = match self.settings.alignment {
+            Alignment::CenteringHorizontal => 0,
+            Alignment::GrowthCenteringHorizontalRight(padding)
+            | Alignment::GrowthCenteringHorizontalLeft(padding)
+            | Alignment::GrowthHorizontalRight(padding)
+            | Alignment::GrowthHorizontalLeft(padding) => padding,
+            _ => {
+                for widget in widgets.iter() {
+                    let mut data = widget.data_mut();
+                    if let Some(width) = data.percent_width(inner) {
+                        data.width = width;
+                    }
+                }
+                return;
+            }
+        };
+
+        let fixed: usize = widgets
+            .iter()
+            .map(|w| w.data())
+            .filter(|data| data.width_percent.is_none())
+            .map(|data| data.width)
+            .sum();
+        let left = inner.saturating_sub(fixed + padding * (widgets.len() - 1));
+
+        let total: f32 = widgets
+            .iter()
+            .filter_map(|w| w.data().width_percent)
+            .map(|percent| percent.max(0.0))
+            .sum();
+        let scale = match total > 100.0 {
+            true => 100.0 / total,
+            false => 1.0,
+        };
+
+        for widget in widgets.iter() {
+            let mut data = widget.data_mut();
+            let Some(percent) = data.width_percent else {
+                continue;
+            };
+
+            let scaled = WidgetData {
+                width_percent: Some(percent * scale),
+                ..*data
+            };
+            data.width = scaled.percent_width(left).unwrap_or(0);
+        }
+    }
+
+    /// Center separators of a horizontally aligned row vertically within the height of the
+    /// tallest widget
+    fn center_separators(&self) {
+        let widgets = self.widgets.borrow();
+        let height = widgets.iter().map(|w| w.data().height).max().unwrap_or(0);
+
+        for widget in widgets
+            .iter()
+            .filter(|w| matches!(w.name(), WidgetList::Separator))
+        {
+            let mut data = widget.data_mut();
+            data.position.1 += (height - data.height) / 2;
+        }
+    }
+
+    /// Move aligned widgets by the row's padding, so they are inset from it's border
+    fn pad_widgets(&self) {
+        let padding = self.settings.style.padding;
+        for widget in self.widgets.borrow().iter() {
+            widget.data_mut().position += (padding.left, padding.up);
+        }
+    }
+
+    fn get_max_width(widgets: &[Box<dyn Widget>]) -> Result<usize, WidgetError> {
+        if widgets.is_empty() {
+            return Ok(0);
+        }
+
+        let mut res = 0;
+        for widget in widgets.iter() {
+            res = usize::max(res, widget.try_data()?.width);
+        }
+        Ok(res)
+    }
+
+    fn get_max_height(widgets: &[Box<dyn Widget>]) -> Result<usize, WidgetError> {
+        if widgets.is_empty() {
+            return Ok(0);
+        }
+
         let mut res = 0;
-        for widget in widgets.iter_mut().map(|a| a.data()) {
+        for widget in widgets.iter() {
+            let widget = widget.try_data()?;
             res = usize::max(res, widget.height + widget.position.1);
         }
-        res
+        Ok(res)
     }
 
     fn align_widgets_centered_horizontal(&self) -> Result<(), RowError> {
@@ -225,7 +475,7 @@ impl Row {
 
         if widgets.len() == 1 {
             {
-                let mut widget = widgets[0].data_mut();
+                let mut widget = widgets[0].try_data_mut()?;
 
                 widget.position.0 = data.position.0
                     + (data.width - border * 2 - widget.width) / 2
@@ -236,13 +486,13 @@ impl Row {
                 }
             }
 
-            data.height = Row::get_max_height(&mut widgets) + border;
+            data.height = Row::get_max_height(&widgets)? + border;
             return Ok(());
         }
 
         let mut total_width = 0;
         for widget in widgets.iter_mut() {
-            total_width += widget.data_mut().width;
+            total_width += widget.try_data()?.width;
         }
 
         if total_width > data.width - 2 * border {
@@ -253,7 +503,7 @@ impl Row {
         let mut x = data.position.0 + border;
 
         for widget in widgets.iter_mut() {
-            let mut widget = widget.data_mut();
+            let mut widget = widget.try_data_mut()?;
 
             widget.position.0 = x;
             widget.position.1 = data.position.1;
@@ -261,20 +511,20 @@ impl Row {
             x += widget.width + dist;
         }
 
-        data.height = Row::get_max_height(&mut widgets) + border;
+        data.height = Row::get_max_height(&widgets)? + border;
 
         Ok(())
     }
 
     fn align_widgets_growth_ch(&self, padding: usize) -> Result<()> {
         {
-            let mut widgets = self.widgets.borrow_mut();
+            let widgets = self.widgets.borrow();
             let mut data = self.data.borrow_mut();
 
             data.width = 0;
 
-            for widget in widgets.iter_mut().map(|a| a.data_mut()) {
-                data.width += widget.width + padding;
+            for widget in widgets.iter() {
+                data.width += widget.try_data()?.width + padding;
             }
 
             data.width -= padding;
@@ -286,7 +536,7 @@ impl Row {
     }
 
     fn align_widgets_growth_hr(&self, padding: usize) -> Result<()> {
-        let mut widgets = self.widgets.borrow_mut();
+        let widgets = self.widgets.borrow();
         let mut data = self.data.borrow_mut();
 
         let border = match self.settings.style.border {
@@ -296,7 +546,8 @@ impl Row {
 
         let mut offset = border + data.position.0 + self.settings.style.margin.left;
         data.height = 0;
-        for mut widget in widgets.iter_mut().map(|a| a.data_mut()) {
+        for widget in widgets.iter() {
+            let mut widget = widget.try_data_mut()?;
             widget.position.1 = data.position.1 + self.settings.style.margin.up + border;
             widget.position.0 = offset;
             offset += widget.width + padding;
@@ -310,7 +561,7 @@ impl Row {
     }
 
     fn align_widgets_growth_hl(&self, padding: usize) -> Result<()> {
-        let mut widgets = self.widgets.borrow_mut();
+        let widgets = self.widgets.borrow();
         let mut data = self.data.borrow_mut();
 
         let border = match self.settings.style.border {
@@ -320,7 +571,8 @@ impl Row {
 
         let mut offset = data.position.0 - border - self.settings.style.margin.right;
         data.height = 0;
-        for mut widget in widgets.iter_mut().map(|a| a.data_mut()) {
+        for widget in widgets.iter() {
+            let mut widget = widget.try_data_mut()?;
             widget.position.1 = data.position.1;
             widget.position.0 = offset - widget.width;
             offset -= widget.width + padding;
@@ -335,6 +587,125 @@ impl Row {
         Ok(())
     }
 
+    fn align_widgets_centered_vertical(&self) -> Result<(), RowError> {
+        let mut data = self.data.borrow_mut();
+
+        let border = match self.settings.style.border {
+            Some((i, _)) => i,
+            None => 0,
+        };
+
+        let mut widgets = self.widgets.borrow_mut();
+        let inner = data.height.saturating_sub(2 * border);
+
+        if widgets.len() == 1 {
+            {
+                let mut widget = widgets[0].try_data_mut()?;
+
+                if widget.height > inner {
+                    return Err(RowError::HeightOverflow);
+                }
+
+                widget.position.0 = data.position.0 + border + self.style().margin.left;
+                widget.position.1 =
+                    data.position.1 + (inner - widget.height) / 2 + self.style().margin.up;
+                if let Some(styled) = widgets[0].as_styled() {
+                    widget.position.0 += styled.style().margin.left;
+                }
+            }
+
+            data.width = Row::get_max_width(&widgets)? + 2 * border;
+            return Ok(());
+        }
+
+        let mut total_height = 0;
+        for widget in widgets.iter_mut() {
+            total_height += widget.try_data()?.height;
+        }
+
+        if total_height > inner {
+            return Err(RowError::HeightOverflow);
+        }
+
+        let dist = (inner - total_height) / (widgets.len() - 1);
+        let mut y = data.position.1 + border;
+
+        for widget in widgets.iter_mut() {
+            let mut widget = widget.try_data_mut()?;
+
+            widget.position.0 = data.position.0 + border;
+            widget.position.1 = y;
+
+            y += widget.height + dist;
+        }
+
+        data.width = Row::get_max_width(&widgets)? + 2 * border;
+
+        Ok(())
+    }
+
+    fn align_widgets_growth_vd(&self, padding: usize) -> Result<()> {
+        let widgets = self.widgets.borrow();
+        let mut data = self.data.borrow_mut();
+
+        let border = match self.settings.style.border {
+            Some((i, _)) => i,
+            None => 0,
+        };
+
+        let mut offset = border + data.position.1 + self.settings.style.margin.up;
+        data.width = 0;
+        for widget in widgets.iter() {
+            let mut widget = widget.try_data_mut()?;
+            widget.position.0 = data.position.0 + self.settings.style.margin.left + border;
+            widget.position.1 = offset;
+            offset += widget.height + padding;
+            data.width = usize::max(data.width, widget.width);
+        }
+
+        data.height = offset - padding + border - data.position.1;
+        data.width +=
+            self.settings.style.margin.left + self.settings.style.margin.right + 2 * border;
+
+        Ok(())
+    }
+
+    fn align_widgets_growth_vu(&self, padding: usize) -> Result<()> {
+        let widgets = self.widgets.borrow();
+        let mut data = self.data.borrow_mut();
+
+        let border = match self.settings.style.border {
+            Some((i, _)) => i,
+            None => 0,
+        };
+
+        let mut offset = data
+            .position
+            .1
+            .checked_sub(border + self.settings.style.margin.down)
+            .ok_or(RowError::HeightOverflow)?;
+        let mut top = offset;
+        data.width = 0;
+        for widget in widgets.iter() {
+            let mut widget = widget.try_data_mut()?;
+            widget.position.0 = data.position.0 + self.settings.style.margin.left + border;
+            widget.position.1 = offset
+                .checked_sub(widget.height)
+                .ok_or(RowError::HeightOverflow)?;
+            top = widget.position.1;
+            offset = top.saturating_sub(padding);
+            data.width = usize::max(data.width, widget.width);
+        }
+        data.width +=
+            self.settings.style.margin.left + self.settings.style.margin.right + 2 * border;
+
+        data.height = data.position.1 - top + border;
+
+        data.position.1 = top.checked_sub(border).ok_or(RowError::HeightOverflow)?;
+
+        Ok(())
+    }
+
     fn align_widgets(&self) -> Result<()> {
         if self.widgets.borrow_mut().is_empty() {
             self.data.borrow_mut().height =
@@ -344,7 +715,7 @@ impl Row {
 
         match self.settings.alignment {
             Alignment::CenteringHorizontal => self.align_widgets_centered_horizontal()?,
-            Alignment::CenteringVertical => todo!(),
+            Alignment::CenteringVertical => self.align_widgets_centered_vertical()?,
             Alignment::GrowthCenteringHorizontalRight(padding) => {
                 self.align_widgets_growth_ch(padding)?
             }
@@ -353,10 +724,19 @@ impl Row {
             Alignment::GrowthCenteringVerticalLeft(_) => todo!(),
             Alignment::GrowthHorizontalRight(padding) => self.align_widgets_growth_hr(padding)?,
             Alignment::GrowthHorizontalLeft(padding) => self.align_widgets_growth_hl(padding)?,
-            Alignment::GrowthVerticalUp(_) => todo!(),
-            Alignment::GrowthVerticalDown(_) => todo!(),
+            Alignment::GrowthVerticalUp(padding) => self.align_widgets_growth_vu(padding)?,
+            Alignment::GrowthVerticalDown(padding) => self.align_widgets_growth_vd(padding)?,
         };
 
+        match self.settings.alignment {
+            Alignment::CenteringHorizontal
+            | Alignment::GrowthCenteringHorizontalRight(_)
+            | Alignment::GrowthCenteringHorizontalLeft(_)
+            | Alignment::GrowthHorizontalRight(_)
+            | Alignment::GrowthHorizontalLeft(_) => self.center_separators(),
+            _ => {}
+        }
+
         Ok(())
     }
 }
@@ -401,6 +781,10 @@ impl Container for Row {
             service.run()?;
         }
 
+        for widget in self.widgets.borrow().iter() {
+            widget.run_services()?;
+        }
+
         Ok(())
     }
 }
@@ -411,7 +795,26 @@ impl ContainerSingle for Row {
         W: WidgetNew + Widget + 'static,
         F: FnOnce(Option<Rc<Environment>>, W::Settings) -> Result<W, WidgetError>,
     {
-        self.add_widget(Box::new(f(self.env.clone(), settings)?));
+        let widget = f(self.env.clone(), settings)?;
+        if self.env.is_some() {
+            widget.init()?;
+        }
+
+        self.add_widget(Box::new(widget));
+
+        Ok(())
+    }
+
+    fn create_boxed_widget<F>(&mut self, f: F) -> Result<(), WidgetError>
+    where
+        F: FnOnce(Option<Rc<Environment>>) -> Result<Box<dyn Widget>, WidgetError>,
+    {
+        let widget = f(self.env.clone())?;
+        if self.env.is_some() {
+            widget.init()?;
+        }
+
+        self.add_widget(widget);
 
         Ok(())
     }
@@ -1,18 +1,25 @@
-use std::cell::{Ref, RefCell, RefMut};
+use std::{cell::RefCell, time::Instant};
 
 use anyhow::Result;
 use chrono::{DateTime, Local, TimeDelta};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sysinfo::{CpuRefreshKind, RefreshKind, System};
 
+use crate::util::{animation::Animated, format::NumberFormat, schedule, UpdateRate};
+
 use super::{
-    icon_text::{IconText, IconTextSettings},
+    icon_text::{default_icon_fontid, IconText, IconTextSettings},
     text::TextSettings,
     Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
 };
 
+const fn default_animate() -> bool {
+    true
+}
+
 /// Settings of a [CPU] widget
-#[derive(Deserialize, Debug, Default, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CPUSettings {
     #[serde(default, flatten)]
     pub default_data: WidgetData,
@@ -24,9 +31,58 @@ pub struct CPUSettings {
     #[serde(default, flatten)]
     pub style: Style,
 
-    /// How often to update CPU status in milliseconds
+    /// How often to update CPU status
+    #[serde(default)]
+    pub update_rate: UpdateRate,
+
+    /// Format of displayed usage
+    #[serde(default = "NumberFormat::percent")]
+    pub number_format: NumberFormat,
+
+    /// Glide displayed usage to a new reading over `update_rate` instead of jumping to it. Bar
+    /// refresh rate controls how smooth the animation is.
+    #[serde(default = "default_animate")]
+    pub animate: bool,
+
+    /// Display usage of every core instead of the overall usage, e.g. `12 45 8 90`. Each core
+    /// is formatted with `number_format` decimals but without it's unit and is not animated.
     #[serde(default)]
-    pub update_rate: u32,
+    pub per_core: bool,
+
+    /// Id of font in [Environment::fonts](crate::root::Environment::fonts) the icon is drawn with
+    #[serde(default = "default_icon_fontid")]
+    pub icon_fontid: usize,
+}
+
+impl CPUSettings {
+    /// Usage of every core separated by spaces
+    pub fn format_cores(&self, usages: &[f32]) -> String {
+        let format = NumberFormat {
+            unit: String::new(),
+            ..self.number_format.clone()
+        };
+
+        usages
+            .iter()
+            .map(|usage| format.format(*usage as f64))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Default for CPUSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            update_rate: UpdateRate::ZERO,
+            number_format: NumberFormat::percent(),
+            animate: default_animate(),
+            per_core: false,
+            icon_fontid: default_icon_fontid(),
+        }
+    }
 }
 
 /// Widget displaying current CPU status.
@@ -41,13 +97,25 @@ pub struct CPU {
 
     last_update: RefCell<DateTime<Local>>,
     update_rate: TimeDelta,
+
+    usage: RefCell<Animated<f32>>,
 }
 
 impl CPU {
-    fn get_info(&self) -> usize {
+    fn get_info(&self) -> f32 {
         let mut sys = self.sys.borrow_mut();
         sys.refresh_cpu_usage();
-        sys.global_cpu_usage().round() as usize
+        sys.global_cpu_usage()
+    }
+
+    fn get_cores(&self) -> Vec<f32> {
+        let mut sys = self.sys.borrow_mut();
+        sys.refresh_cpu_specifics(CpuRefreshKind::nothing().with_cpu_usage());
+        sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect()
+    }
+
+    fn animated(&self) -> bool {
+        self.settings.animate && !self.settings.per_core
     }
 }
 
@@ -60,12 +128,8 @@ impl Widget for CPU {
         Some(self)
     }
 
-    fn data(&self) -> Ref<'_, WidgetData> {
-        self.data.borrow()
-    }
-
-    fn data_mut(&self) -> RefMut<'_, WidgetData> {
-        self.data.borrow_mut()
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
     }
 
     fn bind(
@@ -115,21 +179,43 @@ impl Widget for CPU {
 
         let mut last_update = self.last_update.borrow_mut();
 
+        let mut usage = self.usage.borrow_mut();
         if Local::now() - *last_update >= self.update_rate {
-            let info = self.get_info();
-
-            if self.sys.borrow_mut().cpus().is_empty() {
-                self.icon_text.borrow_mut().change_icon("");
-                self.icon_text.borrow_mut().change_text("ERR");
+            if self.settings.per_core {
+                let cores = self.get_cores();
+
+                if cores.is_empty() {
+                    self.icon_text.borrow_mut().change_icon("");
+                    self.icon_text.borrow_mut().change_text("ERR");
+                } else {
+                    self.icon_text
+                        .borrow_mut()
+                        .change_text(&self.settings.format_cores(&cores));
+                }
             } else {
-                self.icon_text
-                    .borrow_mut()
-                    .change_text(format!("{info}%").as_str());
+                let info = self.get_info();
+
+                if self.sys.borrow_mut().cpus().is_empty() {
+                    self.icon_text.borrow_mut().change_icon("");
+                    self.icon_text.borrow_mut().change_text("ERR");
+                } else if self.settings.animate {
+                    usage.set(info);
+                } else {
+                    self.icon_text
+                        .borrow_mut()
+                        .change_text(&self.settings.number_format.format(info as f64));
+                }
             }
 
             *last_update = Local::now();
         }
 
+        if self.animated() && !self.sys.borrow().cpus().is_empty() {
+            self.icon_text
+                .borrow_mut()
+                .change_text(&self.settings.number_format.format(usage.value() as f64));
+        }
+
         {
             let it = self.icon_text.borrow();
             let mut it_data = it.data_mut();
@@ -141,6 +227,18 @@ impl Widget for CPU {
 
         self.icon_text.borrow().draw()
     }
+
+    fn next_update(&self) -> Option<Instant> {
+        // Animated usage is redrawn as often as the bar allows until it reaches the reading
+        if self.animated() && !self.usage.borrow().is_finished() {
+            return Some(Instant::now());
+        }
+
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.update_rate,
+        ))
+    }
 }
 
 impl WidgetNew for CPU {
@@ -163,6 +261,7 @@ impl WidgetNew for CPU {
                 IconTextSettings {
                     icon_settings: settings.text_settings.clone(),
                     text_settings: settings.text_settings.clone(),
+                    icon_fontid: settings.icon_fontid,
                     ..IconTextSettings::default()
                 },
             )?),
@@ -171,10 +270,10 @@ impl WidgetNew for CPU {
                 RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing().with_cpu_usage()),
             )),
 
-            update_rate: TimeDelta::milliseconds(settings.update_rate as i64),
-            last_update: RefCell::new(
-                chrono::Local::now() - TimeDelta::milliseconds(settings.update_rate as i64),
-            ),
+            update_rate: settings.update_rate.as_delta(),
+            last_update: RefCell::new(chrono::Local::now() - settings.update_rate.as_delta()),
+
+            usage: RefCell::new(Animated::new(0.0, settings.update_rate.as_duration())),
 
             settings,
         })
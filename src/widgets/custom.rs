@@ -0,0 +1,67 @@
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    sync::{LazyLock, Mutex},
+};
+
+use anyhow::anyhow;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::root::Environment;
+
+use super::{Widget, WidgetError};
+
+/// Function creating a custom widget from it's config settings
+pub type CustomWidgetBuilder =
+    fn(Option<Rc<Environment>>, &toml::Table) -> Result<Box<dyn Widget>, WidgetError>;
+
+static REGISTRY: LazyLock<Mutex<HashMap<String, CustomWidgetBuilder>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Settings of a custom widget in config. All the keys except `name` are passed to the builder
+/// registered under that name.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CustomSettings {
+    /// Name the widget builder is registered with
+    pub name: String,
+
+    #[serde(default, flatten)]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "std::collections::BTreeMap<String, serde_json::Value>")
+    )]
+    pub settings: toml::Table,
+}
+
+/// Register a custom widget, so it can be created from config as `widget = "custom"` with
+/// provided name in settings. Should be called before the config is applied. Registering the
+/// same name again replaces the builder.
+pub fn register(name: &str, builder: CustomWidgetBuilder) {
+    REGISTRY.lock().unwrap().insert(name.to_string(), builder);
+}
+
+/// Create a custom widget with a builder registered under `settings.name`
+pub fn create(
+    env: Option<Rc<Environment>>,
+    settings: &CustomSettings,
+) -> Result<Box<dyn Widget>, WidgetError> {
+    let builder = REGISTRY.lock().unwrap().get(&settings.name).copied();
+
+    match builder {
+        Some(builder) => builder(env, &settings.settings),
+        None => Err(WidgetError::Custom(anyhow!(
+            "Custom widget \"{}\" is not registered. Register it with \
+            capybar::widgets::custom::register before applying the config",
+            settings.name
+        ))),
+    }
+}
+
+/// Helper for builders to deserialize their settings from a config table
+pub fn parse_settings<T: DeserializeOwned>(settings: &toml::Table) -> Result<T, WidgetError> {
+    settings
+        .clone()
+        .try_into()
+        .map_err(|e: toml::de::Error| WidgetError::Custom(e.into()))
+}
@@ -0,0 +1,240 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use anyhow::{anyhow, Result};
+use chrono::{format::StrftimeItems, DateTime, Local, NaiveDate, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    util::{schedule, Color, UpdateRate},
+    widgets::{text::Text, Widget},
+};
+
+use super::{
+    text::TextSettings, Margin, Style, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+fn default_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+const fn default_update_rate() -> UpdateRate {
+    UpdateRate::from_millis(60000)
+}
+
+/// Settings of a [Date] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DateSettings {
+    /// Default font size
+    #[serde(default)]
+    pub size: f32,
+
+    /// Format of the date in strftime format
+    #[serde(default = "default_format")]
+    pub format: String,
+
+    #[serde(default)]
+    pub font_color: Color,
+
+    #[serde(default)]
+    pub default_data: WidgetData,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// How often to check whether the day changed. Date is also checked at every midnight, the
+    /// rate only matters if the clock jumps, e.g. after a suspend.
+    #[serde(default = "default_update_rate")]
+    pub update_rate: UpdateRate,
+}
+
+impl Default for DateSettings {
+    fn default() -> Self {
+        Self {
+            size: 25.0,
+            format: default_format(),
+
+            font_color: Color::BLACK,
+
+            default_data: WidgetData::default(),
+
+            style: Style::default(),
+
+            update_rate: default_update_rate(),
+        }
+    }
+}
+
+impl DateSettings {
+    /// Check that the format is valid
+    pub fn validate(&self) -> Result<(), WidgetError> {
+        if StrftimeItems::new(&self.format).parse().is_err() {
+            return Err(WidgetError::Custom(anyhow!(
+                "Invalid date format \"{}\"",
+                self.format
+            )));
+        }
+
+        if self.update_rate.is_zero() {
+            return Err(WidgetError::Custom(anyhow!(
+                "Update rate of the date has to be positive"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Widget displaying current date. Supports C's strftime formating. Unlike
+/// [Clock](super::clock::Clock) the text is only changed when the day changes.
+pub struct Date {
+    text: RefCell<Text>,
+    settings: DateSettings,
+
+    data: RefCell<WidgetData>,
+    is_ready: RefCell<bool>,
+
+    /// Day currently shown
+    day: RefCell<NaiveDate>,
+    last_update: RefCell<DateTime<Local>>,
+}
+
+impl Date {
+    /// Format `time` according to the date settings
+    pub fn format(&self, time: &DateTime<Local>) -> String {
+        time.format(&self.settings.format).to_string()
+    }
+
+    /// Change the text if the day changed since the last update. Returns whether it did
+    pub fn update(&self) -> bool {
+        let now = Local::now();
+        *self.last_update.borrow_mut() = now;
+
+        let mut day = self.day.borrow_mut();
+        if *day == now.date_naive() {
+            return false;
+        }
+        *day = now.date_naive();
+
+        self.text.borrow_mut().change_text(&self.format(&now));
+        true
+    }
+}
+
+impl Widget for Date {
+    fn name(&self) -> WidgetList {
+        WidgetList::Date
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.text.borrow().env()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        let text = self.text.borrow();
+
+        text.init()?;
+
+        let text_data = text.data();
+        let mut data = self.data.borrow_mut();
+
+        data.width += text_data.width;
+        data.height += text_data.height;
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let text = self.text.borrow();
+            text.prepare()?;
+            let mut it_data = text.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env().is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Date));
+        }
+        self.draw_style()?;
+        self.update();
+
+        let text = self.text.borrow();
+        text.data_mut().position = self.data.borrow().position;
+        text.draw()
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        let midnight = Instant::now() + schedule::until_next(Local::now(), TimeDelta::days(1));
+        let check = schedule::instant_after(
+            *self.last_update.borrow(),
+            self.settings.update_rate.as_delta(),
+        );
+
+        Some(midnight.min(check))
+    }
+}
+
+impl WidgetNew for Date {
+    type Settings = DateSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        settings.validate()?;
+
+        let now = Local::now();
+        let text = RefCell::new(Text::new(
+            env,
+            TextSettings {
+                text: now.format(&settings.format).to_string(),
+                font_color: settings.font_color,
+                size: settings.size,
+
+                style: Style {
+                    margin: Margin::symmetric(2, 0),
+                    ..Style::default()
+                },
+
+                ..TextSettings::default()
+            },
+        )?);
+        Ok(Date {
+            text,
+            data: RefCell::new(settings.default_data),
+            settings,
+            is_ready: RefCell::new(false),
+            day: RefCell::new(now.date_naive()),
+            last_update: RefCell::new(now),
+        })
+    }
+}
+
+impl WidgetStyled for Date {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
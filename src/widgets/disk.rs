@@ -0,0 +1,251 @@
+use std::{cell::RefCell, path::Path, rc::Rc, time::Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+use sysinfo::{DiskRefreshKind, Disks};
+
+use crate::{
+    root::Environment,
+    util::{format::NumberFormat, schedule, UpdateRate},
+};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+const BYTES_IN_GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+fn default_mount() -> String {
+    "/".to_string()
+}
+
+const fn default_update_rate() -> UpdateRate {
+    UpdateRate::from_millis(10000)
+}
+
+fn default_format() -> String {
+    "{free}G".to_string()
+}
+
+fn default_number_format() -> NumberFormat {
+    NumberFormat::decimals(1)
+}
+
+/// Settings of a [Disk] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DiskSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Mount point of the disk, e.g. `"/home"`
+    #[serde(default = "default_mount")]
+    pub mount: String,
+
+    /// How often to update disk usage
+    #[serde(default = "default_update_rate")]
+    pub update_rate: UpdateRate,
+
+    /// Format of displayed usage. `{used}`, `{free}` and `{total}` are replaced with space in
+    /// GiB, `{percent}` with used share of total space
+    #[serde(default = "default_format")]
+    pub format: String,
+
+    /// Format of `{used}`, `{free}` and `{total}` in [DiskSettings::format], one decimal without
+    /// a unit by default. `{percent}` is a whole number
+    #[serde(default = "default_number_format")]
+    pub number_format: NumberFormat,
+}
+
+impl Default for DiskSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            mount: default_mount(),
+            update_rate: default_update_rate(),
+            format: default_format(),
+            number_format: default_number_format(),
+        }
+    }
+}
+
+impl DiskSettings {
+    /// Fill [DiskSettings::format] with provided used and free space in bytes. Usage is 0% if
+    /// the disk has no space.
+    pub fn format_usage(&self, used: u64, free: u64) -> String {
+        let total = used + free;
+        let percent = match total {
+            0 => 0.0,
+            total => used as f64 / total as f64 * 100.0,
+        };
+        let size = |bytes: u64| self.number_format.format(bytes as f64 / BYTES_IN_GIB);
+
+        self.format
+            .replace("{used}", &size(used))
+            .replace("{free}", &size(free))
+            .replace("{total}", &size(total))
+            .replace("{percent}", &NumberFormat::decimals(0).format(percent))
+    }
+}
+
+/// Widget displaying space usage of a disk mounted at [DiskSettings::mount]
+pub struct Disk {
+    data: RefCell<WidgetData>,
+    settings: DiskSettings,
+    is_ready: RefCell<bool>,
+
+    icon_text: RefCell<IconText>,
+
+    disks: RefCell<Disks>,
+
+    last_update: RefCell<DateTime<Local>>,
+    update_rate: TimeDelta,
+}
+
+impl Disk {
+    /// Used and free space of the configured disk in bytes. `None` if nothing is mounted there
+    fn get_info(&self) -> Option<(u64, u64)> {
+        let mut disks = self.disks.borrow_mut();
+        disks.refresh_specifics(true, DiskRefreshKind::nothing().with_storage());
+
+        let mount = Path::new(&self.settings.mount);
+        let disk = disks.list().iter().find(|d| d.mount_point() == mount)?;
+        let free = disk.available_space();
+        Some((disk.total_space().saturating_sub(free), free))
+    }
+}
+
+impl Widget for Disk {
+    fn name(&self) -> WidgetList {
+        WidgetList::Disk
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.icon_text.borrow().env()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        self.apply_style()?;
+
+        self.icon_text.borrow_mut().change_text("ERR");
+        self.icon_text.borrow_mut().change_icon("󰋊");
+        self.icon_text.borrow().init()?;
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env().is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Disk));
+        }
+
+        self.draw_style()?;
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update >= self.update_rate {
+            let text = match self.get_info() {
+                Some((used, free)) => self.settings.format_usage(used, free),
+                None => "ERR".to_string(),
+            };
+            self.icon_text.borrow_mut().change_text(&text);
+
+            *last_update = Local::now();
+        }
+
+        {
+            let it = self.icon_text.borrow();
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.icon_text.borrow().draw()
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.update_rate,
+        ))
+    }
+}
+
+impl WidgetNew for Disk {
+    type Settings = DiskSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+
+            is_ready: RefCell::new(false),
+
+            icon_text: RefCell::new(IconText::new(
+                env,
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?),
+
+            disks: RefCell::new(Disks::new()),
+
+            update_rate: settings.update_rate.as_delta(),
+            last_update: RefCell::new(DateTime::default()),
+
+            settings,
+        })
+    }
+}
+
+impl WidgetStyled for Disk {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
@@ -1,18 +1,25 @@
-use std::{
-    cell::{Ref, RefCell, RefMut},
-    rc::Rc,
-};
+use std::{cell::RefCell, rc::Rc, time::Instant};
 
+use anyhow::anyhow;
 use serde::Deserialize;
 
-use crate::root::Environment;
+use crate::{
+    root::Environment,
+    util::{schedule, Color},
+};
 
 use super::{
     text::{Text, TextSettings},
     Margin, Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
 };
 
-#[derive(Default, Debug, Clone, Deserialize)]
+/// Id of the font icons are drawn with, unless configured otherwise. The second preloaded font is
+/// expected to be an icon or emoji font.
+pub const fn default_icon_fontid() -> usize {
+    1
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct IconTextSettings {
     #[serde(default, flatten)]
     pub default_data: WidgetData,
@@ -25,6 +32,22 @@ pub struct IconTextSettings {
 
     #[serde(default)]
     pub style: Style,
+
+    /// Id of font in [Environment::fonts](crate::root::Environment::fonts) the icon is drawn with
+    #[serde(default = "default_icon_fontid")]
+    pub icon_fontid: usize,
+}
+
+impl Default for IconTextSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            icon_settings: TextSettings::default(),
+            style: Style::default(),
+            icon_fontid: default_icon_fontid(),
+        }
+    }
 }
 
 pub struct IconText {
@@ -39,33 +62,24 @@ pub struct IconText {
 }
 
 impl IconText {
+    /// Place the icon and the text next to each other. Sizes of both include their margins
+    /// already and they inset themselves by them while drawing, so margins are not added here
     fn align(&self) {
         let mut icon_data = self.icon.data_mut();
-        let icon_style = self.icon.style();
-
         let mut text_data = self.text.data_mut();
-        let text_style = self.text.style();
         let data = &mut self.data.borrow_mut();
-
-        icon_data.position.0 = data.position.0 + icon_style.margin.left;
-        icon_data.position.1 = data.position.1 + icon_style.margin.up;
-        text_data.position.0 = icon_data.position.0
-            + icon_data.width
-            + icon_style.margin.right
-            + text_style.margin.left;
-        text_data.position.1 = data.position.1 + text_style.margin.up;
-
-        data.height = usize::max(
-            text_data.position.1 - data.position.1 + text_data.height + text_style.margin.down,
-            icon_data.position.1 - data.position.1 + icon_data.height + icon_style.margin.down,
-        );
-
-        data.width = icon_style.margin.left
-            + icon_style.margin.right
-            + icon_data.width
-            + text_style.margin.left
-            + text_style.margin.right
-            + text_data.width;
+        // Content is inset by the margin of the whole widget
+        let origin = data.position
+            + (
+                self.settings.style.margin.left,
+                self.settings.style.margin.up,
+            );
+
+        icon_data.position = origin;
+        text_data.position = origin + (icon_data.width, 0);
+
+        data.height = usize::max(icon_data.height, text_data.height);
+        data.width = icon_data.width + text_data.width;
     }
 
     pub fn change_text(&mut self, text: &str) {
@@ -75,6 +89,40 @@ impl IconText {
     pub fn change_icon(&mut self, text: &str) {
         self.icon.change_text(text);
     }
+
+    /// Change color of both the icon and the text
+    pub fn change_color(&mut self, color: Color) {
+        self.change_icon_color(color);
+        self.change_text_color(color);
+    }
+
+    /// Change color of the text only. Layout is not recalculated
+    pub fn change_text_color(&mut self, color: Color) {
+        self.text.change_color(color);
+    }
+
+    /// Change color of the icon only. Layout is not recalculated
+    pub fn change_icon_color(&mut self, color: Color) {
+        self.icon.change_color(color);
+    }
+
+    /// Widget the icon is drawn with
+    pub fn icon(&self) -> &Text {
+        &self.icon
+    }
+
+    /// Widget the text is drawn with
+    pub fn text(&self) -> &Text {
+        &self.text
+    }
+
+    pub fn text_color(&self) -> Color {
+        self.text.color()
+    }
+
+    pub fn icon_color(&self) -> Color {
+        self.icon.color()
+    }
 }
 
 impl Widget for IconText {
@@ -86,12 +134,8 @@ impl Widget for IconText {
         Some(self)
     }
 
-    fn data(&self) -> Ref<'_, WidgetData> {
-        self.data.borrow()
-    }
-
-    fn data_mut(&self) -> RefMut<'_, WidgetData> {
-        self.data.borrow_mut()
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
     }
 
     fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
@@ -106,6 +150,16 @@ impl Widget for IconText {
     }
 
     fn init(&self) -> Result<(), WidgetError> {
+        if let Some(env) = &self.env {
+            let fonts = env.fonts.len();
+            if self.settings.icon_fontid >= fonts {
+                return Err(WidgetError::Custom(anyhow!(
+                    "icon font {} is used, but only {fonts} fonts are loaded",
+                    self.settings.icon_fontid
+                )));
+            }
+        }
+
         self.icon.init()?;
         self.text.init()?;
 
@@ -132,13 +186,14 @@ impl Widget for IconText {
         }
 
         self.draw_style()?;
-        let style = self.style();
-        self.icon.data_mut().position += (style.margin.left, style.margin.up);
-        self.icon.data_mut().position += (style.margin.left, style.margin.up);
 
         self.text.draw()?;
         self.icon.draw()
     }
+
+    fn next_update(&self) -> Option<Instant> {
+        schedule::earliest(self.icon.next_update(), self.text.next_update())
+    }
 }
 
 impl WidgetNew for IconText {
@@ -154,9 +209,7 @@ impl WidgetNew for IconText {
             icon: Text::new(
                 env.clone(),
                 TextSettings {
-                    default_data: WidgetData {
-                        ..WidgetData::default()
-                    },
+                    default_data: WidgetData::default(),
                     style: Style {
                         margin: Margin {
                             left: 2,
@@ -166,7 +219,7 @@ impl WidgetNew for IconText {
                         },
                         ..Style::default()
                     },
-                    fontid: 1,
+                    fontid: settings.icon_fontid,
                     ..settings.text_settings.clone()
                 },
             )?,
@@ -174,9 +227,7 @@ impl WidgetNew for IconText {
             text: Text::new(
                 env.clone(),
                 TextSettings {
-                    default_data: WidgetData {
-                        ..WidgetData::default()
-                    },
+                    default_data: WidgetData::default(),
                     style: Style {
                         margin: Margin {
                             left: 2,
@@ -0,0 +1,238 @@
+use std::{
+    cell::{Ref, RefCell},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::root::Environment;
+
+use super::{Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled};
+
+/// Settings of an [Image] widget
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ImageSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// PNG or JPEG file to show. SVG files require `svg` feature
+    pub path: PathBuf,
+
+    /// Width the image is scaled to in pixels. Aspect ratio is kept if only one of the sizes is
+    /// set, the size of the file is used if none are.
+    #[serde(default)]
+    pub width: Option<usize>,
+
+    /// Height the image is scaled to in pixels
+    #[serde(default)]
+    pub height: Option<usize>,
+}
+
+/// Decoded image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pixels {
+    /// Non-premultiplied `[r, g, b, a]` bytes, row by row
+    pub rgba: Vec<u8>,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Size an image of `natural` size is scaled to, see [ImageSettings::width]
+fn target_size(natural: (f32, f32), width: Option<usize>, height: Option<usize>) -> (usize, usize) {
+    let scaled = |size: usize, from: f32, to: f32| (size as f32 * to / from).round() as usize;
+
+    match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        (Some(width), None) => (width, scaled(width, natural.0, natural.1)),
+        (None, Some(height)) => (scaled(height, natural.1, natural.0), height),
+        (None, None) => (natural.0.round() as usize, natural.1.round() as usize),
+    }
+}
+
+/// Decode an image file and scale it to the requested size. Format is chosen by the extension
+pub fn load(path: &Path, width: Option<usize>, height: Option<usize>) -> Result<Pixels> {
+    let is_svg = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+
+    if is_svg {
+        return load_svg(path, width, height);
+    }
+
+    let image = ::image::open(path)?.into_rgba8();
+    let natural = (image.width() as f32, image.height() as f32);
+    let (width, height) = target_size(natural, width, height);
+
+    let image = match (width as u32, height as u32) == image.dimensions() {
+        true => image,
+        false => ::image::imageops::resize(
+            &image,
+            width as u32,
+            height as u32,
+            ::image::imageops::FilterType::Triangle,
+        ),
+    };
+
+    Ok(Pixels {
+        rgba: image.into_raw(),
+        width,
+        height,
+    })
+}
+
+#[cfg(feature = "svg")]
+fn load_svg(path: &Path, width: Option<usize>, height: Option<usize>) -> Result<Pixels> {
+    use resvg::{tiny_skia, usvg};
+
+    let tree = usvg::Tree::from_data(&std::fs::read(path)?, &usvg::Options::default())?;
+    let size = tree.size();
+    let (width, height) = target_size((size.width(), size.height()), width, height);
+
+    let mut pixmap = tiny_skia::Pixmap::new(width as u32, height as u32)
+        .ok_or_else(|| anyhow!("Image {} is scaled to nothing", path.display()))?;
+    let transform = tiny_skia::Transform::from_scale(
+        width as f32 / size.width(),
+        height as f32 / size.height(),
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    let rgba = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+
+    Ok(Pixels {
+        rgba,
+        width,
+        height,
+    })
+}
+
+#[cfg(not(feature = "svg"))]
+fn load_svg(path: &Path, _width: Option<usize>, _height: Option<usize>) -> Result<Pixels> {
+    Err(anyhow!(
+        "Image {} is an SVG, capybar has to be built with `svg` feature to show it",
+        path.display()
+    ))
+}
+
+/// Widget showing an image file, e.g. a logo. The file is only decoded once on init
+pub struct Image {
+    data: RefCell<WidgetData>,
+    settings: ImageSettings,
+    is_ready: RefCell<bool>,
+
+    pixels: RefCell<Option<Pixels>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Image {
+    /// Decoded image. `None` before init
+    pub fn pixels(&self) -> Ref<'_, Option<Pixels>> {
+        self.pixels.borrow()
+    }
+}
+
+impl Widget for Image {
+    fn name(&self) -> WidgetList {
+        WidgetList::Image
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env);
+        Ok(())
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        let settings = &self.settings;
+        let pixels = load(&settings.path, settings.width, settings.height).map_err(|e| {
+            WidgetError::Custom(anyhow!(
+                "Could not load image {}: {e}",
+                settings.path.display()
+            ))
+        })?;
+
+        *self.pixels.borrow_mut() = Some(pixels);
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let pixels = self.pixels.borrow();
+            let mut data = self.data.borrow_mut();
+            (data.width, data.height) = pixels
+                .as_ref()
+                .map_or((0, 0), |pixels| (pixels.width, pixels.height));
+        }
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Image));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        self.draw_style()?;
+
+        if let Some(pixels) = self.pixels.borrow().as_ref() {
+            let data = self.data.borrow();
+            let mut drawer = self.env.as_ref().unwrap().drawer.borrow_mut();
+            drawer.draw_image(&data, &pixels.rgba, pixels.width, pixels.height)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl WidgetNew for Image {
+    type Settings = ImageSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+            settings,
+            is_ready: RefCell::new(false),
+            pixels: RefCell::new(None),
+            env,
+        })
+    }
+}
+
+impl WidgetStyled for Image {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
@@ -1,24 +1,21 @@
-use std::{
-    cell::{Ref, RefCell, RefMut},
-    collections::HashMap,
-    rc::Rc,
-};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     root::Environment, services::ServiceList, util::signals::SignalNames, widgets::Widget,
 };
 
 use super::{
-    icon_text::{IconText, IconTextSettings},
+    icon_text::{default_icon_fontid, IconText, IconTextSettings},
     text::TextSettings,
     Style, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
 };
 
 /// Settings of a [Keyboard] widget
-#[derive(Deserialize, Default, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct KeyboardSettings {
     #[serde(default, flatten)]
     pub default_data: WidgetData,
@@ -33,6 +30,31 @@ pub struct KeyboardSettings {
     /// Map from underlying layout name to display name
     #[serde(default)]
     pub layout_mappings: HashMap<String, String>,
+
+    /// Signals emitting layout names. The latest value emitted by any of them is displayed
+    #[serde(default = "default_signals")]
+    pub signals: Vec<SignalNames>,
+
+    /// Id of font in [Environment::fonts](crate::root::Environment::fonts) the icon is drawn with
+    #[serde(default = "default_icon_fontid")]
+    pub icon_fontid: usize,
+}
+
+fn default_signals() -> Vec<SignalNames> {
+    vec![SignalNames::Keyboard]
+}
+
+impl Default for KeyboardSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            layout_mappings: HashMap::new(),
+            signals: default_signals(),
+            icon_fontid: default_icon_fontid(),
+        }
+    }
 }
 
 /// Widget displaying current keyboard layout.
@@ -42,6 +64,7 @@ pub struct Keyboard {
     is_ready: RefCell<bool>,
 
     layout_mappings: Rc<HashMap<String, String>>,
+    signals: Vec<SignalNames>,
 
     icon_text: Rc<RefCell<IconText>>,
 
@@ -57,12 +80,8 @@ impl Widget for Keyboard {
         Some(self)
     }
 
-    fn data(&self) -> Ref<'_, WidgetData> {
-        self.data.borrow()
-    }
-
-    fn data_mut(&self) -> RefMut<'_, WidgetData> {
-        self.data.borrow_mut()
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
     }
 
     fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
@@ -79,9 +98,11 @@ impl Widget for Keyboard {
             return Err(WidgetError::InitWithNoEnv(WidgetList::Keyboard));
         }
 
-        let signals = self.env.as_ref().unwrap().signals.borrow_mut();
+        let env = self.env.as_ref().unwrap();
 
-        if !signals.contains_key(&SignalNames::Keyboard) {
+        if self.signals.contains(&SignalNames::Keyboard)
+            && !env.signals.borrow().contains_key(&SignalNames::Keyboard)
+        {
             return Err(WidgetError::NoCorespondingSignal(
                 WidgetList::Keyboard,
                 ServiceList::Keyboard,
@@ -91,7 +112,7 @@ impl Widget for Keyboard {
         let signal_ic = Rc::clone(&self.icon_text);
         let layout_mappings = Rc::clone(&self.layout_mappings);
 
-        signals[&SignalNames::Keyboard].connect(move |data| {
+        env.connect_signals(&self.signals, move |_, data| {
             if let Some(text) = data.downcast_ref::<String>() {
                 let layout = if layout_mappings.contains_key(text) {
                     layout_mappings.get(text).unwrap()
@@ -162,12 +183,14 @@ impl WidgetNew for Keyboard {
             is_ready: RefCell::new(false),
 
             layout_mappings: Rc::new(settings.layout_mappings),
+            signals: settings.signals,
 
             icon_text: Rc::new(RefCell::new(IconText::new(
                 env.clone(),
                 IconTextSettings {
                     icon_settings: settings.text_settings.clone(),
                     text_settings: settings.text_settings.clone(),
+                    icon_fontid: settings.icon_fontid,
                     ..IconTextSettings::default()
                 },
             )?)),
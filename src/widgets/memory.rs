@@ -0,0 +1,236 @@
+use std::{cell::RefCell, time::Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+use sysinfo::{MemoryRefreshKind, RefreshKind, System};
+
+use crate::util::{format::NumberFormat, schedule, UpdateRate};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+const BYTES_IN_GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+fn default_format() -> String {
+    "{used}/{total}G".to_string()
+}
+
+fn default_number_format() -> NumberFormat {
+    NumberFormat::decimals(1)
+}
+
+/// Settings of a [Memory] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MemorySettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// How often to update memory status
+    #[serde(default)]
+    pub update_rate: UpdateRate,
+
+    /// Format of displayed usage. `{used}` and `{total}` are replaced with memory in GiB,
+    /// `{percent}` with used share of total memory
+    #[serde(default = "default_format")]
+    pub format: String,
+
+    /// Format of `{used}` and `{total}` in [MemorySettings::format], one decimal without a unit
+    /// by default. `{percent}` is a whole number
+    #[serde(default = "default_number_format")]
+    pub number_format: NumberFormat,
+}
+
+impl Default for MemorySettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            update_rate: UpdateRate::ZERO,
+            format: default_format(),
+            number_format: default_number_format(),
+        }
+    }
+}
+
+impl MemorySettings {
+    /// Fill [MemorySettings::format] with provided usage in bytes. Usage is 0% if total is zero.
+    pub fn format_usage(&self, used: u64, total: u64) -> String {
+        let percent = match total {
+            0 => 0.0,
+            total => used as f64 / total as f64 * 100.0,
+        };
+        let size = |bytes: u64| self.number_format.format(bytes as f64 / BYTES_IN_GIB);
+
+        self.format
+            .replace("{used}", &size(used))
+            .replace("{total}", &size(total))
+            .replace("{percent}", &NumberFormat::decimals(0).format(percent))
+    }
+}
+
+/// Widget displaying current memory usage.
+pub struct Memory {
+    data: RefCell<WidgetData>,
+    settings: MemorySettings,
+    is_ready: RefCell<bool>,
+
+    icon_text: RefCell<IconText>,
+
+    sys: RefCell<System>,
+
+    last_update: RefCell<DateTime<Local>>,
+    update_rate: TimeDelta,
+}
+
+impl Memory {
+    fn get_info(&self) -> (u64, u64) {
+        let mut sys = self.sys.borrow_mut();
+        sys.refresh_memory();
+        (sys.used_memory(), sys.total_memory())
+    }
+}
+
+impl Widget for Memory {
+    fn name(&self) -> WidgetList {
+        WidgetList::Memory
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(
+        &mut self,
+        env: std::rc::Rc<crate::root::Environment>,
+    ) -> anyhow::Result<(), WidgetError> {
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<std::rc::Rc<crate::root::Environment>> {
+        self.icon_text.borrow().env()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        self.apply_style()?;
+
+        self.icon_text.borrow_mut().change_text("Err");
+        self.icon_text.borrow_mut().change_icon("󰍛");
+        self.icon_text.borrow().init()?;
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env().is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Memory));
+        }
+
+        self.draw_style()?;
+
+        let mut last_update = self.last_update.borrow_mut();
+
+        if Local::now() - *last_update >= self.update_rate {
+            let (used, total) = self.get_info();
+
+            self.icon_text
+                .borrow_mut()
+                .change_text(&self.settings.format_usage(used, total));
+
+            *last_update = Local::now();
+        }
+
+        {
+            let it = self.icon_text.borrow();
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.icon_text.borrow().draw()
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.update_rate,
+        ))
+    }
+}
+
+impl WidgetNew for Memory {
+    type Settings = MemorySettings;
+
+    fn new(
+        env: Option<std::rc::Rc<crate::root::Environment>>,
+        settings: Self::Settings,
+    ) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+
+            is_ready: RefCell::new(false),
+
+            icon_text: RefCell::new(IconText::new(
+                env.clone(),
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?),
+
+            sys: RefCell::new(System::new_with_specifics(
+                RefreshKind::nothing().with_memory(MemoryRefreshKind::nothing().with_ram()),
+            )),
+
+            update_rate: settings.update_rate.as_delta(),
+            last_update: RefCell::new(chrono::Local::now() - settings.update_rate.as_delta()),
+
+            settings,
+        })
+    }
+}
+
+impl WidgetStyled for Memory {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
@@ -0,0 +1,235 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    services::{microphone::MicrophoneState, ServiceList},
+    util::signals::SignalNames,
+};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Interactive, Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+fn default_icon() -> String {
+    "󰍬".to_string()
+}
+
+fn default_muted_icon() -> String {
+    "󰍭".to_string()
+}
+
+const fn default_show_level() -> bool {
+    true
+}
+
+/// Settings of a [Microphone] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MicrophoneSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Icon displayed while the microphone is not muted
+    #[serde(default = "default_icon")]
+    pub icon: String,
+
+    /// Icon displayed while the microphone is muted or there is no input device
+    #[serde(default = "default_muted_icon")]
+    pub muted_icon: String,
+
+    /// Show input level next to the icon
+    #[serde(default = "default_show_level")]
+    pub show_level: bool,
+}
+
+impl Default for MicrophoneSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            icon: default_icon(),
+            muted_icon: default_muted_icon(),
+            show_level: default_show_level(),
+        }
+    }
+}
+
+impl MicrophoneSettings {
+    /// Icon matching the microphone state
+    pub fn icon(&self, state: &MicrophoneState) -> &str {
+        match state.available && !state.muted {
+            true => &self.icon,
+            false => &self.muted_icon,
+        }
+    }
+
+    /// Input level shown next to the icon. Empty if there is no input device
+    pub fn text(&self, state: &MicrophoneState) -> String {
+        if !state.available || !self.show_level {
+            return String::new();
+        }
+
+        format!("{}%", state.volume)
+    }
+}
+
+/// Widget displaying whether the default input device is muted and it's level. Clicking it
+/// toggles mute. Requires [Microphone](crate::services::microphone::Microphone) service.
+pub struct Microphone {
+    data: RefCell<WidgetData>,
+    settings: Rc<MicrophoneSettings>,
+    is_ready: RefCell<bool>,
+
+    icon_text: Rc<RefCell<IconText>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for Microphone {
+    fn name(&self) -> WidgetList {
+        WidgetList::Microphone
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn as_interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::Microphone));
+        }
+
+        let env = self.env.as_ref().unwrap();
+
+        if !env.signals.borrow().contains_key(&SignalNames::Microphone) {
+            return Err(WidgetError::NoCorespondingSignal(
+                WidgetList::Microphone,
+                ServiceList::Microphone,
+            ));
+        }
+
+        {
+            let mut ic = self.icon_text.borrow_mut();
+            ic.change_icon(&self.settings.muted_icon);
+            ic.change_text("");
+            ic.init()?;
+        }
+
+        let signal_ic = Rc::clone(&self.icon_text);
+        let settings = Rc::clone(&self.settings);
+
+        env.signals.borrow()[&SignalNames::Microphone]
+            .typed::<MicrophoneState>()
+            .connect(move |state| {
+                let mut ic = signal_ic.borrow_mut();
+                ic.change_icon(settings.icon(state));
+                ic.change_text(&settings.text(state));
+            });
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Microphone));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        self.draw_style()?;
+
+        {
+            let ic_data = self.icon_text.borrow();
+            ic_data.data_mut().position = self.data().position;
+        }
+        self.icon_text.borrow_mut().draw()
+    }
+}
+
+impl WidgetNew for Microphone {
+    type Settings = MicrophoneSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Microphone {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            icon_text: Rc::new(RefCell::new(IconText::new(
+                env.clone(),
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?)),
+
+            settings: Rc::new(settings),
+
+            env: None,
+        })
+    }
+}
+
+impl Interactive for Microphone {
+    fn click(&self, _button: u32) -> Result<(), WidgetError> {
+        MicrophoneState::toggle_mute().map_err(WidgetError::Custom)
+    }
+}
+
+impl WidgetStyled for Microphone {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
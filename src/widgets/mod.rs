@@ -1,27 +1,61 @@
 pub mod containers;
 
 pub mod battery;
+pub mod brightness;
 pub mod clock;
 pub mod cpu;
+pub mod custom;
+pub mod date;
+pub mod disk;
 pub mod icon_text;
+#[cfg(feature = "image")]
+pub mod image;
 pub mod keyboard;
+pub mod memory;
+pub mod microphone;
+pub mod modifiers;
+#[cfg(feature = "mpris")]
+pub mod mpris;
+#[cfg(feature = "notifications")]
+pub mod notifications;
+#[cfg(feature = "power_profile")]
+pub mod power_profile;
+pub mod progress_bar;
+pub mod script;
+pub mod separator;
+pub mod spacer;
+pub mod temperature;
 pub mod text;
+#[cfg(feature = "tray")]
+pub mod tray;
+pub mod volume;
+#[cfg(feature = "weather")]
+pub mod weather;
+#[cfg(feature = "hyprland")]
+pub mod window_title;
+#[cfg(feature = "hyprland")]
+pub mod workspaces;
 
 use std::{
-    cell::{Ref, RefMut},
+    cell::{Ref, RefCell, RefMut},
     fmt::Display,
     ops::{Add, AddAssign},
     rc::Rc,
+    sync::LazyLock,
+    time::{Duration, Instant},
 };
 
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{
+    de::{value::MapAccessDeserializer, IntoDeserializer, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 use thiserror::Error;
 
 use crate::{
     root::Environment,
     services::{ProcessSettings, ServiceList, ServiceNew},
-    util::Color,
+    util::{drawer::DrawerError, schedule, Color, Drawer},
 };
 
 use {battery::BatterySettings, clock::ClockSettings, cpu::CPUSettings, text::TextSettings};
@@ -48,18 +82,36 @@ pub trait Widget {
     /// Prepare [Widget] for a first draw
     fn init(&self) -> Result<(), WidgetError>;
 
-    /// Return [WidgetData] associated to the widget immutably
-    fn data(&self) -> Ref<'_, WidgetData>;
+    /// Cell storing [WidgetData] associated to the widget. Prefer accessors below to borrowing it
+    /// directly.
+    fn data_cell(&self) -> &RefCell<WidgetData>;
 
-    /// Return [WidgetData] associated to the widget mutably
-    fn data_mut(&self) -> RefMut<'_, WidgetData>;
+    /// Return [WidgetData] associated to the widget immutably. Panics if it is borrowed mutably,
+    /// see [Widget::try_data]
+    fn data(&self) -> Ref<'_, WidgetData> {
+        self.data_cell().borrow()
+    }
 
-    fn try_data(&self) -> Ref<'_, WidgetData> {
-        todo!()
+    /// Return [WidgetData] associated to the widget mutably. Panics if it is already borrowed,
+    /// see [Widget::try_data_mut]
+    fn data_mut(&self) -> RefMut<'_, WidgetData> {
+        self.data_cell().borrow_mut()
     }
 
-    fn try_data_mut(&self) -> RefMut<'_, WidgetData> {
-        todo!()
+    /// Return [WidgetData] associated to the widget immutably or
+    /// [WidgetError::DataBorrowed] if it is borrowed mutably
+    fn try_data(&self) -> Result<Ref<'_, WidgetData>, WidgetError> {
+        self.data_cell()
+            .try_borrow()
+            .map_err(|_| WidgetError::DataBorrowed(self.name()))
+    }
+
+    /// Return [WidgetData] associated to the widget mutably or [WidgetError::DataBorrowed] if it
+    /// is already borrowed
+    fn try_data_mut(&self) -> Result<RefMut<'_, WidgetData>, WidgetError> {
+        self.data_cell()
+            .try_borrow_mut()
+            .map_err(|_| WidgetError::DataBorrowed(self.name()))
     }
 
     /// Runtime check if widget is styled
@@ -67,6 +119,111 @@ pub trait Widget {
     fn as_styled(&self) -> Option<&dyn WidgetStyled> {
         None
     }
+
+    /// Runtime check if widget reacts to clicks
+    /// Override this function with body `Some(self)` if [Interactive] implemented
+    fn as_interactive(&self) -> Option<&dyn Interactive> {
+        None
+    }
+
+    /// Route a click at global position to the widget. Returns `true` if the click was handled.
+    /// Containers override it to pass the click to the top child under the pointer.
+    fn click_at(&self, x: usize, y: usize, button: u32) -> Result<bool, WidgetError> {
+        match self.as_interactive() {
+            Some(interactive) if interactive.hit_test(x, y) => {
+                interactive.click(button)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Route a scroll at global position to the widget. Returns `true` if the scroll was handled.
+    /// Containers override it to pass the scroll to the top child under the pointer.
+    fn scroll_at(&self, x: usize, y: usize, event: ScrollEvent) -> Result<bool, WidgetError> {
+        match self.as_interactive() {
+            Some(interactive) if interactive.hit_test(x, y) => {
+                interactive.scroll(event)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// [Tooltip](Style::tooltip) of the widget under a global position. Containers override it to
+    /// ask the top child under the pointer first.
+    fn tooltip_at(&self, x: usize, y: usize) -> Option<String> {
+        let tooltip = self.as_styled()?.style().tooltip.clone()?;
+        self.data().contains(x, y).then_some(tooltip)
+    }
+
+    /// Moment the widget has to be redrawn on it's own, e.g. when it polls it's data next time.
+    /// `None` if the widget only changes in response to events and signals. Containers return the
+    /// earliest moment among their children.
+    fn next_update(&self) -> Option<Instant> {
+        None
+    }
+
+    /// Run [Service](crate::services::Service)s owned by the widget. Containers run their own
+    /// services and the ones of nested containers.
+    fn run_services(&self) -> Result<(), WidgetError> {
+        Ok(())
+    }
+
+    /// Computed geometry of the widget, one line per widget. Containers list their children
+    /// below themselves, see [debug_tree].
+    fn debug_tree(&self) -> String {
+        debug_tree(self.name(), &self.data(), [])
+    }
+}
+
+/// Line with name, position and size of a widget followed by trees of it's `children` indented
+/// by two spaces, e.g.
+/// ```text
+/// Row (0, 0) 120x24
+///   Cpu (2, 0) 40x24
+/// ```
+pub fn debug_tree(
+    name: WidgetList,
+    data: &WidgetData,
+    children: impl IntoIterator<Item = String>,
+) -> String {
+    let mut tree = format!(
+        "{name} ({}, {}) {}x{}",
+        data.position.0, data.position.1, data.width, data.height
+    );
+    for child in children {
+        for line in child.lines() {
+            tree.push_str("\n  ");
+            tree.push_str(line);
+        }
+    }
+
+    tree
+}
+
+/// [Widget] that reacts to pointer clicks
+pub trait Interactive: Widget {
+    /// Check if a global position lies inside of the widget
+    fn hit_test(&self, x: usize, y: usize) -> bool {
+        self.data().contains(x, y)
+    }
+
+    /// Handle a click. `button` is a linux input event code, e.g. `0x110` for the left button
+    fn click(&self, button: u32) -> Result<(), WidgetError>;
+
+    /// Handle a scroll. Scrolls are ignored by default
+    fn scroll(&self, _event: ScrollEvent) -> Result<(), WidgetError> {
+        Ok(())
+    }
+}
+
+/// Pointer scroll over a widget in surface coordinates. Positive values scroll down and to the
+/// right.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScrollEvent {
+    pub delta_x: f64,
+    pub delta_y: f64,
 }
 
 /// A `Widget` that can be unifiedly created.
@@ -106,13 +263,27 @@ pub enum WidgetError {
     )]
     StyleInitDataBorrowed(WidgetList),
 
+    /// Argument is a name of a widget
+    #[error("Data of widget \"{0}\" is already borrowed")]
+    DataBorrowed(WidgetList),
+
+    #[error(transparent)]
+    Drawer(#[from] DrawerError),
+
     #[error(transparent)]
     Custom(#[from] anyhow::Error),
 }
 
-#[derive(Default, Debug, Clone, Copy, Deserialize)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Position(pub usize, pub usize);
 
+impl Position {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self(x, y)
+    }
+}
+
 impl AddAssign for Position {
     fn add_assign(&mut self, rhs: Self) {
         self.0 += rhs.0;
@@ -144,7 +315,8 @@ impl Add<(usize, usize)> for Position {
 }
 
 /// Global common data used by `Widget` data structure.
-#[derive(Default, Debug, Clone, Copy, Deserialize)]
+#[derive(Default, Debug, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct WidgetData {
     /// Offset of the widget in a global scope. Usually controlled by parent.
     #[serde(default)]
@@ -157,6 +329,32 @@ pub struct WidgetData {
     /// Height of the widget should be controlled by the widget itself
     #[serde(default)]
     pub height: usize,
+
+    /// Width in percent of the space the parent gives to the widget, overriding the width the
+    /// widget would take otherwise. Rows of the [Bar](containers::bar::Bar) are sized by the width
+    /// of the bar, widgets in a [Row](containers::row::Row) share the space of a row with a known
+    /// width left by their fixed width siblings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width_percent: Option<f32>,
+
+    /// Drawing order among siblings. Widgets with higher `z` are drawn on top, widgets with equal
+    /// `z` are drawn in insertion order.
+    #[serde(default)]
+    pub z: i32,
+
+    /// Content of the widget changed and the area it covers has to be damaged on the next frame.
+    /// Cleared by [WidgetData::report_damage].
+    #[serde(skip)]
+    pub dirty: bool,
+
+    /// Area covered by the widget on the last frame as `(x, y, width, height)`
+    #[serde(skip)]
+    pub drawn_area: Option<(usize, usize, usize, usize)>,
+
+    /// Position the widget was placed at before [WidgetStyled::draw_style] inset it by the margin
+    /// and the padding
+    #[serde(skip)]
+    pub base_position: Option<Position>,
 }
 
 impl WidgetData {
@@ -165,11 +363,81 @@ impl WidgetData {
             position: Position(0, 0),
             width: 0,
             height: 0,
+            width_percent: None,
+            z: 0,
+            dirty: false,
+            drawn_area: None,
+            base_position: None,
+        }
+    }
+
+    /// Width of [WidgetData::width_percent] of `available` pixels, rounded down. Percentage is
+    /// clamped to `0..=100`
+    pub fn percent_width(&self, available: usize) -> Option<usize> {
+        let percent = self.width_percent?;
+        let percent = match percent.is_nan() {
+            true => 0.0,
+            false => percent.clamp(0.0, 100.0),
+        };
+
+        Some((available as f64 * percent as f64 / 100.0).floor() as usize)
+    }
+
+    /// Area covered by the widget as `(x, y, width, height)`
+    pub fn area(&self) -> (usize, usize, usize, usize) {
+        (self.position.0, self.position.1, self.width, self.height)
+    }
+
+    /// Check if a global position lies inside of the widget
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.position.0..self.position.0 + self.width).contains(&x)
+            && (self.position.1..self.position.1 + self.height).contains(&y)
+    }
+
+    /// Damage the area of the widget if it is [dirty](WidgetData::dirty) or was moved or resized
+    /// since the last frame. Old area is damaged too, so nothing is left behind. Should be called
+    /// by every widget that draws to the [Drawer](crate::util::Drawer).
+    pub fn report_damage(&mut self, drawer: &mut Drawer) {
+        let area = self.area();
+        if self.dirty || self.drawn_area != Some(area) {
+            if let Some(old) = self.drawn_area {
+                drawer.damage(old);
+            }
+            drawer.damage(area);
+        }
+
+        self.dirty = false;
+        self.drawn_area = Some(area);
+    }
+
+    /// [WidgetData] with provided width and everything else default
+    pub const fn with_width(width: usize) -> Self {
+        Self {
+            width,
+            ..Self::default()
+        }
+    }
+
+    /// [WidgetData] with provided width and height and everything else default
+    pub const fn with_size(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            ..Self::default()
+        }
+    }
+
+    /// [WidgetData] with provided position and everything else default
+    pub const fn with_position(position: Position) -> Self {
+        Self {
+            position,
+            ..Self::default()
         }
     }
 }
 
-#[derive(Default, Debug, Clone, Copy, Deserialize)]
+#[derive(Default, Debug, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Margin {
     pub left: usize,
     pub right: usize,
@@ -186,12 +454,230 @@ impl Margin {
             down: 0,
         }
     }
+
+    /// Same margin on every side
+    pub const fn uniform(margin: usize) -> Self {
+        Self {
+            left: margin,
+            right: margin,
+            up: margin,
+            down: margin,
+        }
+    }
+
+    /// Whether the margin is empty on every side
+    pub const fn is_zero(&self) -> bool {
+        self.left == 0 && self.right == 0 && self.up == 0 && self.down == 0
+    }
+
+    /// Shift of content inset by the margin on the left and top
+    pub const fn offset(&self) -> Position {
+        Position(self.left, self.up)
+    }
+
+    /// `horizontal` margin on the left and right, `vertical` margin on the top and bottom
+    pub const fn symmetric(horizontal: usize, vertical: usize) -> Self {
+        Self {
+            left: horizontal,
+            right: horizontal,
+            up: vertical,
+            down: vertical,
+        }
+    }
+}
+
+/// Background of a [Style]. Deserialized either from a [Color] or from a table describing a
+/// gradient, e.g. `{ from = "#1e1e2eff", to = "#313244ff", angle = 90 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Background {
+    Solid(Color),
+    /// Gradient between two colors. `angle` in degrees sets the direction of the gradient: 0 goes
+    /// from left to right, 90 from top to bottom.
+    LinearGradient {
+        from: Color,
+        to: Color,
+        #[serde(default)]
+        angle: f32,
+    },
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Background::Solid(Color::NONE)
+    }
+}
+
+impl From<Color> for Background {
+    fn from(color: Color) -> Self {
+        Background::Solid(color)
+    }
+}
+
+impl Background {
+    /// Color of the pixel at `(x, y)` of a `width` x `height` area filled with the background
+    pub fn color_at(&self, x: usize, y: usize, width: usize, height: usize) -> Color {
+        let (from, to, angle) = match *self {
+            Background::Solid(color) => return color,
+            Background::LinearGradient { from, to, angle } => (from, to, angle),
+        };
+
+        // Pixel centers are projected on the direction of the gradient, the area's corners
+        // furthest along it get `from` and `to`
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (width, height) = (width as f32, height as f32);
+        let half = (width * cos.abs() + height * sin.abs()) / 2.0;
+        if half == 0.0 {
+            return from;
+        }
+
+        let projection =
+            (x as f32 + 0.5 - width / 2.0) * cos + (y as f32 + 0.5 - height / 2.0) * sin;
+        Color::lerp(&from, &to, (projection + half) / (2.0 * half))
+    }
+}
+
+impl<'de> Deserialize<'de> for Background {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Gradient {
+            from: Color,
+            to: Color,
+            #[serde(default)]
+            angle: f32,
+        }
+
+        struct BackgroundVisitor;
+
+        impl<'de> Visitor<'de> for BackgroundVisitor {
+            type Value = Background;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a color or a gradient with `from`, `to` and `angle`")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Color::deserialize(v.into_deserializer()).map(Background::Solid)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Color::deserialize(v.into_deserializer()).map(Background::Solid)
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Color::deserialize(v.into_deserializer()).map(Background::Solid)
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let gradient = Gradient::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(Background::LinearGradient {
+                    from: gradient.from,
+                    to: gradient.to,
+                    angle: gradient.angle,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(BackgroundVisitor)
+    }
+}
+
+/// Moment phases of all [Animation]s are counted from, so widgets animated with the same period
+/// blink together
+static ANIMATION_EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+/// Effect repeatedly changing opacity of a widget over time, see [Style::animation]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Animation {
+    #[default]
+    None,
+    /// Widget is shown for the first half of every period and hidden for the second one
+    Blink { period_ms: u64 },
+    /// Opacity smoothly falls to 0 and rises back every period
+    Pulse { period_ms: u64 },
+}
+
+impl Animation {
+    pub fn is_none(&self) -> bool {
+        matches!(self, Animation::None)
+    }
+
+    fn period(&self) -> Option<Duration> {
+        match *self {
+            Animation::None => None,
+            Animation::Blink { period_ms } | Animation::Pulse { period_ms } => {
+                (period_ms > 0).then(|| Duration::from_millis(period_ms))
+            }
+        }
+    }
+
+    /// Opacity multiplier `elapsed` after the start of the animation
+    pub fn opacity_at(&self, elapsed: Duration) -> f32 {
+        let Some(period) = self.period() else {
+            return 1.0;
+        };
+        let phase = (elapsed.as_millis() % period.as_millis()) as f32 / period.as_millis() as f32;
+
+        match self {
+            Animation::Blink { .. } if phase < 0.5 => 1.0,
+            Animation::Blink { .. } => 0.0,
+            Animation::Pulse { .. } => 0.5 + 0.5 * (phase * std::f32::consts::TAU).cos(),
+            Animation::None => 1.0,
+        }
+    }
+
+    /// Current opacity multiplier
+    pub fn opacity(&self) -> f32 {
+        self.opacity_at(ANIMATION_EPOCH.elapsed())
+    }
+
+    /// Moment the opacity changes next: the next half of the period for blinking, the next frame
+    /// for pulsing. `None` without animation
+    pub fn next_update(&self) -> Option<Instant> {
+        let period = self.period()?;
+        match self {
+            Animation::Blink { .. } => {
+                let half = (period.as_millis() / 2).max(1);
+                let elapsed = ANIMATION_EPOCH.elapsed().as_millis();
+                let next = (elapsed / half + 1) * half;
+                Some(*ANIMATION_EPOCH + Duration::from_millis(next as u64))
+            }
+            _ => Some(Instant::now()),
+        }
+    }
+}
+
+const fn default_opacity() -> f32 {
+    1.0
+}
+
+fn is_opaque(opacity: &f32) -> bool {
+    *opacity >= 1.0
 }
 
 /// Common style used by `Widget`
-#[derive(Default, Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Style {
-    pub background: Option<Color>,
+    pub background: Option<Background>,
 
     /// Border of a pixel (border pixel width, color)
     pub border: Option<(usize, Color)>,
@@ -199,6 +685,29 @@ pub struct Style {
     /// Margin of a widget (Left, Right, Up, Down)
     #[serde(default)]
     pub margin: Margin,
+
+    /// Space between the border and the content of a widget (Left, Right, Up, Down)
+    // Skipped when empty, since bar settings have their own `padding` next to the flattened style
+    #[serde(default, skip_serializing_if = "Margin::is_zero")]
+    pub padding: Margin,
+
+    /// Radius of rounded corners of background and border in pixels. 0 means sharp corners
+    #[serde(default)]
+    pub border_radius: usize,
+
+    /// Text shown in a floating tooltip while the pointer rests over the widget
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
+
+    /// Opacity of the widget from 0.0 (invisible) to 1.0 (opaque). Background, border and
+    /// content, including nested widgets, fade together. Values outside are clamped
+    #[serde(default = "default_opacity", skip_serializing_if = "is_opaque")]
+    pub opacity: f32,
+
+    /// Change opacity of the widget over time, e.g. to make a warning blink. Multiplies
+    /// [Style::opacity]
+    #[serde(default, skip_serializing_if = "Animation::is_none")]
+    pub animation: Animation,
 }
 
 impl Style {
@@ -207,8 +716,57 @@ impl Style {
             background: None,
             border: None,
             margin: Margin::default(),
+            padding: Margin::default(),
+            border_radius: 0,
+            tooltip: None,
+            opacity: default_opacity(),
+            animation: Animation::None,
         }
     }
+
+    /// [Style::opacity] multiplied by the current opacity of [Style::animation]
+    pub fn current_opacity(&self) -> f32 {
+        self.opacity * self.animation.opacity()
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style::default()
+    }
+}
+
+/// [Widget::next_update] of `widget` combined with the next frame of it's [Style::animation].
+/// Containers should schedule their children with it.
+pub fn next_update_of(widget: &dyn Widget) -> Option<Instant> {
+    let animation = widget
+        .as_styled()
+        .and_then(|w| w.style().animation.next_update());
+    schedule::earliest(widget.next_update(), animation)
+}
+
+/// Draw `widget` faded by it's [Style::opacity] and [Style::animation]. Opacity of the drawer is
+/// multiplied by it while the widget is drawn and restored afterwards, so opacities of nested
+/// widgets multiply. Containers should draw their children with it.
+pub fn draw_with_opacity(widget: &dyn Widget) -> Result<(), WidgetError> {
+    let style = widget.as_styled().map(|w| w.style());
+    let opacity = style.map_or(1.0, Style::current_opacity);
+    // Area of an animated widget changes every frame even if it's content does not
+    if style.is_some_and(|style| !style.animation.is_none()) {
+        widget.data_mut().dirty = true;
+    }
+
+    let env = match widget.env() {
+        Some(env) if opacity < 1.0 => env,
+        _ => return widget.draw(),
+    };
+
+    let previous = env.drawer.borrow().opacity();
+    env.drawer.borrow_mut().set_opacity(previous * opacity);
+    let result = widget.draw();
+    env.drawer.borrow_mut().set_opacity(previous);
+
+    result
 }
 
 /// [Widget] that supports common styling. Already provides helper functions fot initialising and drawing styled widget.
@@ -221,7 +779,7 @@ pub trait WidgetStyled: Widget {
     /// <b> Before using default function make sure you understand and follow these points:</b> <br>
     /// <ul>
     /// <li> Borrows [WidgetData] via calling [Widget::data_mut()] then
-    /// adds border and margins to width and height; <br> </li>
+    /// adds border, paddings and margins to width and height; <br> </li>
     /// <li> Borrows [Style] immutably; <br> </li>
     /// <li> Should be called once after every width or height are overwritten. Otherwise width, height and position will be innacurate. <br> </li>
     /// </ul>
@@ -237,6 +795,9 @@ pub trait WidgetStyled: Widget {
 
         data.height += border.0 * 2;
 
+        data.width += style.padding.left + style.padding.right;
+        data.height += style.padding.up + style.padding.down;
+
         data.width += style.margin.left + style.margin.right;
         data.height += style.margin.up + style.margin.down;
 
@@ -248,7 +809,9 @@ pub trait WidgetStyled: Widget {
     /// <b> Before using default function make sure you understand and follow these points:</b> <br>
     /// <ul>
     /// <li> Borrows [WidgetData] via calling [Widget::data_mut()] then
-    /// adds margins to position; <br> </li>
+    /// adds margins to position. Paddings are added after the background and border are drawn,
+    /// so the content drawn at the position is inset by them. Both are added to the position the
+    /// widget was placed at, so drawing it again does not move it; <br> </li>
     /// <li> Borrows [Style] immutably; <br> </li>
     /// <li> Draws the background and border, therefore should be called every draw before the main
     /// logic<br> </li>
@@ -264,36 +827,65 @@ pub trait WidgetStyled: Widget {
         let border = style.border.unwrap_or((0, Color::NONE));
         let mut data = self.data_mut();
 
-        data.position.0 += style.margin.left;
-        data.position.1 += style.margin.up;
+        // Position left by the previous draw is inset already, anything else means the widget was
+        // placed anew
+        let inset = style.margin.offset() + style.padding.offset();
+        let base = match data.base_position {
+            Some(base) if data.position == base + inset => base,
+            _ => data.position,
+        };
+        data.base_position = Some(base);
+        data.position = base + style.margin.offset();
 
         let mut drawer = env.as_ref().drawer.borrow_mut();
-        if let Some(color) = style.background {
-            for x in border.0..data.width - border.0 {
-                for y in border.0..data.height - border.0 {
-                    drawer.draw_pixel(&data, (x, y), color);
-                }
-            }
-        }
+        data.report_damage(&mut drawer);
 
-        if border.1 == Color::NONE {
-            return Ok(());
+        if let Some(background) = style.background {
+            let inner = WidgetData {
+                position: data.position + (border.0, border.0),
+                ..*data
+            };
+            let width = data.width.saturating_sub(2 * border.0);
+            let height = data.height.saturating_sub(2 * border.0);
+            drawer.fill_rounded_rect(
+                &inner,
+                width,
+                height,
+                style.border_radius.saturating_sub(border.0),
+                |x, y| background.color_at(x, y, width, height),
+            )?;
         }
 
-        for x in 0..border.0 {
-            for y in 0..data.height {
-                drawer.draw_pixel(&data, (x, y), border.1);
-                drawer.draw_pixel(&data, (data.width - 1 - x, y), border.1);
+        if style.border_radius > 0 {
+            if border.1 != Color::NONE {
+                drawer.draw_rounded_border(
+                    &data,
+                    data.width,
+                    data.height,
+                    style.border_radius,
+                    border.0,
+                    border.1,
+                )?;
+            }
+        } else if border.1 != Color::NONE {
+            // Border of a widget narrower than two borders covers all of it
+            for x in 0..border.0.min(data.width) {
+                for y in 0..data.height {
+                    drawer.draw_pixel(&data, (x, y), border.1)?;
+                    drawer.draw_pixel(&data, (data.width - 1 - x, y), border.1)?;
+                }
             }
-        }
 
-        for x in 0..data.width {
-            for y in 0..border.0 {
-                drawer.draw_pixel(&data, (x, y), border.1);
-                drawer.draw_pixel(&data, (x, data.height - 1 - y), border.1);
+            for x in 0..data.width {
+                for y in 0..border.0.min(data.height) {
+                    drawer.draw_pixel(&data, (x, y), border.1)?;
+                    drawer.draw_pixel(&data, (x, data.height - 1 - y), border.1)?;
+                }
             }
         }
 
+        data.position += style.padding.offset();
+
         Ok(())
     }
 }
@@ -305,10 +897,31 @@ pub enum WidgetList {
     IconText,
     Clock,
     Battery,
+    Brightness,
     CPU,
+    Date,
+    Disk,
+    Image,
     Keyboard,
+    Memory,
+    Microphone,
+    Modifiers,
+    Mpris,
+    Notifications,
+    PowerProfile,
+    ProgressBar,
+    Script,
+    Separator,
+    Spacer,
+    Temperature,
+    Tray,
+    Volume,
+    Weather,
+    WindowTitle,
+    Workspaces,
 
     Row,
+    Grid,
     Bar,
 
     Custom(String),
@@ -321,10 +934,31 @@ impl Display for WidgetList {
             Self::IconText => write!(f, "Text"),
             Self::Clock => write!(f, "Clock"),
             Self::Battery => write!(f, "Battery"),
+            Self::Brightness => write!(f, "Brightness"),
             Self::CPU => write!(f, "Cpu"),
+            Self::Date => write!(f, "Date"),
+            Self::Disk => write!(f, "Disk"),
+            Self::Image => write!(f, "Image"),
             Self::Keyboard => write!(f, "Keyboard"),
+            Self::Memory => write!(f, "Memory"),
+            Self::Microphone => write!(f, "Microphone"),
+            Self::Modifiers => write!(f, "Modifiers"),
+            Self::Mpris => write!(f, "Mpris"),
+            Self::Notifications => write!(f, "Notifications"),
+            Self::PowerProfile => write!(f, "PowerProfile"),
+            Self::ProgressBar => write!(f, "ProgressBar"),
+            Self::Script => write!(f, "Script"),
+            Self::Separator => write!(f, "Separator"),
+            Self::Spacer => write!(f, "Spacer"),
+            Self::Temperature => write!(f, "Temperature"),
+            Self::Tray => write!(f, "Tray"),
+            Self::Volume => write!(f, "Volume"),
+            Self::Weather => write!(f, "Weather"),
+            Self::WindowTitle => write!(f, "WindowTitle"),
+            Self::Workspaces => write!(f, "Workspaces"),
 
             Self::Row => write!(f, "Row"),
+            Self::Grid => write!(f, "Grid"),
             Self::Bar => write!(f, "Bar"),
 
             Self::Custom(name) => write!(f, "{name}"),
@@ -333,19 +967,175 @@ impl Display for WidgetList {
 }
 
 /// Enum of [Widget]s with their settings.
-#[derive(Deserialize, Debug, Clone)]
+// Settings are only stored until widgets are created, boxing them is not worth it
+#[allow(clippy::large_enum_variant)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "widget", content = "settings", rename_all = "snake_case")]
 pub enum WidgetsSettingsList {
     Text(TextSettings),
     Clock(ClockSettings),
     Battery(BatterySettings),
+    Brightness(brightness::BrightnessSettings),
     #[serde(rename = "cpu")]
     CPU(CPUSettings),
+    Date(date::DateSettings),
+    Disk(disk::DiskSettings),
+    #[cfg(feature = "image")]
+    Image(image::ImageSettings),
+    #[cfg(feature = "keyboard")]
     Keyboard(keyboard::KeyboardSettings, ProcessSettings),
-    Custom(String),
+    Memory(memory::MemorySettings),
+    Microphone(microphone::MicrophoneSettings, ProcessSettings),
+    Modifiers(modifiers::ModifiersSettings),
+    #[cfg(feature = "mpris")]
+    Mpris(mpris::MprisSettings, ProcessSettings),
+    #[cfg(feature = "notifications")]
+    Notifications(notifications::NotificationsSettings, ProcessSettings),
+    #[cfg(feature = "power_profile")]
+    PowerProfile(power_profile::PowerProfileSettings, ProcessSettings),
+    ProgressBar(progress_bar::ProgressBarSettings),
+    Script(script::ScriptSettings),
+    Separator(separator::SeparatorSettings),
+    Spacer(spacer::SpacerSettings),
+    Temperature(temperature::TemperatureSettings),
+    #[cfg(feature = "tray")]
+    Tray(tray::TraySettings, ProcessSettings),
+    Volume(volume::VolumeSettings, ProcessSettings),
+    #[cfg(feature = "weather")]
+    Weather(weather::WeatherSettings),
+    #[cfg(feature = "hyprland")]
+    WindowTitle(window_title::WindowTitleSettings, ProcessSettings),
+    #[cfg(feature = "hyprland")]
+    Workspaces(workspaces::WorkspacesSettings, ProcessSettings),
+    Custom(custom::CustomSettings),
+    Row(containers::row::RowWidgetSettings),
 }
 
 impl WidgetsSettingsList {
+    /// Check constraints of the settings that are not enforced by their types. `fonts` is the
+    /// number of preloaded fonts. Returns descriptions of all found problems.
+    pub fn validate(&self, fonts: usize) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        // Clock and date do not have text settings of their own yet
+        let clock_text = TextSettings::default();
+        let text = match self {
+            WidgetsSettingsList::Text(settings) => Some(settings),
+            WidgetsSettingsList::Clock(settings) => {
+                if let Err(e) = settings.validate() {
+                    problems.push(e.to_string());
+                }
+                Some(&clock_text)
+            }
+            WidgetsSettingsList::Battery(settings) => Some(&settings.text_settings),
+            WidgetsSettingsList::Brightness(settings) => Some(&settings.text_settings),
+            WidgetsSettingsList::CPU(settings) => Some(&settings.text_settings),
+            WidgetsSettingsList::Date(settings) => {
+                if let Err(e) = settings.validate() {
+                    problems.push(e.to_string());
+                }
+                Some(&clock_text)
+            }
+            WidgetsSettingsList::Disk(settings) => Some(&settings.text_settings),
+            #[cfg(feature = "keyboard")]
+            WidgetsSettingsList::Keyboard(settings, _) => Some(&settings.text_settings),
+            WidgetsSettingsList::Memory(settings) => Some(&settings.text_settings),
+            WidgetsSettingsList::Microphone(settings, _) => Some(&settings.text_settings),
+            WidgetsSettingsList::Modifiers(settings) => Some(&settings.text_settings),
+            #[cfg(feature = "mpris")]
+            WidgetsSettingsList::Mpris(settings, _) => Some(&settings.text_settings),
+            #[cfg(feature = "notifications")]
+            WidgetsSettingsList::Notifications(settings, _) => Some(&settings.text_settings),
+            #[cfg(feature = "power_profile")]
+            WidgetsSettingsList::PowerProfile(settings, _) => Some(&settings.text_settings),
+            WidgetsSettingsList::Script(settings) => {
+                if let Err(e) = settings.validate() {
+                    problems.push(e.to_string());
+                }
+                Some(&settings.text_settings)
+            }
+            #[cfg(feature = "image")]
+            WidgetsSettingsList::Image(_) => None,
+            WidgetsSettingsList::ProgressBar(_)
+            | WidgetsSettingsList::Separator(_)
+            | WidgetsSettingsList::Spacer(_) => None,
+            WidgetsSettingsList::Temperature(settings) => Some(&settings.text_settings),
+            #[cfg(feature = "tray")]
+            WidgetsSettingsList::Tray(settings, _) => Some(&settings.text_settings),
+            WidgetsSettingsList::Volume(settings, _) => Some(&settings.text_settings),
+            #[cfg(feature = "weather")]
+            WidgetsSettingsList::Weather(settings) => {
+                if let Err(e) = settings.validate() {
+                    problems.push(e.to_string());
+                }
+                Some(&settings.text_settings)
+            }
+            #[cfg(feature = "hyprland")]
+            WidgetsSettingsList::WindowTitle(settings, _) => Some(&settings.text_settings),
+            #[cfg(feature = "hyprland")]
+            WidgetsSettingsList::Workspaces(settings, _) => Some(&settings.text_settings),
+            // Settings of custom widgets are only known to their builders
+            WidgetsSettingsList::Custom(_) => None,
+            WidgetsSettingsList::Row(settings) => {
+                for widget in &settings.widgets {
+                    problems.extend(widget.validate(fonts));
+                }
+                None
+            }
+        };
+
+        for fontid in text.into_iter().flat_map(TextSettings::font_ids) {
+            if fontid >= fonts {
+                problems.push(format!(
+                    "font {fontid} is used, but only {fonts} fonts are preloaded"
+                ));
+            }
+        }
+
+        let icon_fontid = match self {
+            WidgetsSettingsList::Battery(settings) => Some(settings.icon_fontid),
+            WidgetsSettingsList::CPU(settings) => Some(settings.icon_fontid),
+            #[cfg(feature = "keyboard")]
+            WidgetsSettingsList::Keyboard(settings, _) => Some(settings.icon_fontid),
+            _ => None,
+        };
+        if let Some(fontid) = icon_fontid.filter(|&id| id >= fonts) {
+            problems.push(format!(
+                "icon font {fontid} is used, but only {fonts} fonts are preloaded"
+            ));
+        }
+
+        let process = match self {
+            #[cfg(feature = "keyboard")]
+            WidgetsSettingsList::Keyboard(_, psettings) => Some(psettings),
+            WidgetsSettingsList::Microphone(_, psettings) => Some(psettings),
+            #[cfg(feature = "mpris")]
+            WidgetsSettingsList::Mpris(_, psettings) => Some(psettings),
+            #[cfg(feature = "notifications")]
+            WidgetsSettingsList::Notifications(_, psettings) => Some(psettings),
+            #[cfg(feature = "power_profile")]
+            WidgetsSettingsList::PowerProfile(_, psettings) => Some(psettings),
+            #[cfg(feature = "tray")]
+            WidgetsSettingsList::Tray(_, psettings) => Some(psettings),
+            WidgetsSettingsList::Volume(_, psettings) => Some(psettings),
+            #[cfg(feature = "hyprland")]
+            WidgetsSettingsList::WindowTitle(_, psettings) => Some(psettings),
+            #[cfg(feature = "hyprland")]
+            WidgetsSettingsList::Workspaces(_, psettings) => Some(psettings),
+            _ => None,
+        };
+
+        if let Some(process) = process.filter(|p| p.update_rate.is_zero()) {
+            problems.push(format!(
+                "update rate of the service has to be positive, got {}",
+                process.update_rate
+            ));
+        }
+
+        problems
+    }
+
     pub fn create_in_container(
         &self,
         container: &mut impl containers::ContainerSingle,
@@ -360,16 +1150,109 @@ impl WidgetsSettingsList {
             WidgetsSettingsList::Battery(settings) => {
                 container.create_widget(battery::Battery::new, settings.clone())
             }
+            WidgetsSettingsList::Brightness(settings) => {
+                container.create_widget(brightness::Brightness::new, settings.clone())
+            }
             WidgetsSettingsList::CPU(settings) => {
                 container.create_widget(cpu::CPU::new, settings.clone())
             }
+            WidgetsSettingsList::Date(settings) => {
+                container.create_widget(date::Date::new, settings.clone())
+            }
+            WidgetsSettingsList::Disk(settings) => {
+                container.create_widget(disk::Disk::new, settings.clone())
+            }
+            #[cfg(feature = "image")]
+            WidgetsSettingsList::Image(settings) => {
+                container.create_widget(image::Image::new, settings.clone())
+            }
+            #[cfg(feature = "keyboard")]
             WidgetsSettingsList::Keyboard(wsettings, psettings) => {
                 container.create_service(crate::services::clients::Keyboard::new, *psettings)?;
                 container.create_widget(keyboard::Keyboard::new, wsettings.clone())
             }
-            WidgetsSettingsList::Custom(_) => {
-                todo!()
+            WidgetsSettingsList::Memory(settings) => {
+                container.create_widget(memory::Memory::new, settings.clone())
+            }
+            WidgetsSettingsList::Microphone(wsettings, psettings) => {
+                container
+                    .create_service(crate::services::microphone::Microphone::new, *psettings)?;
+                container.create_widget(microphone::Microphone::new, wsettings.clone())
+            }
+            WidgetsSettingsList::Modifiers(settings) => {
+                container.create_widget(modifiers::Modifiers::new, settings.clone())
+            }
+            #[cfg(feature = "mpris")]
+            WidgetsSettingsList::Mpris(wsettings, psettings) => {
+                container.create_service(crate::services::mpris::Mpris::new, *psettings)?;
+                container.create_widget(mpris::Mpris::new, wsettings.clone())
             }
+            #[cfg(feature = "notifications")]
+            WidgetsSettingsList::Notifications(wsettings, psettings) => {
+                container.create_service(
+                    crate::services::notifications::Notifications::new,
+                    *psettings,
+                )?;
+                container.create_widget(notifications::Notifications::new, wsettings.clone())
+            }
+            #[cfg(feature = "power_profile")]
+            WidgetsSettingsList::PowerProfile(wsettings, psettings) => {
+                container.create_service(
+                    crate::services::power_profile::PowerProfile::new,
+                    *psettings,
+                )?;
+                container.create_widget(power_profile::PowerProfile::new, wsettings.clone())
+            }
+            WidgetsSettingsList::ProgressBar(settings) => {
+                container.create_widget(progress_bar::ProgressBar::new, settings.clone())
+            }
+            WidgetsSettingsList::Script(settings) => {
+                container.create_widget(script::Script::new, settings.clone())
+            }
+            WidgetsSettingsList::Separator(settings) => {
+                container.create_widget(separator::Separator::new, settings.clone())
+            }
+            WidgetsSettingsList::Spacer(settings) => {
+                container.create_widget(spacer::Spacer::new, settings.clone())
+            }
+            WidgetsSettingsList::Temperature(settings) => {
+                container.create_widget(temperature::Temperature::new, settings.clone())
+            }
+            #[cfg(feature = "tray")]
+            WidgetsSettingsList::Tray(wsettings, psettings) => {
+                container.create_service(crate::services::tray::Tray::new, *psettings)?;
+                container.create_widget(tray::Tray::new, wsettings.clone())
+            }
+            WidgetsSettingsList::Volume(wsettings, psettings) => {
+                container.create_service(crate::services::volume::Volume::new, *psettings)?;
+                container.create_widget(volume::Volume::new, wsettings.clone())
+            }
+            #[cfg(feature = "weather")]
+            WidgetsSettingsList::Weather(settings) => {
+                container
+                    .create_service(crate::services::weather::Weather::new, settings.service())?;
+                container.create_widget(weather::Weather::new, settings.clone())
+            }
+            #[cfg(feature = "hyprland")]
+            WidgetsSettingsList::WindowTitle(wsettings, psettings) => {
+                container.create_service(crate::services::clients::WindowTitle::new, *psettings)?;
+                container.create_widget(window_title::WindowTitle::new, wsettings.clone())
+            }
+            #[cfg(feature = "hyprland")]
+            WidgetsSettingsList::Workspaces(wsettings, psettings) => {
+                container.create_service(crate::services::clients::Workspaces::new, *psettings)?;
+                container.create_widget(workspaces::Workspaces::new, wsettings.clone())
+            }
+            WidgetsSettingsList::Custom(settings) => {
+                container.create_boxed_widget(|env| custom::create(env, settings))
+            }
+            WidgetsSettingsList::Row(settings) => container.create_boxed_widget(|env| {
+                let mut row = containers::row::Row::new(env, settings.row.clone())?;
+                for widget in &settings.widgets {
+                    widget.create_in_container(&mut row)?;
+                }
+                Ok(Box::new(row))
+            }),
         }
     }
 }
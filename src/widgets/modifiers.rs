@@ -0,0 +1,204 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use smithay_client_toolkit::seat::keyboard::Modifiers as KeyboardModifiers;
+
+use crate::{root::Environment, util::signals::SignalNames, widgets::Widget};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Style, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+fn default_caps_lock() -> String {
+    "CAPS".to_string()
+}
+
+fn default_num_lock() -> String {
+    "NUM".to_string()
+}
+
+/// Settings of a [Modifiers] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ModifiersSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Text displayed while caps lock is active
+    #[serde(default = "default_caps_lock")]
+    pub caps_lock: String,
+
+    /// Text displayed while num lock is active
+    #[serde(default = "default_num_lock")]
+    pub num_lock: String,
+}
+
+impl Default for ModifiersSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            caps_lock: default_caps_lock(),
+            num_lock: default_num_lock(),
+        }
+    }
+}
+
+/// Widget displaying locked keyboard modifiers. Listens to [SignalNames::Modifiers] emitted by
+/// [crate::root::Root], so it works with any compositor.
+///
+/// Compositors only send modifiers to a surface with keyboard focus, so the state is updated
+/// whenever the bar receives focus.
+pub struct Modifiers {
+    data: RefCell<WidgetData>,
+    settings: Rc<ModifiersSettings>,
+    is_ready: RefCell<bool>,
+
+    icon_text: Rc<RefCell<IconText>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Modifiers {
+    fn locked(settings: &ModifiersSettings, modifiers: &KeyboardModifiers) -> String {
+        let mut locked = Vec::new();
+        if modifiers.caps_lock {
+            locked.push(settings.caps_lock.as_str());
+        }
+        if modifiers.num_lock {
+            locked.push(settings.num_lock.as_str());
+        }
+
+        locked.join(" ")
+    }
+}
+
+impl Widget for Modifiers {
+    fn name(&self) -> WidgetList {
+        WidgetList::Modifiers
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::Modifiers));
+        }
+
+        {
+            let mut ic = self.icon_text.borrow_mut();
+            ic.change_icon("󰌌");
+            ic.change_text("");
+            ic.init()?;
+        }
+
+        let mut signals = self.env.as_ref().unwrap().signals.borrow_mut();
+        let signal = signals.entry(SignalNames::Modifiers).or_default();
+
+        let signal_ic = Rc::clone(&self.icon_text);
+        let settings = Rc::clone(&self.settings);
+
+        signal.connect(move |data| {
+            if let Some(modifiers) = data.downcast_ref::<KeyboardModifiers>() {
+                signal_ic
+                    .borrow_mut()
+                    .change_text(&Modifiers::locked(&settings, modifiers));
+            }
+        });
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Modifiers));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        self.draw_style()?;
+
+        {
+            let ic_data = self.icon_text.borrow();
+            ic_data.data_mut().position = self.data().position;
+        }
+        self.icon_text.borrow_mut().draw()
+    }
+}
+
+impl WidgetNew for Modifiers {
+    type Settings = ModifiersSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Modifiers {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            icon_text: Rc::new(RefCell::new(IconText::new(
+                env.clone(),
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?)),
+
+            settings: Rc::new(settings),
+            env: None,
+        })
+    }
+}
+
+impl WidgetStyled for Modifiers {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
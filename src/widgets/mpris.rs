@@ -0,0 +1,312 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    services::{
+        mpris::{MprisState, PlaybackStatus, PlayerState, MPRIS_PREFIX},
+        ServiceList,
+    },
+    util::signals::SignalNames,
+};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Interactive, Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+fn default_format() -> String {
+    "{artist} - {title}".to_string()
+}
+
+const fn default_max_length() -> usize {
+    40
+}
+
+fn default_playing_icon() -> String {
+    "󰐊".to_string()
+}
+
+fn default_paused_icon() -> String {
+    "󰏤".to_string()
+}
+
+fn default_stopped_icon() -> String {
+    "󰓛".to_string()
+}
+
+/// Settings of a [Mpris] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MprisSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Player to show, e.g. `"spotify"` or `"org.mpris.MediaPlayer2.spotify"`. The first playing
+    /// player, or the first one if none are playing, is shown if not set.
+    #[serde(default)]
+    pub player: Option<String>,
+
+    /// Format of the track. `{artist}` and `{title}` are replaced with track metadata. Only the
+    /// title is shown for tracks without an artist.
+    #[serde(default = "default_format")]
+    pub format: String,
+
+    /// Longest displayed text in characters, longer one is truncated with `…`. Text is not
+    /// truncated if set to 0.
+    #[serde(default = "default_max_length")]
+    pub max_length: usize,
+
+    #[serde(default = "default_playing_icon")]
+    pub playing_icon: String,
+
+    #[serde(default = "default_paused_icon")]
+    pub paused_icon: String,
+
+    /// Icon shown while the player is stopped or there are no players
+    #[serde(default = "default_stopped_icon")]
+    pub stopped_icon: String,
+}
+
+impl Default for MprisSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            player: None,
+            format: default_format(),
+            max_length: default_max_length(),
+            playing_icon: default_playing_icon(),
+            paused_icon: default_paused_icon(),
+            stopped_icon: default_stopped_icon(),
+        }
+    }
+}
+
+impl MprisSettings {
+    /// Player shown by the widget
+    pub fn select<'a>(&self, players: &'a [PlayerState]) -> Option<&'a PlayerState> {
+        match &self.player {
+            Some(player) => {
+                let name = player.strip_prefix(MPRIS_PREFIX).unwrap_or(player);
+                players.iter().find(|p| p.name() == name)
+            }
+            None => players
+                .iter()
+                .find(|p| p.status == PlaybackStatus::Playing)
+                .or_else(|| players.first()),
+        }
+    }
+
+    /// Icon of a playback status
+    pub fn icon(&self, status: PlaybackStatus) -> &str {
+        match status {
+            PlaybackStatus::Playing => &self.playing_icon,
+            PlaybackStatus::Paused => &self.paused_icon,
+            PlaybackStatus::Stopped => &self.stopped_icon,
+        }
+    }
+
+    /// Text describing the current track of the player
+    pub fn text(&self, player: &PlayerState) -> String {
+        let text = match player.artist.is_empty() {
+            true => player.title.clone(),
+            false => self
+                .format
+                .replace("{artist}", &player.artist)
+                .replace("{title}", &player.title),
+        };
+
+        if self.max_length == 0 || text.chars().count() <= self.max_length {
+            return text;
+        }
+
+        let mut truncated: String = text.chars().take(self.max_length - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+/// Widget displaying the track of a media player. Clicking it toggles playback. Requires
+/// [Mpris](crate::services::mpris::Mpris) service.
+pub struct Mpris {
+    data: RefCell<WidgetData>,
+    settings: Rc<MprisSettings>,
+    is_ready: RefCell<bool>,
+
+    icon_text: Rc<RefCell<IconText>>,
+    /// Player shown at the moment
+    player: Rc<RefCell<Option<PlayerState>>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for Mpris {
+    fn name(&self) -> WidgetList {
+        WidgetList::Mpris
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn as_interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::Mpris));
+        }
+
+        let env = self.env.as_ref().unwrap();
+
+        if !env.signals.borrow().contains_key(&SignalNames::Mpris) {
+            return Err(WidgetError::NoCorespondingSignal(
+                WidgetList::Mpris,
+                ServiceList::Mpris,
+            ));
+        }
+
+        {
+            let mut ic = self.icon_text.borrow_mut();
+            ic.change_icon(&self.settings.stopped_icon);
+            ic.change_text("");
+            ic.init()?;
+        }
+
+        let signal_ic = Rc::clone(&self.icon_text);
+        let settings = Rc::clone(&self.settings);
+        let player = Rc::clone(&self.player);
+
+        env.connect_signals(&[SignalNames::Mpris], move |_, data| {
+            if let Some(state) = data.downcast_ref::<MprisState>() {
+                let selected = settings.select(&state.players);
+                let mut ic = signal_ic.borrow_mut();
+                match selected {
+                    Some(selected) => {
+                        ic.change_icon(settings.icon(selected.status));
+                        ic.change_text(&settings.text(selected));
+                    }
+                    None => {
+                        ic.change_icon(&settings.stopped_icon);
+                        ic.change_text("");
+                    }
+                }
+                *player.borrow_mut() = selected.cloned();
+            }
+        });
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Mpris));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        self.draw_style()?;
+
+        {
+            let ic_data = self.icon_text.borrow();
+            ic_data.data_mut().position = self.data().position;
+        }
+        self.icon_text.borrow_mut().draw()
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        // Long titles may scroll
+        self.icon_text.borrow().next_update()
+    }
+}
+
+impl WidgetNew for Mpris {
+    type Settings = MprisSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Mpris {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            icon_text: Rc::new(RefCell::new(IconText::new(
+                env.clone(),
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?)),
+            player: Rc::new(RefCell::new(None)),
+
+            settings: Rc::new(settings),
+
+            env: None,
+        })
+    }
+}
+
+impl Interactive for Mpris {
+    fn click(&self, _button: u32) -> Result<(), WidgetError> {
+        if let Some(player) = self.player.borrow().as_ref() {
+            player
+                .play_pause()
+                .map_err(|e| WidgetError::Custom(e.into()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl WidgetStyled for Mpris {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
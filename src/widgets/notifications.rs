@@ -0,0 +1,244 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    services::{notifications::NotificationsState, ServiceList},
+    util::signals::SignalNames,
+};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Interactive, Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+fn default_icon() -> String {
+    "󰂚".to_string()
+}
+
+fn default_dnd_icon() -> String {
+    "󰂛".to_string()
+}
+
+/// Settings of a [Notifications] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NotificationsSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Icon shown while do-not-disturb mode is off. It is also shown alone if there is no
+    /// supported notification daemon.
+    #[serde(default = "default_icon")]
+    pub icon: String,
+
+    /// Icon shown while do-not-disturb mode is on
+    #[serde(default = "default_dnd_icon")]
+    pub dnd_icon: String,
+
+    /// Show the amount of notifications even if there are none
+    #[serde(default)]
+    pub show_zero: bool,
+}
+
+impl Default for NotificationsSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            icon: default_icon(),
+            dnd_icon: default_dnd_icon(),
+            show_zero: false,
+        }
+    }
+}
+
+impl NotificationsSettings {
+    /// Icon of the daemon state
+    pub fn icon(&self, state: &NotificationsState) -> &str {
+        match state.dnd {
+            true => &self.dnd_icon,
+            false => &self.icon,
+        }
+    }
+
+    /// Amount of notifications shown next to the icon
+    pub fn text(&self, state: &NotificationsState) -> String {
+        if state.daemon.is_none() || (state.count == 0 && !self.show_zero) {
+            return String::new();
+        }
+
+        state.count.to_string()
+    }
+}
+
+/// Widget displaying the amount of pending notifications and do-not-disturb mode. Clicking it
+/// toggles do-not-disturb. Requires [Notifications](crate::services::notifications::Notifications)
+/// service.
+pub struct Notifications {
+    data: RefCell<WidgetData>,
+    settings: Rc<NotificationsSettings>,
+    is_ready: RefCell<bool>,
+
+    icon_text: Rc<RefCell<IconText>>,
+    state: Rc<RefCell<NotificationsState>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for Notifications {
+    fn name(&self) -> WidgetList {
+        WidgetList::Notifications
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn as_interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::Notifications));
+        }
+
+        let env = self.env.as_ref().unwrap();
+
+        if !env
+            .signals
+            .borrow()
+            .contains_key(&SignalNames::Notifications)
+        {
+            return Err(WidgetError::NoCorespondingSignal(
+                WidgetList::Notifications,
+                ServiceList::Notifications,
+            ));
+        }
+
+        {
+            let mut ic = self.icon_text.borrow_mut();
+            ic.change_icon(&self.settings.icon);
+            ic.change_text("");
+            ic.init()?;
+        }
+
+        let signal_ic = Rc::clone(&self.icon_text);
+        let settings = Rc::clone(&self.settings);
+        let state = Rc::clone(&self.state);
+
+        env.signals.borrow()[&SignalNames::Notifications]
+            .typed::<NotificationsState>()
+            .connect(move |new_state| {
+                let mut ic = signal_ic.borrow_mut();
+                ic.change_icon(settings.icon(new_state));
+                ic.change_text(&settings.text(new_state));
+                *state.borrow_mut() = *new_state;
+            });
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Notifications));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        self.draw_style()?;
+
+        {
+            let ic_data = self.icon_text.borrow();
+            ic_data.data_mut().position = self.data().position;
+        }
+        self.icon_text.borrow_mut().draw()
+    }
+}
+
+impl WidgetNew for Notifications {
+    type Settings = NotificationsSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Notifications {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            icon_text: Rc::new(RefCell::new(IconText::new(
+                env.clone(),
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?)),
+            state: Rc::new(RefCell::new(NotificationsState::default())),
+
+            settings: Rc::new(settings),
+
+            env: None,
+        })
+    }
+}
+
+impl Interactive for Notifications {
+    fn click(&self, _button: u32) -> Result<(), WidgetError> {
+        self.state
+            .borrow()
+            .toggle_dnd()
+            .map_err(|e| WidgetError::Custom(e.into()))
+    }
+}
+
+impl WidgetStyled for Notifications {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
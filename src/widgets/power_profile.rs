@@ -0,0 +1,257 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    services::{
+        power_profile::{PowerProfileState, Profile},
+        ServiceList,
+    },
+    util::signals::SignalNames,
+};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Interactive, Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+fn default_power_saver_icon() -> String {
+    "󰌪".to_string()
+}
+
+fn default_balanced_icon() -> String {
+    "󰗑".to_string()
+}
+
+fn default_performance_icon() -> String {
+    "󰓅".to_string()
+}
+
+/// Settings of a [PowerProfile] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PowerProfileSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    #[serde(default = "default_power_saver_icon")]
+    pub power_saver_icon: String,
+
+    #[serde(default = "default_balanced_icon")]
+    pub balanced_icon: String,
+
+    #[serde(default = "default_performance_icon")]
+    pub performance_icon: String,
+
+    /// Icon shown if power-profiles-daemon is not running. Nothing is shown if not set
+    #[serde(default)]
+    pub unavailable_icon: Option<String>,
+
+    /// Show name of the active profile next to the icon
+    #[serde(default)]
+    pub show_label: bool,
+}
+
+impl Default for PowerProfileSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            power_saver_icon: default_power_saver_icon(),
+            balanced_icon: default_balanced_icon(),
+            performance_icon: default_performance_icon(),
+            unavailable_icon: None,
+            show_label: false,
+        }
+    }
+}
+
+impl PowerProfileSettings {
+    /// Icon of the active profile
+    pub fn icon(&self, state: &PowerProfileState) -> &str {
+        match state.profile {
+            Some(Profile::PowerSaver) => &self.power_saver_icon,
+            Some(Profile::Balanced) => &self.balanced_icon,
+            Some(Profile::Performance) => &self.performance_icon,
+            None => self.unavailable_icon.as_deref().unwrap_or_default(),
+        }
+    }
+
+    /// Label shown next to the icon
+    pub fn text(&self, state: &PowerProfileState) -> String {
+        match state.profile {
+            Some(profile) if self.show_label => profile.to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Widget displaying the active power profile. Clicking it switches to the next available
+/// profile. Requires [PowerProfile](crate::services::power_profile::PowerProfile) service.
+pub struct PowerProfile {
+    data: RefCell<WidgetData>,
+    settings: Rc<PowerProfileSettings>,
+    is_ready: RefCell<bool>,
+
+    icon_text: Rc<RefCell<IconText>>,
+    state: Rc<RefCell<PowerProfileState>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for PowerProfile {
+    fn name(&self) -> WidgetList {
+        WidgetList::PowerProfile
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn as_interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::PowerProfile));
+        }
+
+        let env = self.env.as_ref().unwrap();
+
+        if !env
+            .signals
+            .borrow()
+            .contains_key(&SignalNames::PowerProfile)
+        {
+            return Err(WidgetError::NoCorespondingSignal(
+                WidgetList::PowerProfile,
+                ServiceList::PowerProfile,
+            ));
+        }
+
+        {
+            let mut ic = self.icon_text.borrow_mut();
+            ic.change_icon(self.settings.icon(&PowerProfileState::default()));
+            ic.change_text("");
+            ic.init()?;
+        }
+
+        let signal_ic = Rc::clone(&self.icon_text);
+        let settings = Rc::clone(&self.settings);
+        let state = Rc::clone(&self.state);
+
+        env.signals.borrow()[&SignalNames::PowerProfile]
+            .typed::<PowerProfileState>()
+            .connect(move |new_state| {
+                let mut ic = signal_ic.borrow_mut();
+                ic.change_icon(settings.icon(new_state));
+                ic.change_text(&settings.text(new_state));
+                *state.borrow_mut() = new_state.clone();
+            });
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::PowerProfile));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        self.draw_style()?;
+
+        {
+            let ic_data = self.icon_text.borrow();
+            ic_data.data_mut().position = self.data().position;
+        }
+        self.icon_text.borrow_mut().draw()
+    }
+}
+
+impl WidgetNew for PowerProfile {
+    type Settings = PowerProfileSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(PowerProfile {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            icon_text: Rc::new(RefCell::new(IconText::new(
+                env.clone(),
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?)),
+            state: Rc::new(RefCell::new(PowerProfileState::default())),
+
+            settings: Rc::new(settings),
+
+            env: None,
+        })
+    }
+}
+
+impl Interactive for PowerProfile {
+    fn click(&self, _button: u32) -> Result<(), WidgetError> {
+        self.state
+            .borrow()
+            .cycle()
+            .map_err(|e| WidgetError::Custom(e.into()))
+    }
+}
+
+impl WidgetStyled for PowerProfile {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
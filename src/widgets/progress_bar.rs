@@ -0,0 +1,260 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    util::{signals::SignalNames, Color},
+};
+
+use super::{Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled};
+
+const fn default_width() -> usize {
+    100
+}
+
+const fn default_height() -> usize {
+    8
+}
+
+const fn default_fill_color() -> Color {
+    Color::from_hex(0x89b4faff)
+}
+
+const fn default_track_color() -> Color {
+    Color::from_hex(0x313244ff)
+}
+
+/// Settings of a [ProgressBar] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ProgressBarSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Width of the bar in pixels, including the border
+    #[serde(default = "default_width")]
+    pub width: usize,
+
+    /// Height of the bar in pixels, including the border
+    #[serde(default = "default_height")]
+    pub height: usize,
+
+    /// Color of the filled part
+    #[serde(default = "default_fill_color")]
+    pub fill_color: Color,
+
+    /// Color of the unfilled part
+    #[serde(default = "default_track_color")]
+    pub track_color: Color,
+
+    /// Thickness and color of an outline around the bar. Named `bar_border` in the config, since
+    /// `border` is taken by the style of the widget.
+    #[serde(default, rename = "bar_border")]
+    pub border: Option<(usize, Color)>,
+
+    /// Signal emitting progress as `f32` in `0..=1`, e.g. a custom signal emitted by a script
+    #[serde(default)]
+    pub signal: Option<SignalNames>,
+}
+
+impl Default for ProgressBarSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            style: Style::default(),
+            width: default_width(),
+            height: default_height(),
+            fill_color: default_fill_color(),
+            track_color: default_track_color(),
+            border: None,
+            signal: None,
+        }
+    }
+}
+
+/// Widget drawing a horizontal bar filled according to a progress. Progress is either set with
+/// [ProgressBar::set_progress] or received from [ProgressBarSettings::signal].
+pub struct ProgressBar {
+    data: RefCell<WidgetData>,
+    settings: ProgressBarSettings,
+    is_ready: RefCell<bool>,
+
+    progress: Rc<Cell<f32>>,
+    /// Width of the fill on the last frame, the bar is damaged once it changes
+    drawn_fill: Cell<Option<usize>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl ProgressBar {
+    /// Set progress of the bar. Clamped to `0..=1`, `NaN` is treated as no progress.
+    pub fn set_progress(&self, progress: f32) {
+        self.progress.set(clamp(progress));
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.progress.get()
+    }
+
+    /// Width of the filled part in pixels, rounded to the nearest pixel
+    pub fn fill_width(&self) -> usize {
+        let inner = self
+            .settings
+            .width
+            .saturating_sub(2 * self.border_thickness());
+        (self.progress.get() * inner as f32).round() as usize
+    }
+
+    fn border_thickness(&self) -> usize {
+        self.settings.border.map_or(0, |(thickness, _)| thickness)
+    }
+}
+
+fn clamp(progress: f32) -> f32 {
+    match progress.is_nan() {
+        true => 0.0,
+        false => progress.clamp(0.0, 1.0),
+    }
+}
+
+impl Widget for ProgressBar {
+    fn name(&self) -> WidgetList {
+        WidgetList::ProgressBar
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env);
+        Ok(())
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        let Some(signal) = &self.settings.signal else {
+            return Ok(());
+        };
+
+        let Some(env) = &self.env else {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::ProgressBar));
+        };
+
+        // Custom signals may be emitted for the first time after the bar is created
+        let progress = Rc::clone(&self.progress);
+        env.signals
+            .borrow_mut()
+            .entry(signal.clone())
+            .or_default()
+            .typed::<f32>()
+            .connect(move |value| progress.set(clamp(*value)));
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let mut data = self.data.borrow_mut();
+            data.width = self.settings.width;
+            data.height = self.settings.height;
+        }
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::ProgressBar));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        // Progress is set from outside of the widget, e.g. by a signal, so a changed fill is
+        // noticed here
+        let fill = self.fill_width();
+        if self.drawn_fill.replace(Some(fill)) != Some(fill) {
+            self.data.borrow_mut().dirty = true;
+        }
+
+        self.draw_style()?;
+
+        let settings = &self.settings;
+        let border = self.border_thickness();
+        let inner_width = settings.width.saturating_sub(2 * border);
+        let inner_height = settings.height.saturating_sub(2 * border);
+
+        let data = self.data.borrow();
+        let mut drawer = self.env.as_ref().unwrap().drawer.borrow_mut();
+        drawer.draw_rect_filled(
+            &data,
+            (border + fill, border),
+            inner_width - fill,
+            inner_height,
+            settings.track_color,
+        )?;
+        drawer.draw_rect_filled(
+            &data,
+            (border, border),
+            fill,
+            inner_height,
+            settings.fill_color,
+        )?;
+
+        if let Some((thickness, color)) = settings.border {
+            drawer.draw_rect_outline(
+                &data,
+                (0, 0),
+                settings.width,
+                settings.height,
+                thickness,
+                color,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl WidgetNew for ProgressBar {
+    type Settings = ProgressBarSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+            settings,
+            is_ready: RefCell::new(false),
+            progress: Rc::new(Cell::new(0.0)),
+            drawn_fill: Cell::new(None),
+            env,
+        })
+    }
+}
+
+impl WidgetStyled for ProgressBar {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    cell::RefCell,
+    process::Command,
+    rc::Rc,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    util::{schedule, UpdateRate},
+};
+
+use super::{
+    text::{Text, TextSettings},
+    Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+/// How often a finished command is checked for while it is running
+const POLL_RATE: Duration = Duration::from_millis(250);
+
+fn default_error_text() -> String {
+    "ERR".to_string()
+}
+
+const fn default_update_rate() -> UpdateRate {
+    UpdateRate::from_secs(1)
+}
+
+/// Settings of a [Script] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScriptSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Executable to run. It is not passed through a shell, use `sh` with `-c` argument for
+    /// pipes and expansions
+    pub command: String,
+
+    /// Arguments passed to the command
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// How often to run the command. Has to be positive
+    #[serde(default = "default_update_rate")]
+    pub update_rate: UpdateRate,
+
+    /// Text displayed if the command can not be started or exits with non-zero code
+    #[serde(default = "default_error_text")]
+    pub error_text: String,
+}
+
+impl Default for ScriptSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            command: String::new(),
+            args: Vec::new(),
+            update_rate: default_update_rate(),
+            error_text: default_error_text(),
+        }
+    }
+}
+
+impl ScriptSettings {
+    /// Run the command and wait for it to finish. Returns trimmed stdout or
+    /// [ScriptSettings::error_text] on failure.
+    pub fn run(&self) -> String {
+        match Command::new(&self.command).args(&self.args).output() {
+            Ok(output) if output.status.success() => {
+                String::from_utf8_lossy(&output.stdout).trim().to_string()
+            }
+            _ => self.error_text.clone(),
+        }
+    }
+
+    /// Check that the command is not rerun on every redraw
+    pub fn validate(&self) -> Result<(), WidgetError> {
+        if self.update_rate.is_zero() {
+            return Err(WidgetError::Custom(anyhow!(
+                "Update rate of the script has to be positive"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Widget displaying output of a command. Command is rerun every `update_rate` milliseconds on a
+/// separate thread so the bar is never blocked by it, the text is changed once it finishes.
+pub struct Script {
+    data: RefCell<WidgetData>,
+    settings: ScriptSettings,
+    is_ready: RefCell<bool>,
+
+    text: RefCell<Text>,
+
+    /// Output of a running command
+    pending: RefCell<Option<Receiver<String>>>,
+
+    last_update: RefCell<DateTime<Local>>,
+    update_rate: TimeDelta,
+}
+
+impl Script {
+    fn run(&self) {
+        let (sender, receiver) = mpsc::channel();
+        let settings = self.settings.clone();
+
+        // Output is dropped if the widget is gone before the command is done
+        thread::spawn(move || {
+            let _ = sender.send(settings.run());
+        });
+
+        *self.pending.borrow_mut() = Some(receiver);
+        *self.last_update.borrow_mut() = Local::now();
+    }
+
+    /// Change the text to the output of a finished command and start the next one when it's due
+    fn update(&self) {
+        let received = match self.pending.borrow().as_ref().map(Receiver::try_recv) {
+            Some(Ok(output)) => Some(output),
+            Some(Err(TryRecvError::Empty)) => return,
+            Some(Err(TryRecvError::Disconnected)) => Some(self.settings.error_text.clone()),
+            None => None,
+        };
+
+        if let Some(output) = received {
+            *self.pending.borrow_mut() = None;
+            self.text.borrow_mut().change_text(&output);
+        }
+
+        if Local::now() - *self.last_update.borrow() >= self.update_rate {
+            self.run();
+        }
+    }
+}
+
+impl Widget for Script {
+    fn name(&self) -> WidgetList {
+        WidgetList::Script
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.text.borrow().env()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        self.apply_style()?;
+
+        self.run();
+        self.text.borrow().init()?;
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        self.update();
+
+        {
+            let text = self.text.borrow();
+            text.prepare()?;
+            let mut text_data = text.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            text_data.position = self_data.position;
+            self_data.width = text_data.width;
+            self_data.height = text_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env().is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Script));
+        }
+
+        self.draw_style()?;
+
+        {
+            let text = self.text.borrow();
+            let mut text_data = text.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            text_data.position = self_data.position;
+            self_data.width = text_data.width;
+            self_data.height = text_data.height;
+        }
+
+        self.text.borrow().draw()
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        let update = if self.pending.borrow().is_some() {
+            Instant::now() + POLL_RATE
+        } else {
+            schedule::instant_after(*self.last_update.borrow(), self.update_rate)
+        };
+        schedule::earliest(Some(update), self.text.borrow().next_update())
+    }
+}
+
+impl WidgetNew for Script {
+    type Settings = ScriptSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+
+            is_ready: RefCell::new(false),
+
+            text: RefCell::new(Text::new(env, settings.text_settings.clone())?),
+            pending: RefCell::new(None),
+
+            update_rate: settings.update_rate.as_delta(),
+            last_update: RefCell::new(Local::now() - settings.update_rate.as_delta()),
+
+            settings,
+        })
+    }
+}
+
+impl WidgetStyled for Script {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
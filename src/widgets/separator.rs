@@ -0,0 +1,148 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{root::Environment, util::Color};
+
+use super::{Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled};
+
+const fn default_thickness() -> usize {
+    1
+}
+
+const fn default_color() -> Color {
+    Color::from_hex(0x6c7086ff)
+}
+
+const fn default_height() -> usize {
+    16
+}
+
+/// Settings of a [Separator] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SeparatorSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Width of the line in pixels
+    #[serde(default = "default_thickness")]
+    pub thickness: usize,
+
+    #[serde(default = "default_color")]
+    pub color: Color,
+
+    /// Length of the line in pixels
+    #[serde(default = "default_height")]
+    pub height: usize,
+}
+
+impl Default for SeparatorSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            style: Style::default(),
+            thickness: default_thickness(),
+            color: default_color(),
+            height: default_height(),
+        }
+    }
+}
+
+/// Widget drawing a vertical line between widgets. Rows with horizontal alignment center it
+/// vertically within their height.
+pub struct Separator {
+    data: RefCell<WidgetData>,
+    settings: SeparatorSettings,
+    is_ready: RefCell<bool>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for Separator {
+    fn name(&self) -> WidgetList {
+        WidgetList::Separator
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env);
+        Ok(())
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let mut data = self.data.borrow_mut();
+            data.width = self.settings.thickness;
+            data.height = self.settings.height;
+        }
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Separator));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+        *self.is_ready.borrow_mut() = false;
+
+        self.draw_style()?;
+
+        let data = self.data.borrow();
+        let mut drawer = self.env.as_ref().unwrap().drawer.borrow_mut();
+        for x in 0..self.settings.thickness {
+            for y in 0..self.settings.height {
+                drawer.draw_pixel(&data, (x, y), self.settings.color)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl WidgetNew for Separator {
+    type Settings = SeparatorSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+            settings,
+            is_ready: RefCell::new(false),
+            env,
+        })
+    }
+}
+
+impl WidgetStyled for Separator {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
@@ -0,0 +1,120 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::root::Environment;
+
+use super::{Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled};
+
+const fn default_width() -> usize {
+    10
+}
+
+/// Settings of a [Spacer] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SpacerSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Width reserved by the spacer in pixels
+    #[serde(default = "default_width")]
+    pub width: usize,
+}
+
+impl Default for SpacerSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            style: Style::default(),
+            width: default_width(),
+        }
+    }
+}
+
+/// Widget that reserves empty space between widgets. Only it's style is drawn.
+pub struct Spacer {
+    data: RefCell<WidgetData>,
+    settings: SpacerSettings,
+    is_ready: RefCell<bool>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for Spacer {
+    fn name(&self) -> WidgetList {
+        WidgetList::Spacer
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env);
+        Ok(())
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let mut data = self.data.borrow_mut();
+            data.width = self.settings.width;
+            data.height = 0;
+        }
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Spacer));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+        *self.is_ready.borrow_mut() = false;
+
+        self.draw_style()
+    }
+}
+
+impl WidgetNew for Spacer {
+    type Settings = SpacerSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+            settings,
+            is_ready: RefCell::new(false),
+            env,
+        })
+    }
+}
+
+impl WidgetStyled for Spacer {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
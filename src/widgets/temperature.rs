@@ -0,0 +1,292 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+use sysinfo::Components;
+
+use crate::{
+    root::Environment,
+    util::{format::NumberFormat, schedule, Color, UpdateRate},
+};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+const fn default_update_rate() -> UpdateRate {
+    UpdateRate::from_millis(2000)
+}
+
+const fn default_warning_threshold() -> f32 {
+    70.0
+}
+
+const fn default_critical_threshold() -> f32 {
+    90.0
+}
+
+const fn default_warning_color() -> Color {
+    Color::from_hex(0xffa500ff)
+}
+
+const fn default_critical_color() -> Color {
+    Color::RED
+}
+
+fn default_number_format() -> NumberFormat {
+    NumberFormat {
+        unit: "°C".to_string(),
+        ..NumberFormat::default()
+    }
+}
+
+/// Settings of a [Temperature] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TemperatureSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Part of the sensor label to look for, case insensitive, e.g. `"Package id 0"` or
+    /// `"amdgpu"`. The first sensor with a temperature is used if not set.
+    #[serde(default)]
+    pub component: Option<String>,
+
+    /// How often to update temperature
+    #[serde(default = "default_update_rate")]
+    pub update_rate: UpdateRate,
+
+    /// Temperature in °C at and above which the widget is drawn with
+    /// [TemperatureSettings::warning_color]
+    #[serde(default = "default_warning_threshold")]
+    pub warning_threshold: f32,
+
+    #[serde(default = "default_warning_color")]
+    pub warning_color: Color,
+
+    /// Temperature in °C at and above which the widget is drawn with
+    /// [TemperatureSettings::critical_color]
+    #[serde(default = "default_critical_threshold")]
+    pub critical_threshold: f32,
+
+    #[serde(default = "default_critical_color")]
+    pub critical_color: Color,
+
+    /// Format of displayed temperature, whole degrees with `°C` by default
+    #[serde(default = "default_number_format")]
+    pub number_format: NumberFormat,
+}
+
+impl Default for TemperatureSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            component: None,
+            update_rate: default_update_rate(),
+            warning_threshold: default_warning_threshold(),
+            warning_color: default_warning_color(),
+            critical_threshold: default_critical_threshold(),
+            critical_color: default_critical_color(),
+            number_format: default_number_format(),
+        }
+    }
+}
+
+impl TemperatureSettings {
+    /// Temperature of the configured sensor among `(label, temperature)` pairs. Sensors without a
+    /// reading are skipped.
+    pub fn select<'a>(
+        &self,
+        sensors: impl IntoIterator<Item = (&'a str, Option<f32>)>,
+    ) -> Option<f32> {
+        let component = self.component.as_ref().map(|c| c.to_lowercase());
+
+        sensors
+            .into_iter()
+            .filter(|(label, _)| match &component {
+                Some(component) => label.to_lowercase().contains(component),
+                None => true,
+            })
+            .find_map(|(_, temperature)| temperature)
+    }
+
+    /// Color of the icon and the text for provided temperature
+    pub fn color(&self, temperature: f32) -> Color {
+        if temperature >= self.critical_threshold {
+            self.critical_color
+        } else if temperature >= self.warning_threshold {
+            self.warning_color
+        } else {
+            self.text_settings.font_color
+        }
+    }
+}
+
+/// Widget displaying temperature of a hardware sensor in °C
+pub struct Temperature {
+    data: RefCell<WidgetData>,
+    settings: TemperatureSettings,
+    is_ready: RefCell<bool>,
+
+    icon_text: RefCell<IconText>,
+
+    components: RefCell<Components>,
+
+    last_update: RefCell<DateTime<Local>>,
+    update_rate: TimeDelta,
+}
+
+impl Temperature {
+    fn get_info(&self) -> Option<f32> {
+        let mut components = self.components.borrow_mut();
+        components.refresh(true);
+
+        self.settings.select(
+            components
+                .list()
+                .iter()
+                .map(|c| (c.label(), c.temperature())),
+        )
+    }
+}
+
+impl Widget for Temperature {
+    fn name(&self) -> WidgetList {
+        WidgetList::Temperature
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.icon_text.borrow().env()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        self.apply_style()?;
+
+        self.icon_text.borrow_mut().change_text("ERR");
+        self.icon_text.borrow_mut().change_icon("");
+        self.icon_text.borrow().init()?;
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env().is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Temperature));
+        }
+
+        self.draw_style()?;
+
+        let mut last_update = self.last_update.borrow_mut();
+        if Local::now() - *last_update >= self.update_rate {
+            let mut it = self.icon_text.borrow_mut();
+            match self.get_info() {
+                Some(temperature) => {
+                    it.change_color(self.settings.color(temperature));
+                    it.change_text(&self.settings.number_format.format(temperature as f64));
+                }
+                None => {
+                    it.change_color(self.settings.text_settings.font_color);
+                    it.change_text("ERR");
+                }
+            }
+
+            *last_update = Local::now();
+        }
+
+        {
+            let it = self.icon_text.borrow();
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.icon_text.borrow().draw()
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        Some(schedule::instant_after(
+            *self.last_update.borrow(),
+            self.update_rate,
+        ))
+    }
+}
+
+impl WidgetNew for Temperature {
+    type Settings = TemperatureSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            data: RefCell::new(settings.default_data),
+
+            is_ready: RefCell::new(false),
+
+            icon_text: RefCell::new(IconText::new(
+                env,
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?),
+
+            components: RefCell::new(Components::new()),
+
+            update_rate: settings.update_rate.as_delta(),
+            last_update: RefCell::new(DateTime::default()),
+
+            settings,
+        })
+    }
+}
+
+impl WidgetStyled for Temperature {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
@@ -1,25 +1,51 @@
 use std::{
-    cell::{Ref, RefCell, RefMut},
+    cell::{Cell, RefCell},
     rc::Rc,
+    time::Instant,
 };
 
 use anyhow::Result;
-use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::layout::{CoordinateSystem, HorizontalAlign, Layout, LayoutSettings, TextStyle};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use thiserror::Error;
 
 use crate::{
     root::Environment,
-    util::{fonts, Color},
+    util::{
+        drawer::{Drawer, DrawerError},
+        Color,
+    },
     widgets::Widget,
 };
 
 use super::{Style, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled};
 
+/// Horizontal alignment of text inside a [Text] widget
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl From<TextAlign> for HorizontalAlign {
+    fn from(value: TextAlign) -> Self {
+        match value {
+            TextAlign::Left => HorizontalAlign::Left,
+            TextAlign::Center => HorizontalAlign::Center,
+            TextAlign::Right => HorizontalAlign::Right,
+        }
+    }
+}
+
 /// Settings of a [Text] widget
-#[derive(Deserialize, Debug, Clone, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TextSettings {
     #[serde(default, flatten)]
     pub default_data: WidgetData,
@@ -34,14 +60,117 @@ pub struct TextSettings {
     #[serde(default)]
     pub size: f32,
 
-    /// Id of font in vector of fonts for current [crate::util::fonts::FontsMap]
+    /// Id of font in [Environment::fonts](crate::root::Environment::fonts) of the bar
     #[serde(default)]
     pub fontid: usize,
 
+    /// Ids of fonts used for characters missing in [TextSettings::fontid], tried in order. E.g.
+    /// an emoji or CJK font mixed with a latin one.
+    #[serde(default)]
+    pub fallback_fonts: Vec<usize>,
+
+    /// Alignment of text inside the configured width. Has no effect if width is 0, since the
+    /// widget is sized to it's text then.
+    #[serde(default)]
+    pub horizontal_align: TextAlign,
+
+    /// Widest the widget can get in pixels, text beyond it is clipped. Unlimited if set to 0.
+    #[serde(default)]
+    pub max_width: usize,
+
+    /// Scroll text that does not fit into [TextSettings::max_width] instead of clipping it
+    #[serde(default)]
+    pub scroll: bool,
+
+    /// Cut text that does not fit into [TextSettings::max_width] and end it with "…" instead of
+    /// clipping it mid glyph. Has no effect on [scrolled](TextSettings::scroll) text.
+    #[serde(default)]
+    pub ellipsis: bool,
+
+    /// Draw a line under the text
+    #[serde(default)]
+    pub underline: bool,
+
+    /// Draw a line through the middle of the text
+    #[serde(default)]
+    pub strikethrough: bool,
+
+    /// Color of underline and strikethrough. Same as [TextSettings::font_color] if not set.
+    #[serde(default)]
+    pub decoration_color: Option<Color>,
+
+    /// Thickness of underline and strikethrough in pixels. Scales with font size if set to 0.
+    #[serde(default)]
+    pub decoration_thickness: usize,
+
     #[serde(default)]
     pub style: Style,
 }
 
+impl TextSettings {
+    /// Ids of all fonts the text may be drawn with, the main one first
+    pub fn font_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::once(self.fontid).chain(self.fallback_fonts.iter().copied())
+    }
+
+    /// Thickness of [underline](TextSettings::underline) and
+    /// [strikethrough](TextSettings::strikethrough) in pixels
+    pub fn decoration_thickness(&self) -> usize {
+        match self.decoration_thickness {
+            0 => ((self.size / 16.0).round() as usize).max(1),
+            thickness => thickness,
+        }
+    }
+}
+
+/// Split `text` into runs of characters laid out with the same font. Every character uses the
+/// first font out of `fontid` and `fallbacks` that has a glyph for it, or `fontid` if none do.
+/// Whitespace keeps the font of the preceding character.
+pub fn font_runs<'a>(
+    fonts: &[fontdue::Font],
+    text: &'a str,
+    fontid: usize,
+    fallbacks: &[usize],
+) -> Vec<(&'a str, usize)> {
+    let font_of = |c: char| {
+        std::iter::once(&fontid)
+            .chain(fallbacks)
+            .copied()
+            .find(|&id| fonts.get(id).is_some_and(|f| f.lookup_glyph_index(c) != 0))
+            .unwrap_or(fontid)
+    };
+
+    let mut runs: Vec<(&str, usize)> = Vec::new();
+    let mut start = 0;
+    let mut current = None;
+    for (i, c) in text.char_indices() {
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let font = font_of(c);
+        match current {
+            Some(id) if id != font => {
+                runs.push((&text[start..i], id));
+                start = i;
+            }
+            _ => {}
+        }
+        current = Some(font);
+    }
+
+    if start < text.len() {
+        runs.push((&text[start..], current.unwrap_or(fontid)));
+    }
+    runs
+}
+
+/// Appended to text cut by [TextSettings::ellipsis]
+pub const ELLIPSIS: char = '…';
+
+/// Speed of [scrolling](TextSettings::scroll) text in pixels per second
+pub const SCROLL_SPEED: f32 = 30.0;
+
 #[derive(Debug, Error)]
 pub enum TextError {}
 
@@ -54,6 +183,8 @@ pub struct Text {
     env: Option<Rc<Environment>>,
 
     is_ready: RefCell<bool>,
+    /// Moment text started scrolling from the beginning
+    scroll_start: Cell<Instant>,
 }
 
 impl Text {
@@ -68,29 +199,226 @@ impl Text {
         text
     }
 
+    /// Replace displayed text. Layout is recalculated and the widget is marked
+    /// [dirty](WidgetData::dirty) only if the text actually changed.
     pub fn change_text(&mut self, text: &str) {
+        if self.settings.text == text {
+            return;
+        }
+        self.settings.text = text.to_string();
+        self.scroll_start.set(Instant::now());
+
         self.layout.clear();
-        if let Some(ref mut _env) = self.env {
-            self.layout.append(
-                &fonts::fonts_vec(),
-                &TextStyle::new(text, self.settings.size, self.settings.fontid),
-            );
+        if self.env.is_some() {
+            self.append_text();
         }
 
         self.update_width();
-        self.data.borrow_mut().height = self.layout.height() as usize;
+        let mut data = self.data.borrow_mut();
+        data.height = self.layout.height() as usize;
+        data.dirty = true;
     }
 
-    fn update_width(&self) {
-        let mut data = self.data.borrow_mut();
-        data.width = 0;
+    /// Change color of the text. Layout is not recalculated
+    pub fn change_color(&mut self, color: Color) {
+        if self.settings.font_color != color {
+            self.settings.font_color = color;
+            self.data.borrow_mut().dirty = true;
+        }
+    }
+
+    /// Color glyphs are currently drawn with
+    pub fn color(&self) -> Color {
+        self.settings.font_color
+    }
+
+    /// Whether the text does not fit into [TextSettings::max_width] and is
+    /// [scrolled](TextSettings::scroll)
+    pub fn is_scrolling(&self) -> bool {
+        self.settings.scroll
+            && self.settings.max_width != 0
+            && self.content_width() > self.settings.max_width
+    }
+
+    /// Lay out the text, falling back to other fonts for missing characters. See [font_runs]
+    fn append_text(&mut self) {
+        let Some(env) = self.env.clone() else {
+            return;
+        };
+        let fonts = env.fonts.fonts_vec();
+        let runs = font_runs(
+            &fonts,
+            &self.settings.text,
+            self.settings.fontid,
+            &self.settings.fallback_fonts,
+        );
+
+        for (run, fontid) in runs {
+            // Missing fonts are reported by validation and widgets, not by a panic of the layout
+            if fontid >= fonts.len() {
+                continue;
+            }
+            self.layout
+                .append(&fonts, &TextStyle::new(run, self.settings.size, fontid));
+        }
+
+        if let Some(cut) = self.ellipsized(&fonts) {
+            self.layout.clear();
+            for (run, fontid) in font_runs(
+                &fonts,
+                &cut,
+                self.settings.fontid,
+                &self.settings.fallback_fonts,
+            ) {
+                if fontid >= fonts.len() {
+                    continue;
+                }
+                self.layout
+                    .append(&fonts, &TextStyle::new(run, self.settings.size, fontid));
+            }
+        }
+    }
+
+    /// Text cut to fit into [TextSettings::max_width] together with [ELLIPSIS], if it has to be
+    /// [cut](TextSettings::ellipsis)
+    fn ellipsized(&self, fonts: &[fontdue::Font]) -> Option<String> {
+        let max_width = self.settings.max_width;
+        if !self.settings.ellipsis
+            || self.settings.scroll
+            || max_width == 0
+            || self.content_width() <= max_width
+        {
+            return None;
+        }
+
+        let ellipsis_width = self
+            .settings
+            .font_ids()
+            .filter_map(|id| fonts.get(id))
+            .find(|font| font.lookup_glyph_index(ELLIPSIS) != 0)
+            .map(|font| {
+                font.metrics(ELLIPSIS, self.settings.size)
+                    .advance_width
+                    .ceil() as usize
+            })
+            .unwrap_or(0);
+
+        // Every glyph of the layout is a character of the text
+        let fitting = self
+            .layout
+            .glyphs()
+            .iter()
+            .take_while(|glyph| glyph.x.ceil() as usize + glyph.width + ellipsis_width <= max_width)
+            .count();
+
+        let mut cut: String = self.settings.text.chars().take(fitting).collect();
+        cut.truncate(cut.trim_end().len());
+        cut.push(ELLIPSIS);
+        Some(cut)
+    }
+
+    /// Width of laid out glyphs
+    fn content_width(&self) -> usize {
+        let mut width = 0;
         if let Some(lines) = self.layout.lines() {
             for line in lines {
                 let glyph = self.layout.glyphs()[line.glyph_end];
-                let width = glyph.width + glyph.x.ceil() as usize;
+                width = usize::max(width, glyph.width + glyph.x.ceil() as usize);
+            }
+        }
+
+        width
+    }
+
+    /// Underline and strikethrough of every laid out line as `(start, end, y)` relative to the
+    /// widget. Lines span from the first glyph to the end of the last one.
+    pub fn decorations(&self) -> Vec<(usize, usize, usize)> {
+        let mut decorations = Vec::new();
+        if !self.settings.underline && !self.settings.strikethrough {
+            return decorations;
+        }
+
+        let thickness = self.settings.decoration_thickness();
+        let glyphs = self.layout.glyphs();
+        for line in self.layout.lines().into_iter().flatten() {
+            let Some(line_glyphs) = glyphs.get(line.glyph_start..=line.glyph_end) else {
+                continue;
+            };
+            let Some(start) = line_glyphs
+                .iter()
+                .find(|glyph| !glyph.char_data.is_whitespace())
+                .map(|glyph| glyph.x.max(0.0) as usize)
+            else {
+                continue;
+            };
+            let end = line_glyphs
+                .iter()
+                .map(|glyph| glyph.x.ceil() as usize + glyph.width)
+                .max()
+                .unwrap_or(start);
+
+            if self.settings.underline {
+                let y = line.baseline_y.ceil() as usize + 1 + thickness / 2;
+                decorations.push((start, end, y));
+            }
+            if self.settings.strikethrough {
+                let y = (line.baseline_y - line.max_ascent * 0.3).max(0.0) as usize;
+                decorations.push((start, end, y));
+            }
+        }
+
+        decorations
+    }
 
-                data.width = usize::max(data.width, width);
+    /// Draw [decorations](Text::decorations) moved left by `shift` and cut at `clip_width`
+    fn draw_decorations(
+        &self,
+        drawer: &mut Drawer,
+        data: &WidgetData,
+        shift: isize,
+        clip_width: usize,
+    ) -> Result<(), DrawerError> {
+        let color = self
+            .settings
+            .decoration_color
+            .unwrap_or(self.settings.font_color);
+        let thickness = self.settings.decoration_thickness();
+        // Ends of lines are rounded, so they are moved in to not stick out of the text
+        let inset = thickness / 2;
+
+        for (start, end, y) in self.decorations() {
+            let start = (start as isize - shift).max(0) as usize;
+            let end = (end as isize - shift).clamp(0, clip_width.min(isize::MAX as usize) as isize)
+                as usize;
+            if start >= end {
+                continue;
             }
+
+            let from = start + inset;
+            let to = (end - 1).saturating_sub(inset).max(from);
+            drawer.draw_line(data, (from, y), (to, y), color, thickness)?;
+        }
+
+        Ok(())
+    }
+
+    /// Distance in pixels between the end of scrolled text and it's next repetition
+    fn scroll_gap(&self) -> usize {
+        self.settings.size.ceil() as usize
+    }
+
+    fn update_width(&self) {
+        let mut data = self.data.borrow_mut();
+
+        // Glyphs are already aligned by the layout, widget keeps the width they are aligned in
+        let width = self.settings.default_data.width;
+        data.width = match width != 0 && self.settings.horizontal_align != TextAlign::Left {
+            true => width,
+            false => self.content_width(),
+        };
+
+        if self.settings.max_width != 0 {
+            data.width = data.width.min(self.settings.max_width);
         }
     }
 }
@@ -106,16 +434,7 @@ impl Widget for Text {
 
     fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
         self.env = Some(env);
-
-        let _env = self.env.as_mut().unwrap();
-        self.layout.append(
-            &fonts::fonts_vec(),
-            &TextStyle::new(
-                &self.settings.text,
-                self.settings.size,
-                self.settings.fontid,
-            ),
-        );
+        self.append_text();
 
         Ok(())
     }
@@ -133,10 +452,10 @@ impl Widget for Text {
 
     fn prepare(&self) -> Result<(), WidgetError> {
         self.update_width();
+        self.data.borrow_mut().height = self.layout.height() as usize;
         self.apply_style()?;
 
         *self.is_ready.borrow_mut() = true;
-        self.data.borrow_mut().height = self.layout.height() as usize;
         Ok(())
     }
 
@@ -152,23 +471,52 @@ impl Widget for Text {
 
         self.draw_style()?;
 
-        let font = &fonts::fonts_vec()[self.settings.fontid];
-        let data = &self.data.borrow_mut();
+        let fonts = self.env.as_ref().unwrap().fonts.fonts_vec();
+        let data = &mut self.data.borrow_mut();
         let mut drawer = self.env.as_ref().unwrap().drawer.borrow_mut();
+        let scrolling = self.is_scrolling();
+        // Content changes every frame while scrolling
+        data.dirty |= scrolling;
+        data.report_damage(&mut drawer);
+
+        let color = self.settings.font_color;
+        if !scrolling {
+            let clip_width = match self.settings.max_width {
+                0 => usize::MAX,
+                width => width,
+            };
+            for glyph in self.layout.glyphs() {
+                let font = &fonts[glyph.font_index];
+                drawer.draw_glyph_clipped(data, glyph, font, color, 0, clip_width)?;
+            }
+            self.draw_decorations(&mut drawer, data, 0, clip_width)?;
+            return Ok(());
+        }
 
-        for glyph in self.layout.glyphs() {
-            drawer.draw_glyph(data, glyph, font, self.settings.font_color);
+        // Text is drawn twice so the beginning follows the end when it wraps around
+        let period = self.content_width() + self.scroll_gap();
+        let elapsed = self.scroll_start.get().elapsed().as_secs_f32();
+        let offset = (elapsed * SCROLL_SPEED) as usize % period;
+        for shift in [offset as isize, offset as isize - period as isize] {
+            for glyph in self.layout.glyphs() {
+                let font = &fonts[glyph.font_index];
+                drawer.draw_glyph_clipped(data, glyph, font, color, shift, data.width)?;
+            }
+            self.draw_decorations(&mut drawer, data, shift, data.width)?;
         }
 
         Ok(())
     }
 
-    fn data(&self) -> Ref<'_, WidgetData> {
-        self.data.borrow()
+    fn next_update(&self) -> Option<Instant> {
+        match self.is_scrolling() {
+            true => Some(Instant::now()),
+            false => None,
+        }
     }
 
-    fn data_mut(&self) -> RefMut<'_, WidgetData> {
-        self.data.borrow_mut()
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
     }
 }
 
@@ -186,6 +534,7 @@ impl WidgetNew for Text {
                 0 => None,
                 width => Some(width as f32),
             },
+            horizontal_align: settings.horizontal_align.into(),
             ..LayoutSettings::default()
         });
 
@@ -197,6 +546,7 @@ impl WidgetNew for Text {
             env: None,
 
             is_ready: RefCell::new(false),
+            scroll_start: Cell::new(Instant::now()),
         };
 
         if let Some(e) = env {
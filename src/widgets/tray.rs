@@ -0,0 +1,370 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    services::{
+        tray::{TrayIcon, TrayItem, TrayState},
+        ServiceList,
+    },
+    util::signals::SignalNames,
+};
+
+use super::{
+    containers::{
+        row::{Row, RowSettings},
+        ContainerSingle,
+    },
+    text::{Text, TextSettings},
+    Interactive, ScrollEvent, Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew,
+    WidgetStyled,
+};
+
+const fn default_icon_size() -> usize {
+    20
+}
+
+/// Settings of a [Tray] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TraySettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings of labels shown for items without an icon pixmap
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Layout of tray items
+    #[serde(default)]
+    pub row_settings: RowSettings,
+
+    /// Width and height of item icons in pixels
+    #[serde(default = "default_icon_size")]
+    pub icon_size: usize,
+}
+
+impl Default for TraySettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            row_settings: RowSettings::default(),
+            icon_size: default_icon_size(),
+        }
+    }
+}
+
+impl TraySettings {
+    /// Label shown instead of an icon for items without pixmaps: the first letter of the title,
+    /// id or icon name, whichever is set
+    pub fn label(&self, item: &TrayItem) -> TextSettings {
+        let name = [&item.title, &item.id, &item.icon_name]
+            .into_iter()
+            .find(|name| !name.is_empty());
+
+        TextSettings {
+            text: name
+                .and_then(|name| name.chars().next())
+                .map(|c| c.to_uppercase().to_string())
+                .unwrap_or_else(|| "?".to_string()),
+            ..self.text_settings.clone()
+        }
+    }
+}
+
+/// Icon of a single tray item. Forwards clicks to the item
+struct TrayItemIcon {
+    item: TrayItem,
+    size: usize,
+    data: RefCell<WidgetData>,
+
+    /// Shown instead of the icon if the item has no pixmaps
+    label: Option<Text>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for TrayItemIcon {
+    fn name(&self) -> WidgetList {
+        WidgetList::Tray
+    }
+
+    fn as_interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(Rc::clone(&env));
+        match &mut self.label {
+            Some(label) => label.bind(env),
+            None => Ok(()),
+        }
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        match &self.label {
+            Some(label) => label.init(),
+            None => Ok(()),
+        }
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        let mut data = self.data.borrow_mut();
+        match &self.label {
+            Some(label) => {
+                label.prepare()?;
+                let label_data = label.data();
+                data.width = label_data.width;
+                data.height = label_data.height;
+            }
+            None => {
+                data.width = self.size;
+                data.height = self.size;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        let Some(env) = &self.env else {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Tray));
+        };
+
+        if let Some(label) = &self.label {
+            label.data_mut().position = self.data.borrow().position;
+            return label.draw();
+        }
+
+        let mut data = self.data.borrow_mut();
+        let mut drawer = env.drawer.borrow_mut();
+        data.report_damage(&mut drawer);
+
+        let Some(icon) = TrayIcon::best(&self.item.icons, self.size) else {
+            return Ok(());
+        };
+
+        // Nearest neighbour scaling, icons are usually provided in the requested size
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let pixel = icon.pixel(x * icon.width / self.size, y * icon.height / self.size);
+                if let Some(color) = pixel {
+                    drawer.draw_pixel(&data, (x, y), color)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl WidgetNew for TrayItemIcon {
+    type Settings = (TrayItem, TraySettings);
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        let (item, settings) = settings;
+        let label = match TrayIcon::best(&item.icons, settings.icon_size) {
+            Some(_) => None,
+            None => Some(Text::new(env.clone(), settings.label(&item))?),
+        };
+
+        Ok(TrayItemIcon {
+            item,
+            size: settings.icon_size,
+            data: RefCell::new(WidgetData::default()),
+            label,
+            env,
+        })
+    }
+}
+
+impl Interactive for TrayItemIcon {
+    fn click(&self, button: u32) -> Result<(), WidgetError> {
+        // Position on the surface is the best known approximation of the pointer position
+        let position = self.data.borrow().position;
+        self.item
+            .click(button, position.0 as i32, position.1 as i32)
+            .map_err(|e| WidgetError::Custom(e.into()))
+    }
+}
+
+/// Widget displaying icons of the system tray. Left click activates an item, right click asks for
+/// it's menu. Requires [Tray](crate::services::tray::Tray) service.
+pub struct Tray {
+    data: RefCell<WidgetData>,
+    settings: TraySettings,
+    is_ready: RefCell<bool>,
+
+    row: RefCell<Row>,
+    state: Rc<RefCell<Option<TrayState>>>,
+    changed: Rc<Cell<bool>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Tray {
+    /// Recreate item icons from the last received state
+    fn update_items(&self) -> Result<(), WidgetError> {
+        let state = self.state.borrow();
+        let Some(state) = state.as_ref() else {
+            return Ok(());
+        };
+
+        let mut row = self.row.borrow_mut();
+        row.widgets_mut().clear();
+        for item in &state.items {
+            row.create_widget(TrayItemIcon::new, (item.clone(), self.settings.clone()))?;
+        }
+
+        self.changed.set(false);
+        Ok(())
+    }
+}
+
+impl Widget for Tray {
+    fn name(&self) -> WidgetList {
+        WidgetList::Tray
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.row.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::Tray));
+        }
+
+        let env = self.env.as_ref().unwrap();
+
+        if !env.signals.borrow().contains_key(&SignalNames::Tray) {
+            return Err(WidgetError::NoCorespondingSignal(
+                WidgetList::Tray,
+                ServiceList::Tray,
+            ));
+        }
+
+        let state = Rc::clone(&self.state);
+        let changed = Rc::clone(&self.changed);
+        env.connect_signals(&[SignalNames::Tray], move |_, data| {
+            if let Some(new_state) = data.downcast_ref::<TrayState>() {
+                *state.borrow_mut() = Some(new_state.clone());
+                changed.set(true);
+            }
+        });
+
+        self.update_items()?;
+        self.row.borrow().init()
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        if self.changed.get() {
+            self.update_items()?;
+        }
+
+        {
+            let row = self.row.borrow();
+            row.data_mut().position = self.data.borrow().position;
+            row.prepare()?;
+
+            let row_data = row.data();
+            let mut self_data = self.data.borrow_mut();
+            self_data.width = row_data.width;
+            self_data.height = row_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Tray));
+        }
+
+        if !*self.is_ready.borrow() || self.changed.get() {
+            self.prepare()?;
+        }
+        *self.is_ready.borrow_mut() = false;
+
+        self.draw_style()?;
+
+        // Bar moves the widget after it was prepared, icons are aligned to the final position
+        let row = self.row.borrow();
+        row.data_mut().position = self.data().position;
+        row.prepare()?;
+        row.draw()
+    }
+
+    fn click_at(&self, x: usize, y: usize, button: u32) -> Result<bool, WidgetError> {
+        self.row.borrow().click_at(x, y, button)
+    }
+
+    fn scroll_at(&self, x: usize, y: usize, event: ScrollEvent) -> Result<bool, WidgetError> {
+        self.row.borrow().scroll_at(x, y, event)
+    }
+}
+
+impl WidgetNew for Tray {
+    type Settings = TraySettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Tray {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            row: RefCell::new(Row::new(env, settings.row_settings.clone())?),
+            state: Rc::new(RefCell::new(None)),
+            changed: Rc::new(Cell::new(false)),
+
+            settings,
+
+            env: None,
+        })
+    }
+}
+
+impl WidgetStyled for Tray {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
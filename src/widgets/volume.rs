@@ -0,0 +1,210 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    services::{volume::VolumeState, ServiceList},
+    util::signals::SignalNames,
+};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+fn default_icons() -> Vec<String> {
+    vec!["󰕿".to_string(), "󰖀".to_string(), "󰕾".to_string()]
+}
+
+fn default_muted_icon() -> String {
+    "󰝟".to_string()
+}
+
+/// Settings of a [Volume] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VolumeSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Icons for volume levels from the quietest to the loudest. Range from 0% to 100% is split
+    /// evenly between them, volume above 100% uses the last one.
+    #[serde(default = "default_icons")]
+    pub icons: Vec<String>,
+
+    /// Icon displayed while the output is muted
+    #[serde(default = "default_muted_icon")]
+    pub muted_icon: String,
+}
+
+impl Default for VolumeSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            icons: default_icons(),
+            muted_icon: default_muted_icon(),
+        }
+    }
+}
+
+impl VolumeSettings {
+    /// Icon matching the volume state
+    pub fn icon(&self, state: &VolumeState) -> &str {
+        if state.muted || self.icons.is_empty() {
+            return &self.muted_icon;
+        }
+
+        let index = state.volume as usize * self.icons.len() / 100;
+        &self.icons[index.min(self.icons.len() - 1)]
+    }
+}
+
+/// Widget displaying volume of the default output device. Requires
+/// [Volume](crate::services::volume::Volume) service.
+pub struct Volume {
+    data: RefCell<WidgetData>,
+    settings: Rc<VolumeSettings>,
+    is_ready: RefCell<bool>,
+
+    icon_text: Rc<RefCell<IconText>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for Volume {
+    fn name(&self) -> WidgetList {
+        WidgetList::Volume
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::Volume));
+        }
+
+        let env = self.env.as_ref().unwrap();
+
+        if !env.signals.borrow().contains_key(&SignalNames::Volume) {
+            return Err(WidgetError::NoCorespondingSignal(
+                WidgetList::Volume,
+                ServiceList::Volume,
+            ));
+        }
+
+        {
+            let mut ic = self.icon_text.borrow_mut();
+            ic.change_icon(&self.settings.muted_icon);
+            ic.change_text("ERR");
+            ic.init()?;
+        }
+
+        let signal_ic = Rc::clone(&self.icon_text);
+        let settings = Rc::clone(&self.settings);
+
+        env.connect_signals(&[SignalNames::Volume], move |_, data| {
+            if let Some(state) = data.downcast_ref::<VolumeState>() {
+                let mut ic = signal_ic.borrow_mut();
+                ic.change_icon(settings.icon(state));
+                ic.change_text(&format!("{}%", state.volume));
+            }
+        });
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Volume));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        self.draw_style()?;
+
+        {
+            let ic_data = self.icon_text.borrow();
+            ic_data.data_mut().position = self.data().position;
+        }
+        self.icon_text.borrow_mut().draw()
+    }
+}
+
+impl WidgetNew for Volume {
+    type Settings = VolumeSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Volume {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            icon_text: Rc::new(RefCell::new(IconText::new(
+                env.clone(),
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?)),
+
+            settings: Rc::new(settings),
+
+            env: None,
+        })
+    }
+}
+
+impl WidgetStyled for Volume {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
@@ -0,0 +1,317 @@
+use std::{cell::RefCell, rc::Rc};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    services::{
+        weather::{Condition, WeatherServiceSettings, WeatherState},
+        ServiceList,
+    },
+    util::{signals::SignalNames, UpdateRate},
+};
+
+use super::{
+    icon_text::{IconText, IconTextSettings},
+    text::TextSettings,
+    Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+fn default_url() -> String {
+    "https://wttr.in/?format=j1".to_string()
+}
+
+const fn default_update_rate() -> UpdateRate {
+    UpdateRate::from_mins(30)
+}
+
+fn default_format() -> String {
+    "{temperature}{unit}".to_string()
+}
+
+fn default_unavailable_icon() -> String {
+    "󰨹".to_string()
+}
+
+/// Units temperature is displayed in
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Units {
+    /// Degrees Celsius
+    #[default]
+    Metric,
+    /// Degrees Fahrenheit
+    Imperial,
+}
+
+impl Units {
+    /// Convert a temperature in degrees Celsius
+    pub fn temperature(&self, celsius: f64) -> f64 {
+        match self {
+            Units::Metric => celsius,
+            Units::Imperial => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Units::Metric => "°C",
+            Units::Imperial => "°F",
+        }
+    }
+}
+
+/// Settings of a [Weather] widget
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WeatherSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Url current weather is fetched from. Either wttr.in with `format=j1`, e.g.
+    /// `"https://wttr.in/London?format=j1"`, or an Open-Meteo forecast with current
+    /// `temperature_2m` and `weather_code`. Location is guessed by wttr.in if not given.
+    #[serde(default = "default_url")]
+    pub url: String,
+
+    /// How often the weather is fetched
+    #[serde(default = "default_update_rate")]
+    pub update_rate: UpdateRate,
+
+    /// Format of the text. `{temperature}`, `{unit}` and `{condition}` are replaced with the
+    /// rounded temperature, it's unit and the name of the condition.
+    #[serde(default = "default_format")]
+    pub format: String,
+
+    #[serde(default)]
+    pub units: Units,
+
+    /// Icon shown before the first response and if the last request failed
+    #[serde(default = "default_unavailable_icon")]
+    pub unavailable_icon: String,
+}
+
+impl Default for WeatherSettings {
+    fn default() -> Self {
+        Self {
+            default_data: WidgetData::default(),
+            text_settings: TextSettings::default(),
+            style: Style::default(),
+            url: default_url(),
+            update_rate: default_update_rate(),
+            format: default_format(),
+            units: Units::default(),
+            unavailable_icon: default_unavailable_icon(),
+        }
+    }
+}
+
+impl WeatherSettings {
+    /// Check that there is something to fetch
+    pub fn validate(&self) -> Result<(), WidgetError> {
+        if self.url.is_empty() {
+            return Err(WidgetError::Custom(anyhow!("Weather url is empty")));
+        }
+
+        if self.update_rate.is_zero() {
+            return Err(WidgetError::Custom(anyhow!(
+                "Update rate of the weather has to be positive"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Settings of the [Weather](crate::services::weather::Weather) service fetching the
+    /// weather
+    pub fn service(&self) -> WeatherServiceSettings {
+        WeatherServiceSettings {
+            url: self.url.clone(),
+            update_rate: self.update_rate,
+        }
+    }
+
+    /// Icon of the current weather
+    pub fn icon(&self, state: Option<&WeatherState>) -> &str {
+        let Some(state) = state else {
+            return &self.unavailable_icon;
+        };
+
+        match state.condition {
+            Condition::Clear => "󰖙",
+            Condition::PartlyCloudy => "󰖕",
+            Condition::Cloudy => "󰖐",
+            Condition::Fog => "󰖑",
+            Condition::Drizzle => "󰖗",
+            Condition::Rain => "󰖖",
+            Condition::Snow => "󰖘",
+            Condition::Thunderstorm => "󰖓",
+            Condition::Unknown => &self.unavailable_icon,
+        }
+    }
+
+    /// Text describing the current weather. Empty if there is none
+    pub fn text(&self, state: Option<&WeatherState>) -> String {
+        let Some(state) = state else {
+            return String::new();
+        };
+
+        self.format
+            .replace(
+                "{temperature}",
+                // Adding zero turns `-0` into `0`
+                &(self.units.temperature(state.temperature).round() + 0.0).to_string(),
+            )
+            .replace("{unit}", self.units.symbol())
+            .replace("{condition}", state.condition.name())
+    }
+}
+
+/// Widget displaying current weather. Requires [Weather](crate::services::weather::Weather)
+/// service.
+pub struct Weather {
+    data: RefCell<WidgetData>,
+    settings: Rc<WeatherSettings>,
+    is_ready: RefCell<bool>,
+
+    icon_text: Rc<RefCell<IconText>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for Weather {
+    fn name(&self) -> WidgetList {
+        WidgetList::Weather
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.icon_text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::Weather));
+        }
+
+        let env = self.env.as_ref().unwrap();
+
+        if !env.signals.borrow().contains_key(&SignalNames::Weather) {
+            return Err(WidgetError::NoCorespondingSignal(
+                WidgetList::Weather,
+                ServiceList::Weather,
+            ));
+        }
+
+        {
+            let mut ic = self.icon_text.borrow_mut();
+            ic.change_icon(self.settings.icon(None));
+            ic.change_text("");
+            ic.init()?;
+        }
+
+        let signal_ic = Rc::clone(&self.icon_text);
+        let settings = Rc::clone(&self.settings);
+
+        env.signals.borrow()[&SignalNames::Weather]
+            .typed::<Option<WeatherState>>()
+            .connect(move |state| {
+                let mut ic = signal_ic.borrow_mut();
+                ic.change_icon(settings.icon(state.as_ref()));
+                ic.change_text(&settings.text(state.as_ref()));
+            });
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let it = self.icon_text.borrow();
+            it.prepare()?;
+            let mut it_data = it.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            it_data.position = self_data.position;
+            self_data.width = it_data.width;
+            self_data.height = it_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Weather));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        self.draw_style()?;
+
+        {
+            let ic_data = self.icon_text.borrow();
+            ic_data.data_mut().position = self.data().position;
+        }
+        self.icon_text.borrow_mut().draw()
+    }
+}
+
+impl WidgetNew for Weather {
+    type Settings = WeatherSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        settings.validate()?;
+
+        Ok(Weather {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            icon_text: Rc::new(RefCell::new(IconText::new(
+                env.clone(),
+                IconTextSettings {
+                    icon_settings: settings.text_settings.clone(),
+                    text_settings: settings.text_settings.clone(),
+                    ..IconTextSettings::default()
+                },
+            )?)),
+
+            settings: Rc::new(settings),
+
+            env: None,
+        })
+    }
+}
+
+impl WidgetStyled for Weather {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
@@ -0,0 +1,176 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{root::Environment, services::ServiceList, util::signals::SignalNames};
+
+use super::{
+    text::{Text, TextSettings},
+    Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew, WidgetStyled,
+};
+
+/// Settings of a [WindowTitle] widget
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WindowTitleSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings for underlying [Text] widget. Long titles are clipped or scrolled according to
+    /// [TextSettings::max_width] and [TextSettings::scroll]
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Text shown if no window is focused or the focused window has no title
+    #[serde(default)]
+    pub empty_title: String,
+}
+
+impl WindowTitleSettings {
+    /// Text shown for a window with `title`
+    pub fn title<'a>(&'a self, title: &'a str) -> &'a str {
+        match title.trim().is_empty() {
+            true => &self.empty_title,
+            false => title,
+        }
+    }
+}
+
+/// Widget displaying title of the focused window. Requires
+/// [WindowTitle](crate::services::clients::WindowTitle) service.
+pub struct WindowTitle {
+    data: RefCell<WidgetData>,
+    settings: Rc<WindowTitleSettings>,
+    is_ready: RefCell<bool>,
+
+    text: Rc<RefCell<Text>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Widget for WindowTitle {
+    fn name(&self) -> WidgetList {
+        WidgetList::WindowTitle
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.text.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::WindowTitle));
+        }
+
+        let env = self.env.as_ref().unwrap();
+
+        if !env.signals.borrow().contains_key(&SignalNames::WindowTitle) {
+            return Err(WidgetError::NoCorespondingSignal(
+                WidgetList::WindowTitle,
+                ServiceList::WindowTitle,
+            ));
+        }
+
+        {
+            let mut text = self.text.borrow_mut();
+            text.change_text(&self.settings.empty_title);
+            text.init()?;
+        }
+
+        let signal_text = Rc::clone(&self.text);
+        let settings = Rc::clone(&self.settings);
+
+        env.signals.borrow()[&SignalNames::WindowTitle]
+            .typed::<String>()
+            .connect(move |title| {
+                signal_text.borrow_mut().change_text(settings.title(title));
+            });
+
+        Ok(())
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        {
+            let text = self.text.borrow();
+            text.prepare()?;
+            let mut text_data = text.data_mut();
+            let mut self_data = self.data.borrow_mut();
+            text_data.position = self_data.position;
+            self_data.width = text_data.width;
+            self_data.height = text_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::WindowTitle));
+        }
+
+        if !*self.is_ready.borrow() {
+            self.prepare()?;
+        }
+
+        self.draw_style()?;
+
+        {
+            let text = self.text.borrow();
+            text.data_mut().position = self.data().position;
+        }
+        self.text.borrow().draw()
+    }
+
+    fn next_update(&self) -> Option<Instant> {
+        self.text.borrow().next_update()
+    }
+}
+
+impl WidgetNew for WindowTitle {
+    type Settings = WindowTitleSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(WindowTitle {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            text: Rc::new(RefCell::new(Text::new(
+                env,
+                settings.text_settings.clone(),
+            )?)),
+
+            settings: Rc::new(settings),
+
+            env: None,
+        })
+    }
+}
+
+impl WidgetStyled for WindowTitle {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
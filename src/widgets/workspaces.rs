@@ -0,0 +1,301 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    root::Environment,
+    services::{
+        clients::{self, WorkspacesState},
+        ServiceList,
+    },
+    util::signals::SignalNames,
+};
+
+use super::{
+    containers::{
+        row::{Row, RowSettings},
+        ContainerSingle,
+    },
+    text::{Text, TextSettings},
+    Interactive, ScrollEvent, Style, Widget, WidgetData, WidgetError, WidgetList, WidgetNew,
+    WidgetStyled,
+};
+
+/// Settings of a [Workspaces] widget
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WorkspacesSettings {
+    #[serde(default, flatten)]
+    pub default_data: WidgetData,
+
+    /// Settings of workspace labels
+    #[serde(default, flatten)]
+    pub text_settings: TextSettings,
+
+    #[serde(default, flatten)]
+    pub style: Style,
+
+    /// Layout of workspace labels
+    #[serde(default)]
+    pub row_settings: RowSettings,
+
+    /// Style of the label of the focused workspace
+    #[serde(default)]
+    pub active_style: Style,
+
+    /// Style of labels of other workspaces
+    #[serde(default)]
+    pub inactive_style: Style,
+
+    /// Display workspace names instead of ids
+    #[serde(default)]
+    pub show_names: bool,
+}
+
+impl WorkspacesSettings {
+    /// Settings of a label for a workspace
+    pub fn label(&self, id: i32, name: &str, active: bool) -> TextSettings {
+        TextSettings {
+            text: match self.show_names {
+                true => name.to_string(),
+                false => id.to_string(),
+            },
+            style: match active {
+                true => self.active_style.clone(),
+                false => self.inactive_style.clone(),
+            },
+            ..self.text_settings.clone()
+        }
+    }
+}
+
+/// Label of a single workspace. Focuses the workspace on click
+struct WorkspaceLabel {
+    id: i32,
+    text: Text,
+}
+
+impl Widget for WorkspaceLabel {
+    fn name(&self) -> WidgetList {
+        WidgetList::Workspaces
+    }
+
+    fn as_interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        self.text.data_cell()
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.text.bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.text.env()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        self.text.init()
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        self.text.prepare()
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        self.text.draw()
+    }
+}
+
+impl WidgetNew for WorkspaceLabel {
+    type Settings = (i32, TextSettings);
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(WorkspaceLabel {
+            id: settings.0,
+            text: Text::new(env, settings.1)?,
+        })
+    }
+}
+
+impl Interactive for WorkspaceLabel {
+    fn click(&self, _button: u32) -> Result<(), WidgetError> {
+        if let Some(compositor) = clients::compositor() {
+            compositor
+                .switch_workspace(self.id)
+                .map_err(|e| WidgetError::Custom(e.into()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Widget displaying a row of workspaces with the focused one highlighted. Clicking a workspace
+/// focuses it. Requires [Workspaces](crate::services::clients::Workspaces) service.
+pub struct Workspaces {
+    data: RefCell<WidgetData>,
+    settings: WorkspacesSettings,
+    is_ready: RefCell<bool>,
+
+    row: RefCell<Row>,
+    state: Rc<RefCell<Option<WorkspacesState>>>,
+    changed: Rc<Cell<bool>>,
+
+    env: Option<Rc<Environment>>,
+}
+
+impl Workspaces {
+    /// Recreate workspace labels from the last received state
+    fn update_labels(&self) -> Result<(), WidgetError> {
+        let state = self.state.borrow();
+        let Some(state) = state.as_ref() else {
+            return Ok(());
+        };
+
+        let mut row = self.row.borrow_mut();
+        row.widgets_mut().clear();
+        for (id, name) in &state.workspaces {
+            let settings = self.settings.label(*id, name, *id == state.active);
+            row.create_widget(WorkspaceLabel::new, (*id, settings))?;
+        }
+
+        self.changed.set(false);
+        Ok(())
+    }
+}
+
+impl Widget for Workspaces {
+    fn name(&self) -> WidgetList {
+        WidgetList::Workspaces
+    }
+
+    fn as_styled(&self) -> Option<&dyn WidgetStyled> {
+        Some(self)
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env.clone());
+        self.row.borrow_mut().bind(env)
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::InitWithNoEnv(WidgetList::Workspaces));
+        }
+
+        let env = self.env.as_ref().unwrap();
+
+        if !env.signals.borrow().contains_key(&SignalNames::Workspaces) {
+            return Err(WidgetError::NoCorespondingSignal(
+                WidgetList::Workspaces,
+                ServiceList::Workspaces,
+            ));
+        }
+
+        let state = Rc::clone(&self.state);
+        let changed = Rc::clone(&self.changed);
+        env.connect_signals(&[SignalNames::Workspaces], move |_, data| {
+            if let Some(new_state) = data.downcast_ref::<WorkspacesState>() {
+                *state.borrow_mut() = Some(new_state.clone());
+                changed.set(true);
+            }
+        });
+
+        self.update_labels()?;
+        self.row.borrow().init()
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        if self.changed.get() {
+            self.update_labels()?;
+        }
+
+        {
+            let row = self.row.borrow();
+            row.data_mut().position = self.data.borrow().position;
+            row.prepare()?;
+
+            let row_data = row.data();
+            let mut self_data = self.data.borrow_mut();
+            self_data.width = row_data.width;
+            self_data.height = row_data.height;
+        }
+
+        self.apply_style()?;
+
+        *self.is_ready.borrow_mut() = true;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if self.env.is_none() {
+            return Err(WidgetError::DrawWithNoEnv(WidgetList::Workspaces));
+        }
+
+        if !*self.is_ready.borrow() || self.changed.get() {
+            self.prepare()?;
+        }
+        *self.is_ready.borrow_mut() = false;
+
+        self.draw_style()?;
+
+        // Bar moves the widget after it was prepared, labels are aligned to the final position
+        let row = self.row.borrow();
+        row.data_mut().position = self.data().position;
+        row.prepare()?;
+        row.draw()
+    }
+
+    fn click_at(&self, x: usize, y: usize, button: u32) -> Result<bool, WidgetError> {
+        self.row.borrow().click_at(x, y, button)
+    }
+
+    fn scroll_at(&self, x: usize, y: usize, event: ScrollEvent) -> Result<bool, WidgetError> {
+        self.row.borrow().scroll_at(x, y, event)
+    }
+}
+
+impl WidgetNew for Workspaces {
+    type Settings = WorkspacesSettings;
+
+    fn new(env: Option<Rc<Environment>>, settings: Self::Settings) -> Result<Self, WidgetError>
+    where
+        Self: Sized,
+    {
+        Ok(Workspaces {
+            data: RefCell::new(settings.default_data),
+            is_ready: RefCell::new(false),
+
+            row: RefCell::new(Row::new(env, settings.row_settings.clone())?),
+            state: Rc::new(RefCell::new(None)),
+            changed: Rc::new(Cell::new(false)),
+
+            settings,
+
+            env: None,
+        })
+    }
+}
+
+impl WidgetStyled for Workspaces {
+    fn style(&self) -> &Style {
+        &self.settings.style
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{
+        util::Color,
+        widgets::{battery::BatterySettings, Animation, Background, Style},
+    };
+
+    fn parse(value: &str) -> Result<Option<Background>, toml::de::Error> {
+        toml::from_str::<Style>(&format!("background = {value}")).map(|style| style.background)
+    }
+
+    #[test]
+    fn solid_color() {
+        let expected = Some(Background::Solid(Color::from_hex(0x1e1e2eff)));
+        assert_eq!(parse("\"#1e1e2eff\"").unwrap(), expected);
+        assert_eq!(parse("0x1e1e2eff").unwrap(), expected);
+    }
+
+    #[test]
+    fn linear_gradient() {
+        assert_eq!(
+            parse("{ from = \"#000000\", to = \"#ffffff\", angle = 90 }").unwrap(),
+            Some(Background::LinearGradient {
+                from: Color::BLACK,
+                to: Color::WHITE,
+                angle: 90.0,
+            })
+        );
+
+        let Some(Background::LinearGradient { angle, .. }) =
+            parse("{ from = \"#000000\", to = \"#ffffff\" }").unwrap()
+        else {
+            panic!("Gradient expected");
+        };
+        assert_eq!(angle, 0.0);
+    }
+
+    #[test]
+    fn gradient_in_widget_settings() {
+        let settings: BatterySettings =
+            toml::from_str("background = { from = 0x000000ff, to = 0xffffffff }").unwrap();
+        assert_eq!(
+            settings.style.background,
+            Some(Background::LinearGradient {
+                from: Color::BLACK,
+                to: Color::WHITE,
+                angle: 0.0,
+            })
+        );
+    }
+
+    #[test]
+    fn opacity() {
+        assert_eq!(Style::default().opacity, 1.0);
+        assert_eq!(toml::from_str::<Style>("").unwrap().opacity, 1.0);
+
+        let settings: BatterySettings = toml::from_str("opacity = 0.5").unwrap();
+        assert_eq!(settings.style.opacity, 0.5);
+    }
+
+    #[test]
+    fn invalid_background() {
+        assert!(parse("\"red\"").is_err());
+        assert!(parse("{ from = \"#000000\" }").is_err());
+        assert!(parse("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn gradient_colors() {
+        let horizontal = Background::LinearGradient {
+            from: Color::BLACK,
+            to: Color::WHITE,
+            angle: 0.0,
+        };
+        assert_eq!(horizontal.color_at(0, 5, 100, 10).r(), 1);
+        assert_eq!(horizontal.color_at(99, 5, 100, 10).r(), 254);
+        assert_eq!(
+            horizontal.color_at(10, 0, 100, 10),
+            horizontal.color_at(10, 9, 100, 10)
+        );
+
+        let vertical = Background::LinearGradient {
+            from: Color::BLACK,
+            to: Color::WHITE,
+            angle: 90.0,
+        };
+        assert!(vertical.color_at(0, 0, 100, 10).r() < 20);
+        assert!(vertical.color_at(0, 9, 100, 10).r() > 235);
+        assert_eq!(
+            vertical.color_at(0, 3, 100, 10),
+            vertical.color_at(99, 3, 100, 10)
+        );
+    }
+
+    #[test]
+    fn solid_color_everywhere() {
+        let solid = Background::from(Color::RED);
+        assert_eq!(solid.color_at(0, 0, 10, 10), Color::RED);
+        assert_eq!(solid.color_at(9, 9, 10, 10), Color::RED);
+    }
+
+    #[test]
+    fn round_trip() {
+        for value in [
+            "\"#1e1e2eff\"",
+            "{ from = \"#000000\", to = \"#ffffff\", angle = 45 }",
+        ] {
+            let style: Style = toml::from_str(&format!("background = {value}")).unwrap();
+            let reparsed: Style = toml::from_str(&toml::to_string(&style).unwrap()).unwrap();
+            assert_eq!(reparsed.background, style.background);
+        }
+    }
+
+    #[test]
+    fn animation() {
+        assert_eq!(Style::default().animation, Animation::None);
+
+        let settings: BatterySettings =
+            toml::from_str("animation = { type = \"pulse\", period_ms = 2000 }").unwrap();
+        assert_eq!(
+            settings.style.animation,
+            Animation::Pulse { period_ms: 2000 }
+        );
+        assert!(toml::from_str::<Style>("animation = { type = \"spin\" }").is_err());
+    }
+
+    #[test]
+    fn animation_opacity() {
+        use std::time::Duration;
+        let ms = Duration::from_millis;
+
+        assert_eq!(Animation::None.opacity_at(ms(300)), 1.0);
+
+        let blink = Animation::Blink { period_ms: 1000 };
+        assert_eq!(blink.opacity_at(ms(0)), 1.0);
+        assert_eq!(blink.opacity_at(ms(499)), 1.0);
+        assert_eq!(blink.opacity_at(ms(500)), 0.0);
+        assert_eq!(blink.opacity_at(ms(1200)), 1.0);
+
+        let pulse = Animation::Pulse { period_ms: 1000 };
+        assert!((pulse.opacity_at(ms(0)) - 1.0).abs() < 1e-6);
+        assert!(pulse.opacity_at(ms(500)).abs() < 1e-6);
+        assert!((pulse.opacity_at(ms(250)) - 0.5).abs() < 1e-6);
+
+        assert_eq!(Animation::Blink { period_ms: 0 }.opacity_at(ms(700)), 1.0);
+        assert!(blink.next_update().is_some());
+        assert!(Animation::None.next_update().is_none());
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{
+        config::widgets::bar::Bar,
+        widgets::containers::bar::{
+            BarAnchor, BarLayer, BarSettings, KeyboardMode, DEFAULT_NAMESPACE,
+        },
+    };
+
+    #[test]
+    fn anchor_defaults_to_top() {
+        let settings: BarSettings = toml::from_str("width = 1920").unwrap();
+        assert_eq!(settings.anchor, BarAnchor::Top);
+    }
+
+    #[test]
+    fn anchor_bottom() {
+        let settings: BarSettings = toml::from_str(r#"anchor = "bottom""#).unwrap();
+        assert_eq!(settings.anchor, BarAnchor::Bottom);
+    }
+
+    #[test]
+    fn exclusive_zone_includes_gap_to_windows() {
+        let settings: BarSettings = toml::from_str("margins = [10, 10, 10, 10]").unwrap();
+        assert_eq!(settings.exclusive_zone(30), 40);
+
+        let settings: BarSettings =
+            toml::from_str("anchor = \"bottom\"\nmargins = [5, 0, 10, 0]").unwrap();
+        assert_eq!(settings.exclusive_zone(30), 35);
+    }
+
+    #[test]
+    fn not_exclusive() {
+        let settings: BarSettings =
+            toml::from_str("exclusive = false\nmargins = [10, 10, 10, 10]").unwrap();
+        assert_eq!(settings.exclusive_zone(30), 0);
+    }
+
+    #[test]
+    fn layer_and_keyboard_defaults() {
+        let settings: BarSettings = toml::from_str("width = 1920").unwrap();
+        assert_eq!(settings.layer, BarLayer::Top);
+        assert_eq!(settings.keyboard_interactivity, KeyboardMode::OnDemand);
+    }
+
+    #[test]
+    fn background_bar_without_focus() {
+        let settings: BarSettings =
+            toml::from_str("layer = \"background\"\nkeyboard_interactivity = \"none\"").unwrap();
+        assert_eq!(settings.layer, BarLayer::Background);
+        assert_eq!(settings.keyboard_interactivity, KeyboardMode::None);
+    }
+
+    #[test]
+    fn shown_on_every_output_by_default() {
+        let settings: BarSettings = toml::from_str("width = 1920").unwrap();
+        assert!(settings.shown_on(Some("DP-1")));
+        assert!(settings.shown_on(None));
+    }
+
+    #[test]
+    fn shown_on_listed_outputs() {
+        let settings: BarSettings = toml::from_str(r#"outputs = ["DP-1", "HDMI-A-1"]"#).unwrap();
+        assert!(settings.shown_on(Some("DP-1")));
+        assert!(settings.shown_on(Some("HDMI-A-1")));
+        assert!(!settings.shown_on(Some("eDP-1")));
+        assert!(!settings.shown_on(None));
+    }
+
+    #[test]
+    fn namespace() {
+        assert_eq!(BarSettings::default().namespace(), DEFAULT_NAMESPACE);
+
+        let settings: BarSettings = toml::from_str(r#"namespace = "capybar-top""#).unwrap();
+        assert_eq!(settings.namespace(), "capybar-top");
+    }
+
+    #[test]
+    fn refresh_rate_defaults_without_settings() {
+        let bar: Bar = toml::from_str("").unwrap();
+        assert_eq!(bar.settings.refresh_rate_ms, 100);
+        assert_eq!(bar.settings.refresh_rate().as_millis(), 100);
+    }
+
+    #[test]
+    fn exclusive_without_settings() {
+        let bar: Bar = toml::from_str(
+            r#"
+[[left]]
+    widget = "spacer"
+    settings = { width = 5 }
+"#,
+        )
+        .unwrap();
+        assert!(bar.settings.exclusive);
+        assert_eq!(bar.settings.padding, (10, 10, 10));
+        assert_eq!(bar.settings.refresh_rate_ms, 100);
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use capybar::util::Color;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Colored {
+        color: Color,
+    }
+
+    fn parse(value: &str) -> Result<Color, toml::de::Error> {
+        toml::from_str::<Colored>(&format!("color = {value}")).map(|c| c.color)
+    }
+
+    #[test]
+    fn hex_string_with_alpha() {
+        assert_eq!(
+            parse(r##""#1e1e2eff""##).unwrap(),
+            Color::from_hex(0x1e1e2eff)
+        );
+        assert_eq!(
+            parse(r##""#1E1E2E80""##).unwrap(),
+            Color::from_hex(0x1e1e2e80)
+        );
+    }
+
+    #[test]
+    fn hex_string_without_alpha() {
+        assert_eq!(
+            parse(r##""#1e1e2e""##).unwrap(),
+            Color::from_hex(0x1e1e2eff)
+        );
+    }
+
+    #[test]
+    fn integer() {
+        assert_eq!(parse("0x1e1e2eff").unwrap(), Color::from_hex(0x1e1e2eff));
+        assert_eq!(parse("255").unwrap(), Color::from_hex(0x000000ff));
+    }
+
+    #[test]
+    fn invalid_strings() {
+        for value in [
+            r#""1e1e2eff""#,
+            r##""#1e1e2""##,
+            r##""#1e1e2eff00""##,
+            r##""#gggggggg""##,
+            r##""#""##,
+        ] {
+            let err = parse(value).unwrap_err().to_string();
+            assert!(err.contains("#rrggbb"), "{value}: {err}");
+        }
+    }
+
+    #[test]
+    fn out_of_range_integer() {
+        assert!(parse("0x1ffffffff").is_err());
+        assert!(parse("-1").is_err());
+    }
+
+    #[test]
+    fn example_config_parses() {
+        let config = capybar::config::Config::parse_toml(
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/examples/toml_config/config.toml"
+            )
+            .into(),
+        );
+        assert!(config.is_ok(), "{:?}", config.err());
+    }
+}
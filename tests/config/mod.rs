@@ -0,0 +1,6 @@
+mod background;
+mod bar;
+mod color;
+mod palette;
+mod serialize;
+mod validate;
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use capybar::{
+        config::{Config, ConfigError},
+        util::{with_palette, Color, Palette},
+        widgets::{Background, WidgetsSettingsList},
+    };
+    use serde::Deserialize;
+
+    /// Parse `content` from a temporary file. Tests run in parallel, so every one uses it's own
+    /// `name`
+    fn parse(name: &str, content: &str) -> anyhow::Result<Config> {
+        let dir = std::env::temp_dir().join(format!("capybar-palette-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path: PathBuf = dir.join(format!("{name}.toml"));
+        std::fs::write(&path, content).unwrap();
+        Config::parse_toml(path)
+    }
+
+    #[test]
+    fn names_resolve_to_colors() {
+        let config = parse(
+            "resolve",
+            r##"
+[preloaded_fonts]
+    list = []
+
+[palette]
+    base = "#1e1e2e"
+    text = "#cdd6f4"
+
+[[bar.left]]
+    widget = "text"
+    [bar.left.settings]
+    font_color = "text"
+    style = { background = "base", border = [1, "#ff0000"] }
+"##,
+        )
+        .unwrap();
+
+        assert_eq!(config.palette.len(), 2);
+        let WidgetsSettingsList::Text(settings) = &config.bar.left[0] else {
+            panic!("Widget should be text");
+        };
+        assert_eq!(settings.font_color, Color::from_hex(0xcdd6f4ff));
+        assert!(matches!(
+            settings.style.background,
+            Some(Background::Solid(color)) if color == Color::from_hex(0x1e1e2eff)
+        ));
+    }
+
+    #[test]
+    fn palette_after_bar() {
+        let config = parse(
+            "order",
+            r##"
+[preloaded_fonts]
+    list = []
+
+[[bar.left]]
+    widget = "text"
+    settings = { font_color = "accent" }
+
+[palette]
+    accent = "#f38ba8"
+"##,
+        )
+        .unwrap();
+
+        let WidgetsSettingsList::Text(settings) = &config.bar.left[0] else {
+            panic!("Widget should be text");
+        };
+        assert_eq!(settings.font_color, Color::from_hex(0xf38ba8ff));
+    }
+
+    #[test]
+    fn unknown_name_lists_palette() {
+        let err = parse(
+            "unknown",
+            r##"
+[preloaded_fonts]
+    list = []
+
+[palette]
+    base = "#1e1e2e"
+    text = "#cdd6f4"
+
+[[bar.left]]
+    widget = "text"
+    settings = { font_color = "txt" }
+"##,
+        )
+        .unwrap_err();
+
+        let message = err.to_string();
+        assert!(matches!(
+            err.downcast_ref::<ConfigError>(),
+            Some(ConfigError::Parse { .. })
+        ));
+        assert!(
+            message.contains("`bar.left[0].settings.font_color`"),
+            "{message}"
+        );
+        assert!(message.contains("`txt`"), "{message}");
+        assert!(message.contains("[base, text]"), "{message}");
+    }
+
+    #[derive(Deserialize)]
+    struct Colored {
+        color: Color,
+    }
+
+    #[test]
+    fn names_need_a_palette() {
+        let parse = || toml::from_str::<Colored>(r#"color = "base""#).map(|c| c.color);
+
+        let err = with_palette(&Palette::new(), parse).unwrap_err();
+        assert!(err.to_string().contains("palette has: []"), "{err}");
+
+        // Palette is only active while deserializing with it
+        let palette = Palette::from([("base".to_string(), Color::WHITE)]);
+        assert!(with_palette(&palette, parse).is_ok());
+        assert!(parse().is_err());
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{config::Config, util::Color, widgets::Background};
+
+    const CONFIG: &str = r##"
+[preloaded_fonts]
+    list = []
+
+[bar.settings]
+    width = 1920
+    background = "#1e1e2eff"
+    border = [1, 0x74c7ecff]
+    padding = [5, 5, 5]
+    margins = [4, 8, 0, 8]
+    [bar.settings.left_settings]
+        margin = [3, 0, 3, 3]
+
+[[bar.left]]
+    widget = "keyboard"
+    [[bar.left.settings]]
+    size = 24
+    font_color = "#f5e0dc"
+    layout_mappings = {"English (US)" = "EN"}
+    [[bar.left.settings]]
+    update_rate = 100
+
+[[bar.left]]
+    widget = "cpu"
+    [bar.left.settings]
+    size = 24
+    update_rate = 500
+    number_format = { decimals = 1, unit = "%", unit_space = true }
+
+[[bar.center]]
+    widget = "clock"
+    [bar.center.settings]
+    size = 24
+    format = "%H:%M:%S"
+
+[[bar.right]]
+    widget = "battery"
+    [bar.right.settings]
+    size = 24
+    font_color = 0xf5e0dcff
+"##;
+
+    #[test]
+    fn toml_round_trip() {
+        let config: Config = toml::from_str(CONFIG).unwrap();
+        let serialized = toml::to_string(&config).unwrap();
+
+        let reparsed: Config = toml::from_str(&serialized).unwrap();
+        assert_eq!(toml::to_string(&reparsed).unwrap(), serialized);
+
+        assert_eq!(reparsed.bar.settings.default_data.width, 1920);
+        assert_eq!(reparsed.bar.settings.margins, (4, 8, 0, 8));
+        assert_eq!(
+            reparsed.bar.settings.style.background,
+            Some(Background::Solid(Color::from_hex(0x1e1e2eff)))
+        );
+        assert_eq!(reparsed.bar.left.len(), 2);
+        assert_eq!(reparsed.bar.center.len(), 1);
+        assert_eq!(reparsed.bar.right.len(), 1);
+    }
+
+    #[test]
+    fn color_serializes_as_hex_string() {
+        let config: Config = toml::from_str(CONFIG).unwrap();
+        let serialized = toml::to_string(&config).unwrap();
+
+        assert!(serialized.contains("\"#1e1e2eff\""));
+        assert!(serialized.contains("\"#f5e0dcff\""));
+        assert!(serialized.contains("\"#74c7ecff\""));
+    }
+
+    /// Write `content` to a temporary file named `name` and return it's path
+    #[cfg(any(feature = "serde_json", feature = "serde_yaml"))]
+    fn write_config(name: &str, content: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("capybar-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn json_config() {
+        const JSON: &str = r##"{
+            "preloaded_fonts": { "list": [] },
+            "bar": {
+                "settings": { "width": 1920, "background": "#1e1e2eff" },
+                "left": [{ "widget": "clock", "settings": { "size": 24 } }],
+                "center": [],
+                "right": [{ "widget": "battery", "settings": { "font_color": 4125154559 } }]
+            }
+        }"##;
+
+        let path = write_config("config.json", JSON);
+        for config in [Config::parse_json(path.clone()), Config::parse_file(path)] {
+            let config = config.unwrap();
+            assert_eq!(config.bar.settings.default_data.width, 1920);
+            assert_eq!(
+                config.bar.settings.style.background,
+                Some(Background::Solid(Color::from_hex(0x1e1e2eff)))
+            );
+            assert_eq!(config.bar.left.len(), 1);
+            assert_eq!(config.bar.right.len(), 1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde_yaml")]
+    fn yaml_config() {
+        const YAML: &str = r##"
+preloaded_fonts:
+  list: []
+bar:
+  settings:
+    width: 1920
+    background: "#1e1e2eff"
+  left:
+    - widget: clock
+      settings:
+        size: 24
+  center: []
+  right:
+    - widget: battery
+      settings:
+        font_color: 0xf5e0dcff
+"##;
+
+        for name in ["config.yaml", "config.yml"] {
+            let path = write_config(name, YAML);
+            for config in [Config::parse_yaml(path.clone()), Config::parse_file(path)] {
+                let config = config.unwrap();
+                assert_eq!(config.bar.settings.default_data.width, 1920);
+                assert_eq!(
+                    config.bar.settings.style.background,
+                    Some(Background::Solid(Color::from_hex(0x1e1e2eff)))
+                );
+                assert_eq!(config.bar.left.len(), 1);
+                assert_eq!(config.bar.right.len(), 1);
+            }
+        }
+    }
+}
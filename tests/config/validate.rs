@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use capybar::config::{Config, ConfigError};
+
+    /// Parse `content` from a temporary file. Tests run in parallel, so every one uses it's own
+    /// `name`
+    fn parse(name: &str, content: &str) -> anyhow::Result<Config> {
+        let dir = std::env::temp_dir().join(format!("capybar-validate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path: PathBuf = dir.join(format!("{name}.toml"));
+        std::fs::write(&path, content).unwrap();
+        Config::parse_toml(path)
+    }
+
+    #[test]
+    fn parse_error_reports_key_and_location() {
+        let err = parse(
+            "unknown_widget",
+            r#"
+[preloaded_fonts]
+    list = []
+
+[[bar.left]]
+    widget = "clok"
+"#,
+        )
+        .unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("`bar.left[0].widget`"), "{message}");
+        assert!(message.contains("line 6"), "{message}");
+        assert!(matches!(
+            err.downcast_ref::<ConfigError>(),
+            Some(ConfigError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn nested_parse_error_reports_key() {
+        let err = parse(
+            "nested",
+            r#"
+[preloaded_fonts]
+    list = []
+
+[[bar.right]]
+    widget = "text"
+    [bar.right.settings]
+    size = "big"
+"#,
+        )
+        .unwrap_err();
+
+        let message = err.to_string();
+        assert!(
+            message.contains("`bar.right[0].settings.size`"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn empty_bar_is_valid() {
+        let config = parse("empty", "[preloaded_fonts]\n    list = []\n\n[bar]\n").unwrap();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn all_problems_are_reported() {
+        let config = parse(
+            "empty",
+            r#"
+[preloaded_fonts]
+    list = []
+
+[[bar.left]]
+    widget = "text"
+    [bar.left.settings]
+    fontid = 2
+
+[[bar.right]]
+    widget = "volume"
+    [[bar.right.settings]]
+    [[bar.right.settings]]
+    update_rate = 0
+"#,
+        )
+        .unwrap();
+
+        let Err(ConfigError::Invalid(problems)) = config.validate() else {
+            panic!("Config should be invalid");
+        };
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(problems[0].starts_with("bar.left[0]: font 2"));
+        assert!(problems[1].starts_with("bar.right[0]: font 0"));
+        assert!(problems[2].starts_with("bar.right[0]: update rate"));
+    }
+
+    #[test]
+    fn invalid_clock_format() {
+        let config = parse(
+            "problems",
+            r#"
+[preloaded_fonts]
+    list = []
+
+[[bar.center]]
+    widget = "clock"
+    [bar.center.settings]
+    format = "%Q"
+"#,
+        )
+        .unwrap();
+
+        let Err(ConfigError::Invalid(problems)) = config.validate() else {
+            panic!("Config should be invalid");
+        };
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].starts_with("bar.center[0]:"));
+    }
+
+    #[test]
+    fn nested_widgets_are_validated() {
+        let config = parse(
+            "nested_row",
+            r#"
+[preloaded_fonts]
+    list = []
+
+[[bar.left]]
+    widget = "row"
+    [[bar.left.settings.widgets]]
+    widget = "text"
+    settings = { fontid = 3 }
+"#,
+        )
+        .unwrap();
+
+        let Err(ConfigError::Invalid(problems)) = config.validate() else {
+            panic!("Config should be invalid");
+        };
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert!(problems[0].starts_with("bar.left[0]: font 3"));
+    }
+
+    #[test]
+    fn icon_font_is_validated() {
+        let config = parse(
+            "icon_font",
+            r#"
+[preloaded_fonts]
+    list = []
+
+[[bar.right]]
+    widget = "cpu"
+    [bar.right.settings]
+    icon_fontid = 5
+"#,
+        )
+        .unwrap();
+
+        let Err(ConfigError::Invalid(problems)) = config.validate() else {
+            panic!("Config should be invalid");
+        };
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].starts_with("bar.right[0]: font 0"));
+        assert!(problems[1].starts_with("bar.right[0]: icon font 5"));
+    }
+}
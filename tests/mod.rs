@@ -1 +1,3 @@
+mod config;
 mod util;
+mod widgets;
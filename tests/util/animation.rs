@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use capybar::util::animation::Animated;
+
+    #[test]
+    fn rests_at_initial_value() {
+        let value = Animated::new(3.0f32, Duration::from_millis(100));
+        assert_eq!(value.value(), 3.0);
+        assert_eq!(value.target(), 3.0);
+        assert!(value.is_finished());
+    }
+
+    #[test]
+    fn interpolates_linearly() {
+        let mut value = Animated::new(0.0f32, Duration::from_millis(100));
+        let start = Instant::now();
+        value.set_at(100.0, start);
+
+        assert_eq!(value.value_at(start), 0.0);
+        assert_eq!(value.value_at(start + Duration::from_millis(25)), 25.0);
+        assert_eq!(value.value_at(start + Duration::from_millis(100)), 100.0);
+        assert_eq!(value.value_at(start + Duration::from_secs(10)), 100.0);
+    }
+
+    #[test]
+    fn retarget_starts_from_current_value() {
+        let mut value = Animated::new(0.0f32, Duration::from_millis(100));
+        let start = Instant::now();
+        value.set_at(100.0, start);
+
+        let middle = start + Duration::from_millis(50);
+        value.set_at(0.0, middle);
+
+        assert_eq!(value.value_at(middle), 50.0);
+        assert_eq!(value.value_at(middle + Duration::from_millis(50)), 25.0);
+        assert_eq!(value.target(), 0.0);
+    }
+
+    #[test]
+    fn zero_duration_jumps() {
+        let mut value = Animated::new(0.0f64, Duration::ZERO);
+        let start = Instant::now();
+        value.set_at(7.0, start);
+        assert_eq!(value.value_at(start), 7.0);
+    }
+}
@@ -192,6 +192,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rgba_bytes_order() {
+        let c = Color::from_rgba(0x12, 0x34, 0x56, 0x78);
+        assert_eq!(c.to_rgba_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(Color::from_rgba_bytes([0x12, 0x34, 0x56, 0x78]), c);
+        assert_eq!(
+            Color::from_hex(0x12345678).to_rgba_bytes(),
+            [0x12, 0x34, 0x56, 0x78]
+        );
+    }
+
+    #[test]
+    fn test_rgba_bytes_independent_of_endianness() {
+        let c = Color::from_rgba(0x12, 0x34, 0x56, 0x78);
+        assert_eq!(c.to_rgba_bytes(), c.to_be_bytes());
+        assert_ne!(c.to_rgba_bytes(), c.to_le_bytes());
+        assert_eq!(Color::from_rgba_bytes(c.to_rgba_bytes()), c);
+    }
+
     #[test]
     fn test_byte_conversion_roundtrip() {
         let original = Color::from_rgba(0x12, 0x34, 0x56, 0x78);
@@ -202,4 +221,84 @@ mod tests {
         let le_bytes = original.to_le_bytes();
         assert_eq!(Color::from_le_bytes(&le_bytes), original);
     }
+
+    #[test]
+    fn test_from_hsl() {
+        assert_eq!(Color::from_hsl(0.0, 1.0, 0.5), Color::from_hex(0xff0000ff));
+        assert_eq!(
+            Color::from_hsl(120.0, 1.0, 0.5),
+            Color::from_hex(0x00ff00ff)
+        );
+        assert_eq!(
+            Color::from_hsl(240.0, 1.0, 0.5),
+            Color::from_hex(0x0000ffff)
+        );
+        assert_eq!(Color::from_hsl(0.0, 0.0, 1.0), Color::WHITE);
+        assert_eq!(Color::from_hsl(0.0, 0.0, 0.0), Color::BLACK);
+        assert_eq!(
+            Color::from_hsl(360.0, 1.0, 0.5),
+            Color::from_hsl(0.0, 1.0, 0.5)
+        );
+        assert_eq!(
+            Color::from_hsl(-120.0, 1.0, 0.5),
+            Color::from_hsl(240.0, 1.0, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_from_hsv() {
+        assert_eq!(Color::from_hsv(0.0, 1.0, 1.0), Color::from_hex(0xff0000ff));
+        assert_eq!(Color::from_hsv(60.0, 1.0, 1.0), Color::from_hex(0xffff00ff));
+        assert_eq!(Color::from_hsv(0.0, 0.0, 1.0), Color::WHITE);
+        assert_eq!(Color::from_hsv(200.0, 1.0, 0.0), Color::BLACK);
+    }
+
+    #[test]
+    fn test_hsl_clamping() {
+        assert_eq!(
+            Color::from_hsl(0.0, 2.0, 0.5),
+            Color::from_hsl(0.0, 1.0, 0.5)
+        );
+        assert_eq!(Color::from_hsl(0.0, 1.0, 1.5), Color::WHITE);
+        assert_eq!(Color::from_hsl(0.0, 1.0, -0.5), Color::BLACK);
+        assert_eq!(Color::from_hsv(0.0, -1.0, 2.0), Color::WHITE);
+    }
+
+    #[test]
+    fn test_hsl_roundtrip() {
+        for hex in [0x1e1e2eff, 0x74c7ecff, 0xf5e0dcff, 0x808080ff, 0x123456ff] {
+            let color = Color::from_hex(hex);
+            let (h, s, l) = color.to_hsl();
+            assert_eq!(Color::from_hsl(h, s, l), color);
+        }
+    }
+
+    #[test]
+    fn test_lighten_darken() {
+        let color = Color::from_hex(0x74c7ec80);
+
+        let lighter = color.lighten(0.1);
+        assert!(lighter.to_hsl().2 > color.to_hsl().2);
+        assert_eq!(lighter.a(), 0x80);
+
+        let darker = color.darken(0.1);
+        assert!(darker.to_hsl().2 < color.to_hsl().2);
+        assert_eq!(darker.a(), 0x80);
+
+        assert_eq!(color.lighten(2.0), Color::from_hex(0xffffff80));
+        assert_eq!(color.darken(2.0), Color::from_hex(0x00000080));
+        assert_eq!(color.lighten(0.0), color);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let a = Color::from_hex(0x00000000);
+        let b = Color::from_hex(0xff8040ff);
+
+        assert_eq!(Color::lerp(&a, &b, 0.0), a);
+        assert_eq!(Color::lerp(&a, &b, 1.0), b);
+        assert_eq!(Color::lerp(&a, &b, 0.5), Color::from_hex(0x80402080));
+        assert_eq!(Color::lerp(&a, &b, -1.0), a);
+        assert_eq!(Color::lerp(&a, &b, 2.0), b);
+    }
 }
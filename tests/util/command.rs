@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs, thread,
+        time::{Duration, Instant},
+    };
+
+    use capybar::util::command;
+
+    #[test]
+    fn runs_in_background() {
+        let path = std::env::temp_dir().join(format!("capybar-command-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        command::spawn(&format!("sleep 0.1; touch '{}'", path.display())).unwrap();
+        assert!(!path.exists());
+
+        let start = Instant::now();
+        while !path.exists() {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "command did not run"
+            );
+            thread::sleep(Duration::from_millis(20));
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,470 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{
+        util::{
+            drawer::{Canvas, Drawer, DrawerError},
+            fonts::FontsMap,
+            Color,
+        },
+        widgets::{Position, WidgetData, WidgetError},
+    };
+    use fontdue::layout::{CoordinateSystem, Layout, TextStyle};
+
+    const WIDTH: usize = 4;
+    const HEIGHT: usize = 2;
+
+    fn buffer() -> Vec<u8> {
+        vec![0; WIDTH * HEIGHT * 4]
+    }
+
+    #[test]
+    fn untouched_pixels_stay_transparent() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        canvas.draw_pixel(&WidgetData::default(), (0, 0), Color::RED);
+
+        for x in 0..WIDTH {
+            for y in 0..HEIGHT {
+                if (x, y) != (0, 0) {
+                    assert_eq!(canvas.pixel(x, y), Some([0, 0, 0, 0]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clear_resets_to_transparent() {
+        let mut bytes = vec![0xFF; WIDTH * HEIGHT * 4];
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        canvas.clear();
+        assert!(bytes.iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn half_alpha_over_empty_is_premultiplied() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        canvas.draw_pixel(
+            &WidgetData::default(),
+            (1, 1),
+            Color::from_rgba(200, 100, 50, 128),
+        );
+
+        assert_eq!(canvas.pixel(1, 1), Some([25, 50, 100, 128]));
+    }
+
+    #[test]
+    fn half_alpha_over_opaque() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        let data = WidgetData::default();
+        canvas.draw_pixel(&data, (0, 0), Color::WHITE);
+        canvas.draw_pixel(&data, (0, 0), Color::from_rgba(200, 100, 50, 128));
+
+        assert_eq!(canvas.pixel(0, 0), Some([152, 177, 227, 255]));
+    }
+
+    #[test]
+    fn opaque_is_stored_as_bgra() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        let data = WidgetData::with_position(Position(1, 0));
+        canvas.draw_pixel(&data, (2, 1), Color::from_rgba(0x12, 0x34, 0x56, 0xFF));
+
+        assert_eq!(canvas.pixel(3, 1), Some([0x56, 0x34, 0x12, 0xFF]));
+    }
+
+    #[test]
+    fn transparent_color_is_noop() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        canvas.draw_pixel(&WidgetData::default(), (0, 0), Color::NONE);
+        assert_eq!(canvas.pixel(0, 0), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn opacity_scales_alpha() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        canvas.set_opacity(0.5);
+        canvas.draw_pixel(
+            &WidgetData::default(),
+            (1, 1),
+            Color::from_rgba(200, 100, 50, 255),
+        );
+
+        // Same as drawing the color with half alpha
+        assert_eq!(canvas.pixel(1, 1), Some([25, 50, 100, 128]));
+    }
+
+    #[test]
+    fn opacity_is_clamped() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        canvas.set_opacity(2.0);
+        assert_eq!(canvas.opacity(), 1.0);
+
+        canvas.set_opacity(-1.0);
+        canvas.draw_pixel(&WidgetData::default(), (0, 0), Color::RED);
+        assert_eq!(canvas.pixel(0, 0), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn out_of_bounds_is_ignored() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        canvas.draw_pixel(&WidgetData::default(), (WIDTH, 0), Color::RED);
+        canvas.draw_pixel(&WidgetData::default(), (0, HEIGHT), Color::RED);
+        assert_eq!(canvas.pixel(WIDTH, 0), None);
+        assert!(bytes.iter().all(|b| *b == 0));
+    }
+
+    fn sans() -> (FontsMap, usize) {
+        let fonts = FontsMap::new();
+        let id = fonts.add_font_by_name("DejaVu Sans").unwrap();
+        (fonts, id)
+    }
+
+    #[test]
+    fn draw_text_with_blanks() {
+        let (fonts, fontid) = sans();
+        let fonts = fonts.fonts_vec();
+        let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+        layout.append(&fonts, &TextStyle::new("a b\tc", 16.0, fontid));
+
+        let mut bytes = vec![0; 64 * 32 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 64, 32);
+        for glyph in layout.glyphs() {
+            canvas.draw_glyph(&WidgetData::default(), glyph, &fonts[fontid], Color::WHITE);
+        }
+
+        let visible: Vec<_> = layout
+            .glyphs()
+            .iter()
+            .filter(|g| !g.parent.is_whitespace())
+            .map(|g| g.x)
+            .collect();
+        assert_eq!(visible.len(), 3);
+        assert!(visible.windows(2).all(|w| w[0] < w[1]));
+        assert!(bytes.iter().any(|b| *b != 0));
+    }
+
+    #[test]
+    fn blank_glyphs_draw_nothing() {
+        let (fonts, fontid) = sans();
+        let fonts = fonts.fonts_vec();
+        let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+        layout.append(&fonts, &TextStyle::new(" \t ", 16.0, fontid));
+
+        let mut bytes = vec![0; 64 * 32 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 64, 32);
+        for glyph in layout.glyphs() {
+            canvas.draw_glyph(&WidgetData::default(), glyph, &fonts[fontid], Color::WHITE);
+        }
+
+        assert!(bytes.iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn faded_text_is_translucent() {
+        let (fonts, fontid) = sans();
+        let fonts = fonts.fonts_vec();
+        let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+        layout.append(&fonts, &TextStyle::new("l", 16.0, fontid));
+
+        let mut bytes = vec![0; 64 * 32 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 64, 32);
+        canvas.set_opacity(0.25);
+        for glyph in layout.glyphs() {
+            canvas.draw_glyph(&WidgetData::default(), glyph, &fonts[fontid], Color::WHITE);
+        }
+
+        let alpha = bytes.chunks(4).map(|p| p[3]).max().unwrap();
+        assert!(alpha > 0 && alpha <= 64, "{alpha}");
+    }
+
+    fn filled(canvas: &Canvas, x: usize, y: usize) -> bool {
+        canvas.pixel(x, y).is_some_and(|p| p[3] != 0)
+    }
+
+    #[test]
+    fn rounded_rect_zero_radius_fills_everything() {
+        let mut bytes = vec![0; 10 * 10 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 10, 10);
+        canvas.draw_rounded_rect(&WidgetData::default(), 10, 10, 0, Color::RED);
+
+        assert!(bytes.chunks(4).all(|p| p == [0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn rounded_rect_skips_corners() {
+        let mut bytes = vec![0; 10 * 10 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 10, 10);
+        canvas.draw_rounded_rect(&WidgetData::default(), 10, 10, 4, Color::RED);
+
+        for (x, y) in [(0, 0), (9, 0), (0, 9), (9, 9)] {
+            assert!(!filled(&canvas, x, y), "corner ({x}, {y}) is filled");
+        }
+        for (x, y) in [(5, 5), (5, 0), (0, 5), (9, 5), (5, 9), (2, 2)] {
+            assert!(filled(&canvas, x, y), "({x}, {y}) is not filled");
+        }
+    }
+
+    #[test]
+    fn rounded_border_leaves_inside_empty() {
+        let mut bytes = vec![0; 10 * 10 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 10, 10);
+        canvas.draw_rounded_border(&WidgetData::default(), 10, 10, 4, 1, Color::RED);
+
+        assert!(!filled(&canvas, 0, 0));
+        assert!(filled(&canvas, 5, 0));
+        assert!(filled(&canvas, 0, 5));
+        assert!(!filled(&canvas, 5, 5));
+        assert!(!filled(&canvas, 1, 5));
+    }
+
+    fn alpha(canvas: &Canvas, x: usize, y: usize) -> u8 {
+        canvas.pixel(x, y).unwrap()[3]
+    }
+
+    #[test]
+    fn rect_filled_respects_position() {
+        let mut bytes = vec![0; 10 * 10 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 10, 10);
+        let data = WidgetData::with_position(Position(2, 1));
+        canvas.draw_rect_filled(&data, (1, 1), 3, 2, Color::RED);
+
+        for x in 0..10 {
+            for y in 0..10 {
+                let inside = (3..6).contains(&x) && (2..4).contains(&y);
+                assert_eq!(filled(&canvas, x, y), inside, "({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn rect_outline_blends_corners_once() {
+        let mut bytes = vec![0; 10 * 10 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 10, 10);
+        let color = Color::from_rgba(255, 0, 0, 128);
+        canvas.draw_rect_outline(&WidgetData::default(), (0, 0), 6, 5, 1, color);
+
+        for (x, y) in [(0, 0), (5, 0), (0, 4), (5, 4), (3, 0), (0, 2)] {
+            assert_eq!(alpha(&canvas, x, y), 128, "({x}, {y})");
+        }
+        assert!(!filled(&canvas, 2, 2));
+        assert!(!filled(&canvas, 6, 0));
+    }
+
+    #[test]
+    fn horizontal_line() {
+        let mut bytes = vec![0; 10 * 10 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 10, 10);
+        canvas.draw_line(&WidgetData::default(), (1, 4), (8, 4), Color::RED, 1);
+
+        for x in 0..10 {
+            for y in 0..10 {
+                let on_line = (1..=8).contains(&x) && y == 4;
+                assert_eq!(filled(&canvas, x, y), on_line, "({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn diagonal_line_is_connected() {
+        let mut bytes = vec![0; 10 * 10 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 10, 10);
+        canvas.draw_line(&WidgetData::default(), (0, 0), (9, 4), Color::RED, 1);
+
+        for x in 0..10 {
+            assert!(
+                (0..10).any(|y| filled(&canvas, x, y)),
+                "column {x} is empty"
+            );
+        }
+        assert!(!filled(&canvas, 0, 9));
+    }
+
+    #[test]
+    fn antialiased_line_has_soft_edges() {
+        let mut bytes = vec![0; 10 * 10 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 10, 10);
+        canvas.draw_line_antialiased(&WidgetData::default(), (0, 0), (9, 3), Color::RED, 1);
+
+        assert_eq!(alpha(&canvas, 0, 0), 255);
+        let partial = bytes.chunks(4).filter(|p| p[3] != 0 && p[3] != 255);
+        assert!(partial.count() > 0);
+    }
+
+    #[test]
+    fn circle() {
+        let mut bytes = vec![0; 10 * 10 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 10, 10);
+        canvas.draw_circle(&WidgetData::default(), (5, 5), 3.0, Color::RED);
+
+        assert_eq!(alpha(&canvas, 5, 5), 255);
+        assert_eq!(alpha(&canvas, 7, 5), 255);
+        // Centers of edge pixels lie on the circle, so they are half covered
+        assert_eq!(alpha(&canvas, 8, 5), 128);
+        assert_eq!(alpha(&canvas, 5, 2), 128);
+        assert!(!filled(&canvas, 9, 5));
+        assert!(!filled(&canvas, 8, 8));
+    }
+
+    #[test]
+    fn image_is_blended_row_by_row() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        let data = WidgetData::with_position(Position(1, 0));
+        #[rustfmt::skip]
+        let rgba = [
+            255, 0, 0, 255,  0, 0, 255, 255,
+            0, 0, 0, 0,      200, 100, 50, 128,
+        ];
+        canvas.draw_image(&data, &rgba, 2, 2);
+
+        assert_eq!(canvas.pixel(1, 0), Some([0, 0, 255, 255]));
+        assert_eq!(canvas.pixel(2, 0), Some([255, 0, 0, 255]));
+        assert_eq!(canvas.pixel(1, 1), Some([0, 0, 0, 0]));
+        assert_eq!(canvas.pixel(2, 1), Some([25, 50, 100, 128]));
+    }
+
+    #[test]
+    fn headless_renders_into_memory() {
+        let mut drawer = Drawer::headless(WIDTH as i32, HEIGHT as i32);
+        assert!(drawer.is_headless());
+
+        drawer
+            .draw_pixel(&WidgetData::default(), (1, 1), Color::RED)
+            .unwrap();
+        assert_eq!(drawer.canvas().unwrap().pixel(1, 1), Some([0, 0, 255, 255]));
+
+        let bytes = drawer.bytes().unwrap();
+        assert_eq!(bytes.len(), WIDTH * HEIGHT * 4);
+        assert_eq!(bytes.iter().filter(|b| **b != 0).count(), 2);
+
+        drawer.resize(2, 2);
+        assert_eq!((drawer.width(), drawer.height()), (2, 2));
+        assert!(drawer.bytes().unwrap().iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn drawer_errors_reach_widgets() {
+        let err = WidgetError::from(DrawerError::BufferBusy);
+        assert!(matches!(err, WidgetError::Drawer(DrawerError::BufferBusy)));
+        assert_eq!(err.to_string(), DrawerError::BufferBusy.to_string());
+    }
+
+    #[test]
+    fn mispositioned_shapes_are_clipped() {
+        let mut bytes = buffer();
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        let far = WidgetData {
+            position: Position(usize::MAX, 1),
+            ..WidgetData::default()
+        };
+        canvas.draw_pixel(&far, (1, 0), Color::RED);
+        canvas.draw_rect_filled(&far, (0, 0), usize::MAX, usize::MAX, Color::RED);
+        canvas.draw_circle(&far, (usize::MAX, 0), 3.0, Color::RED);
+        canvas.draw_line(&far, (0, 0), (usize::MAX, usize::MAX), Color::RED, 2);
+        canvas.draw_image(&far, &[255; 16], 0, 4);
+        assert!(bytes.iter().all(|b| *b == 0));
+
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        let data = WidgetData::default();
+        canvas.draw_rect_filled(&data, (2, 1), usize::MAX, usize::MAX, Color::RED);
+        canvas.draw_rect_outline(&data, (3, 0), usize::MAX, usize::MAX, 1, Color::RED);
+        assert_eq!(canvas.pixel(3, 1), Some([0, 0, 255, 255]));
+        assert_eq!(canvas.pixel(1, 1), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn short_buffer_is_out_of_bounds() {
+        let mut bytes = vec![0; WIDTH * HEIGHT * 4 - 1];
+        let err = Canvas::try_new(&mut bytes, WIDTH, HEIGHT).err().unwrap();
+        assert!(matches!(err, DrawerError::OutOfBounds(31, 32)), "{err}");
+
+        // Missing pixels are skipped by an unchecked canvas
+        let mut canvas = Canvas::new(&mut bytes, WIDTH, HEIGHT);
+        canvas.draw_pixel(&WidgetData::default(), (WIDTH - 1, HEIGHT - 1), Color::RED);
+        assert_eq!(canvas.pixel(WIDTH - 1, HEIGHT - 1), None);
+    }
+
+    #[test]
+    fn scaled_pixel_covers_block() {
+        let mut bytes = vec![0; 8 * 8 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 8, 8);
+        canvas.set_scale(2);
+        canvas.draw_pixel(&WidgetData::default(), (1, 1), Color::RED);
+
+        for x in 0..8 {
+            for y in 0..8 {
+                let inside = (2..4).contains(&x) && (2..4).contains(&y);
+                assert_eq!(filled(&canvas, x, y), inside, "pixel ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn scaled_shapes_are_clipped_logically() {
+        let mut bytes = vec![0; 8 * 8 * 4];
+        let mut canvas = Canvas::new(&mut bytes, 8, 8);
+        canvas.set_scale(2);
+        canvas.draw_rect_filled(&WidgetData::default(), (0, 0), 100, 100, Color::RED);
+
+        assert!(bytes.chunks(4).all(|p| p == [0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn scaled_glyphs_are_rasterized_larger() {
+        let (fonts, fontid) = sans();
+        let fonts = fonts.fonts_vec();
+        let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+        layout.append(&fonts, &TextStyle::new("H", 16.0, fontid));
+
+        let covered = |scale: usize| {
+            let mut bytes = vec![0; 64 * 64 * 4];
+            let mut canvas = Canvas::new(&mut bytes, 64, 64);
+            canvas.set_scale(scale);
+            for glyph in layout.glyphs() {
+                canvas.draw_glyph(&WidgetData::default(), glyph, &fonts[fontid], Color::WHITE);
+            }
+
+            let mut columns = (usize::MAX, 0);
+            for x in 0..64 {
+                if (0..64).any(|y| filled(&canvas, x, y)) {
+                    columns = (columns.0.min(x), columns.1.max(x));
+                }
+            }
+            columns
+        };
+
+        let (left, right) = covered(1);
+        let (scaled_left, scaled_right) = covered(2);
+        let (width, scaled_width) = (right - left + 1, scaled_right - scaled_left + 1);
+        assert!(
+            scaled_width.abs_diff(width * 2) <= 2,
+            "{width} {scaled_width}"
+        );
+        assert!(scaled_left.abs_diff(left * 2) <= 2, "{left} {scaled_left}");
+    }
+
+    #[test]
+    fn headless_scale_multiplies_buffer() {
+        let mut drawer = Drawer::headless(WIDTH as i32, HEIGHT as i32);
+        drawer.set_scale(2);
+
+        assert_eq!(drawer.scale(), 2);
+        assert_eq!((drawer.width(), drawer.height()), (WIDTH, HEIGHT));
+        assert_eq!(drawer.bytes().unwrap().len(), WIDTH * HEIGHT * 4 * 4);
+
+        drawer
+            .draw_rect_filled(&WidgetData::default(), (0, 0), WIDTH, HEIGHT, Color::RED)
+            .unwrap();
+        assert!(drawer
+            .bytes()
+            .unwrap()
+            .chunks(4)
+            .all(|p| p == [0, 0, 255, 255]));
+    }
+}
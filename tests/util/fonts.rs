@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use capybar::{
+        config::util::font::{Font, PreloadedFonts},
+        util::fonts::{FontsError, FontsMap},
+    };
+
+    fn dejavu_path() -> PathBuf {
+        fontconfig::Fontconfig::new()
+            .unwrap()
+            .find("DejaVu Sans", None)
+            .unwrap()
+            .path
+    }
+
+    #[test]
+    fn add_by_path() {
+        let path = dejavu_path();
+        let fonts = FontsMap::new();
+        let id = fonts.add_font_by_path(&path).unwrap();
+
+        assert_eq!(id, 0);
+        assert_eq!(fonts.len(), 1);
+        assert_eq!(fonts.id(&path.display().to_string()), Some(id));
+        assert_eq!(fonts.add_font_by_path(&path).unwrap(), id);
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = FontsMap::new()
+            .add_font_by_path(Path::new("/nonexistent/font.ttf"))
+            .unwrap_err();
+        assert!(matches!(err, FontsError::IO(_)));
+    }
+
+    #[test]
+    fn invalid_font() {
+        let err = FontsMap::new()
+            .add_font_by_path(Path::new("Cargo.toml"))
+            .unwrap_err();
+        assert!(matches!(err, FontsError::InvalidFont(..)), "{err}");
+    }
+
+    #[test]
+    fn preload_name_and_path() {
+        let path = dejavu_path();
+        let content = format!(
+            "list = [\"DejaVu Sans\", {{ path = \"{}\" }}]",
+            path.display()
+        );
+        let preloaded: PreloadedFonts = toml::from_str(&content).unwrap();
+
+        assert!(matches!(&preloaded.list[0], Font::Name(name) if name == "DejaVu Sans"));
+        assert!(matches!(&preloaded.list[1], Font::Path { path: p } if *p == path));
+        assert_eq!(
+            toml::to_string(&preloaded).unwrap(),
+            toml::to_string(&toml::from_str::<toml::Table>(&content).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn preloaded_in_listed_order() {
+        let path = dejavu_path();
+        let content = format!(
+            "list = [{{ path = \"{}\" }}, \"DejaVu Sans Mono\"]",
+            path.display()
+        );
+        let preloaded: PreloadedFonts = toml::from_str(&content).unwrap();
+
+        let fonts = FontsMap::new();
+        preloaded.load(&fonts).unwrap();
+        assert_eq!(fonts.id(&path.display().to_string()), Some(0));
+        assert_eq!(fonts.id("DejaVu Sans Mono"), Some(1));
+
+        // Parsing a config does not load anything
+        assert!(FontsMap::new().is_empty());
+    }
+
+    #[test]
+    fn replace_returns_old_fonts() {
+        let fonts = FontsMap::new();
+        fonts.add_font_by_name("DejaVu Sans").unwrap();
+
+        let other = FontsMap::new();
+        other.add_font_by_name("DejaVu Sans Mono").unwrap();
+        other.add_font_by_name("DejaVu Sans").unwrap();
+
+        let old = fonts.replace(other);
+        assert_eq!(fonts.len(), 2);
+        assert_eq!(fonts.id("DejaVu Sans"), Some(1));
+        assert_eq!(old.id("DejaVu Sans"), Some(0));
+    }
+}
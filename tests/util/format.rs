@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use capybar::util::format::NumberFormat;
+
+    fn format(decimals: usize, unit: &str, unit_space: bool) -> NumberFormat {
+        NumberFormat {
+            decimals,
+            unit: unit.to_string(),
+            unit_space,
+        }
+    }
+
+    #[test]
+    fn rounding_zero_decimals() {
+        let f = format(0, "%", false);
+        assert_eq!(f.format(42.0), "42%");
+        assert_eq!(f.format(42.4), "42%");
+        assert_eq!(f.format(42.5), "43%");
+        assert_eq!(f.format(99.99), "100%");
+        assert_eq!(f.format(0.0), "0%");
+    }
+
+    #[test]
+    fn rounding_one_decimal() {
+        let f = format(1, "%", false);
+        assert_eq!(f.format(42.0), "42.0%");
+        assert_eq!(f.format(42.04), "42.0%");
+        assert_eq!(f.format(42.05), "42.1%");
+        assert_eq!(f.format(42.96), "43.0%");
+    }
+
+    #[test]
+    fn rounding_two_decimals() {
+        let f = format(2, "%", false);
+        assert_eq!(f.format(42.0), "42.00%");
+        assert_eq!(f.format(42.004), "42.00%");
+        assert_eq!(f.format(42.125), "42.13%");
+        assert_eq!(f.format(0.999), "1.00%");
+    }
+
+    #[test]
+    fn unit_space() {
+        assert_eq!(format(1, "%", true).format(42.0), "42.0 %");
+        assert_eq!(format(0, "GiB", true).format(3.2), "3 GiB");
+        assert_eq!(format(0, "", true).format(3.2), "3");
+    }
+
+    #[test]
+    fn percent_default() {
+        assert_eq!(NumberFormat::percent().format(41.6), "42%");
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use std::{os::unix::net::UnixListener, path::PathBuf, thread};
+
+    use capybar::util::ipc::{self, Command, IpcError, Server};
+
+    fn socket(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("capybar-test-{}-{name}.sock", std::process::id()))
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!("quit".parse(), Ok(Command::Quit));
+        assert_eq!("  reload \n".parse(), Ok(Command::Reload));
+        assert_eq!(Command::Reload.to_string().parse(), Ok(Command::Reload));
+        assert_eq!("dump".parse(), Ok(Command::Dump));
+
+        assert_eq!("".parse::<Command>(), Err(IpcError::Empty));
+        assert_eq!(
+            "toggle".parse::<Command>(),
+            Err(IpcError::UnknownCommand("toggle".to_string()))
+        );
+        assert_eq!(
+            "quit now".parse::<Command>(),
+            Err(IpcError::UnexpectedArguments("quit".to_string()))
+        );
+    }
+
+    #[test]
+    fn command_round_trip() {
+        let path = socket("round-trip");
+        let mut server = Server::bind(&path).unwrap();
+
+        let client_path = path.clone();
+        let client = thread::spawn(move || {
+            (
+                ipc::send(&client_path, &Command::Reload),
+                ipc::send(&client_path, &Command::Quit),
+                ipc::send(&client_path, &Command::Dump),
+            )
+        });
+
+        let mut received = Vec::new();
+        while !client.is_finished() {
+            server.handle(|command| match command {
+                Command::Reload => Err("no config".to_string()),
+                Command::Quit => {
+                    received.push(command);
+                    Ok(String::new())
+                }
+                Command::Dump => Ok("Bar (0, 0) 100x20\n  Row (0, 0) 40x20\n".to_string()),
+            });
+        }
+
+        let (reload, quit, dump) = client.join().unwrap();
+        assert_eq!(reload.unwrap_err().to_string(), "no config");
+        assert_eq!(quit.unwrap(), "");
+        assert_eq!(dump.unwrap(), "Bar (0, 0) 100x20\n  Row (0, 0) 40x20");
+        assert_eq!(received, vec![Command::Quit]);
+
+        drop(server);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn stale_socket_replaced() {
+        let path = socket("stale");
+        // Leaves the file behind like a bar that was killed
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let server = Server::bind(&path).unwrap();
+        assert!(Server::bind(&path).is_err());
+        drop(server);
+        assert!(!path.exists());
+    }
+}
@@ -1,2 +1,14 @@
+mod animation;
 mod color;
+mod command;
+mod drawer;
+mod fonts;
+mod format;
+mod ipc;
+mod resize;
+mod schedule;
+mod sighup;
 mod signals;
+mod terminate;
+mod update_rate;
+mod visibility;
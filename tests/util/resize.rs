@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use capybar::util::resize::ResizeDebounce;
+
+    const DELAY: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn same_size_is_kept() {
+        let mut debounce = ResizeDebounce::new(DELAY);
+        assert_eq!(
+            debounce.next_size((100, 20), (100, 20), Instant::now()),
+            None
+        );
+        assert_eq!(debounce.deadline(), None);
+    }
+
+    #[test]
+    fn growth_is_immediate() {
+        let mut debounce = ResizeDebounce::new(DELAY);
+        let now = Instant::now();
+        assert_eq!(
+            debounce.next_size((100, 20), (101, 24), now),
+            Some((101, 24))
+        );
+        assert_eq!(debounce.deadline(), None);
+    }
+
+    #[test]
+    fn jitter_does_not_shrink() {
+        let mut debounce = ResizeDebounce::new(DELAY);
+        let start = Instant::now();
+
+        for frame in 0..10 {
+            let now = start + Duration::from_millis(frame * 300);
+            let wanted = match frame % 2 {
+                0 => (99, 20),
+                _ => (100, 20),
+            };
+            assert_eq!(debounce.next_size((100, 20), wanted, now), None);
+        }
+    }
+
+    #[test]
+    fn shrink_after_delay() {
+        let mut debounce = ResizeDebounce::new(DELAY);
+        let start = Instant::now();
+
+        assert_eq!(debounce.next_size((100, 20), (80, 20), start), None);
+        assert_eq!(debounce.deadline(), Some(start + DELAY));
+        assert_eq!(
+            debounce.next_size((100, 20), (80, 20), start + DELAY / 2),
+            None
+        );
+        assert_eq!(
+            debounce.next_size((100, 20), (80, 20), start + DELAY),
+            Some((80, 20))
+        );
+        assert_eq!(debounce.deadline(), None);
+    }
+
+    #[test]
+    fn growing_dimension_is_not_delayed() {
+        let mut debounce = ResizeDebounce::new(DELAY);
+        let start = Instant::now();
+
+        // Width shrinks later, height grows right away
+        assert_eq!(
+            debounce.next_size((100, 20), (80, 30), start),
+            Some((100, 30))
+        );
+        assert_eq!(
+            debounce.next_size((100, 30), (80, 30), start + DELAY),
+            Some((80, 30))
+        );
+    }
+}
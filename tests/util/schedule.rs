@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use capybar::util::schedule::{earliest, instant_after, until_next};
+    use chrono::{Local, TimeDelta, TimeZone, Timelike};
+
+    #[test]
+    fn until_next_minute() {
+        let now = Local.with_ymd_and_hms(2025, 1, 1, 12, 30, 45).unwrap();
+        assert_eq!(
+            until_next(now, TimeDelta::minutes(1)),
+            Duration::from_secs(15)
+        );
+    }
+
+    #[test]
+    fn until_next_second() {
+        let now = Local
+            .with_ymd_and_hms(2025, 1, 1, 12, 30, 45)
+            .unwrap()
+            .with_nanosecond(250_000_000)
+            .unwrap();
+        assert_eq!(
+            until_next(now, TimeDelta::seconds(1)),
+            Duration::from_millis(750)
+        );
+    }
+
+    #[test]
+    fn until_next_on_boundary() {
+        let now = Local.with_ymd_and_hms(2025, 1, 1, 12, 31, 0).unwrap();
+        assert_eq!(
+            until_next(now, TimeDelta::minutes(1)),
+            Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn until_next_with_empty_step() {
+        let now = Local.with_ymd_and_hms(2025, 1, 1, 12, 30, 45).unwrap();
+        assert_eq!(until_next(now, TimeDelta::zero()), Duration::ZERO);
+    }
+
+    #[test]
+    fn instant_after_past_is_now() {
+        let before = Instant::now();
+        let deadline = instant_after(Local::now() - TimeDelta::seconds(10), TimeDelta::seconds(1));
+        assert!(deadline >= before);
+        assert!(deadline <= Instant::now());
+    }
+
+    #[test]
+    fn instant_after_future() {
+        let deadline = instant_after(Local::now(), TimeDelta::seconds(10));
+        assert!(deadline > Instant::now() + Duration::from_secs(9));
+    }
+
+    #[test]
+    fn earliest_deadline() {
+        let now = Instant::now();
+        let later = now + Duration::from_secs(1);
+
+        assert_eq!(earliest(Some(now), Some(later)), Some(now));
+        assert_eq!(earliest(None, Some(later)), Some(later));
+        assert_eq!(earliest(Some(now), None), Some(now));
+        assert_eq!(earliest(None, None), None);
+    }
+}
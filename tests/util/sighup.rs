@@ -0,0 +1,14 @@
+#[cfg(test)]
+mod tests {
+    use capybar::util::sighup;
+
+    #[test]
+    fn flag_set_once_per_signal() {
+        sighup::install().unwrap();
+        assert!(!sighup::take());
+
+        unsafe { libc::raise(libc::SIGHUP) };
+        assert!(sighup::take());
+        assert!(!sighup::take());
+    }
+}
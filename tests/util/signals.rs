@@ -3,7 +3,11 @@ mod tests {
     use std::cell::{Cell, RefCell};
     use std::rc::Rc;
 
-    use capybar::util::signals::Signal;
+    use capybar::{
+        root::Environment,
+        util::signals::{Signal, SignalNames, TypedSignal},
+    };
+    use serde::Deserialize;
 
     #[test]
     fn initial_state() {
@@ -77,17 +81,46 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "already borrowed")]
-    fn recursive_emit_panics() {
-        let signal = Signal::new();
-        let signal_clone = Rc::new(signal);
-        let weak = Rc::downgrade(&signal_clone);
-        signal_clone.connect(move |_| {
+    fn recursive_emit() {
+        let signal = Rc::new(Signal::new());
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        let weak = Rc::downgrade(&signal);
+        let received_clone = Rc::clone(&received);
+        signal.connect(move |data| {
+            let Some(value) = data.downcast_ref::<&str>() else {
+                return;
+            };
+            received_clone.borrow_mut().push(*value);
+            if *value == "trigger" {
+                if let Some(s) = weak.upgrade() {
+                    s.emit(&"recursive");
+                }
+            }
+        });
+        signal.emit(&"trigger");
+
+        assert_eq!(*received.borrow(), vec!["trigger", "recursive"]);
+        assert_eq!(signal.get_last_value_cloned::<&str>(), Some("recursive"));
+    }
+
+    #[test]
+    fn disconnect_inside_callback() {
+        let signal = Rc::new(Signal::new());
+        let calls = Rc::new(Cell::new(0));
+
+        let weak = Rc::downgrade(&signal);
+        let calls_clone = Rc::clone(&calls);
+        signal.connect(move |_| {
+            calls_clone.set(calls_clone.get() + 1);
             if let Some(s) = weak.upgrade() {
-                s.emit(&"recursive");
+                s.disconnect_all();
             }
         });
-        signal_clone.emit(&"trigger");
+        signal.emit(&1i32);
+        signal.emit(&2i32);
+
+        assert_eq!(calls.get(), 1);
     }
 
     #[test]
@@ -261,4 +294,190 @@ mod tests {
         assert_eq!(*int_state.borrow(), 60);
         assert_eq!(*string_state.borrow(), "test");
     }
+
+    #[test]
+    fn signal_names_from_config() {
+        #[derive(Deserialize)]
+        struct Settings {
+            signals: Vec<SignalNames>,
+        }
+
+        let settings: Settings =
+            toml::from_str(r#"signals = ["keyboard", "modifiers", { custom = "volume" }]"#)
+                .unwrap();
+        assert_eq!(
+            settings.signals,
+            vec![
+                SignalNames::Keyboard,
+                SignalNames::Modifiers,
+                SignalNames::Custom("volume".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn disconnected_callback_does_not_fire() {
+        let signal = Signal::new();
+        let kept = Rc::new(Cell::new(0));
+        let removed = Rc::new(Cell::new(0));
+
+        let kept_clone = Rc::clone(&kept);
+        signal.connect(move |_| kept_clone.set(kept_clone.get() + 1));
+        let removed_clone = Rc::clone(&removed);
+        let id = signal.connect(move |_| removed_clone.set(removed_clone.get() + 1));
+
+        signal.emit(&1i32);
+        signal.disconnect(id);
+        signal.emit(&2i32);
+
+        assert_eq!(kept.get(), 2);
+        assert_eq!(removed.get(), 1);
+    }
+
+    #[test]
+    fn connection_ids_increase() {
+        let signal = Signal::new();
+        let first = signal.connect(|_| {});
+        let second = signal.connect(|_| {});
+        signal.disconnect(first);
+        let third = signal.connect(|_| {});
+
+        assert!(first < second && second < third);
+    }
+
+    #[test]
+    fn disconnect_all_keeps_last_value() {
+        let signal = Signal::new();
+        let calls = Rc::new(Cell::new(0));
+
+        let calls_clone = Rc::clone(&calls);
+        signal.connect(move |_| calls_clone.set(calls_clone.get() + 1));
+        signal.emit(&1i32);
+        signal.disconnect_all();
+        signal.emit(&2i32);
+
+        assert_eq!(calls.get(), 1);
+        assert_eq!(signal.get_last_value_cloned::<i32>(), Some(2));
+    }
+
+    #[test]
+    fn typed_signal() {
+        let signal = Signal::new();
+        let typed = TypedSignal::<String>::new(&signal);
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        let received_clone = Rc::clone(&received);
+        typed.connect(move |value| received_clone.borrow_mut().push(value.clone()));
+        typed.emit(&"first".to_string());
+        signal.emit(&"second".to_string());
+
+        assert_eq!(*received.borrow(), vec!["first", "second"]);
+        assert_eq!(typed.last_value().as_deref(), Some("second"));
+    }
+
+    #[test]
+    #[should_panic(expected = "received a value of another type")]
+    #[cfg(debug_assertions)]
+    fn typed_signal_type_mismatch() {
+        let signal = Signal::new();
+        signal.typed::<String>().connect(|_| {});
+        signal.emit(&1i32);
+    }
+
+    #[test]
+    fn tracked_connections_are_disconnected_alone() {
+        let env = Environment::headless(1, 1);
+        let volume = SignalNames::Custom("volume".to_string());
+        let title = SignalNames::Custom("title".to_string());
+        let calls = Rc::new(Cell::new(0));
+
+        let counter = |calls: &Rc<Cell<i32>>, step: i32| {
+            let calls = Rc::clone(calls);
+            move |_: &dyn std::any::Any| calls.set(calls.get() + step)
+        };
+
+        // E.g. connected by an embedder before the bar was built
+        env.signals
+            .borrow_mut()
+            .entry(volume.clone())
+            .or_default()
+            .connect(counter(&calls, 1));
+
+        let ((), connections) = env.track_connections(|| {
+            env.connect_signals(&[volume.clone(), title.clone()], {
+                let calls = Rc::clone(&calls);
+                move |_, _| calls.set(calls.get() + 10)
+            });
+        });
+        assert_eq!(connections.len(), 2);
+
+        env.disconnect(&connections);
+        let signals = env.signals.borrow();
+        signals[&volume].emit(&1i32);
+        signals[&title].emit(&"title".to_string());
+        assert_eq!(calls.get(), 1);
+        assert_eq!(signals[&volume].connection_ids().len(), 1);
+    }
+
+    #[test]
+    fn clones_share_callbacks_and_last_value() {
+        let signal = Signal::new();
+        let clone = signal.clone();
+        let calls = Rc::new(Cell::new(0));
+
+        let counter = Rc::clone(&calls);
+        clone.connect(move |_| counter.set(counter.get() + 1));
+        signal.emit(&42i32);
+
+        assert_eq!(calls.get(), 1);
+        assert_eq!(clone.get_last_value_cloned::<i32>(), Some(42));
+    }
+
+    #[test]
+    fn cloned_signal_emits_while_listeners_borrow_map() {
+        let env = Environment::headless(1, 1);
+        let workspaces = SignalNames::Custom("workspaces".to_string());
+        let connected = Rc::new(Cell::new(false));
+
+        env.signals
+            .borrow_mut()
+            .entry(workspaces.clone())
+            .or_default()
+            .connect({
+                let signals = Rc::clone(&env.signals);
+                let connected = Rc::clone(&connected);
+                move |_| {
+                    signals
+                        .borrow_mut()
+                        .entry(SignalNames::Custom("title".to_string()))
+                        .or_default()
+                        .connect(|_| {});
+                    connected.set(true);
+                }
+            });
+
+        let signal = env.signals.borrow()[&workspaces].clone();
+        signal.emit(&1i32);
+        assert!(connected.get());
+    }
+
+    #[test]
+    fn emit_from_connect_time_callback() {
+        let signal = Signal::new();
+        signal.emit(&1i32);
+
+        let handle = signal.clone();
+        signal.connect(move |data| {
+            if let Some(num) = data.downcast_ref::<i32>() {
+                if *num < 3 {
+                    handle.emit(&(num + 1));
+                }
+            }
+        });
+
+        // Callback is connected after it's first call, so it doesn't receive it's own emission
+        assert_eq!(signal.get_last_value_cloned::<i32>(), Some(2));
+        signal.emit(&1i32);
+        assert_eq!(signal.get_last_value_cloned::<i32>(), Some(3));
+    }
 }
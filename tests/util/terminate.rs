@@ -0,0 +1,17 @@
+#[cfg(test)]
+mod tests {
+    use capybar::util::terminate;
+
+    #[test]
+    fn flag_set_by_both_signals() {
+        terminate::install().unwrap();
+        assert!(!terminate::take());
+
+        unsafe { libc::raise(libc::SIGTERM) };
+        assert!(terminate::take());
+        assert!(!terminate::take());
+
+        unsafe { libc::raise(libc::SIGINT) };
+        assert!(terminate::take());
+    }
+}
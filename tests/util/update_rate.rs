@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use capybar::{services::ProcessSettings, util::UpdateRate};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Rate {
+        update_rate: UpdateRate,
+    }
+
+    fn parse(toml: &str) -> Result<UpdateRate, toml::de::Error> {
+        toml::from_str::<Rate>(toml).map(|rate| rate.update_rate)
+    }
+
+    #[test]
+    fn integer_is_milliseconds() {
+        assert_eq!(parse("update_rate = 500"), Ok(UpdateRate::from_millis(500)));
+        assert!(parse("update_rate = -1").is_err());
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(
+            parse(r#"update_rate = "500ms""#),
+            Ok(UpdateRate::from_millis(500))
+        );
+        assert_eq!(parse(r#"update_rate = "2s""#), Ok(UpdateRate::from_secs(2)));
+        assert_eq!(parse(r#"update_rate = "5m""#), Ok(UpdateRate::from_mins(5)));
+        assert_eq!(
+            parse(r#"update_rate = "1h""#),
+            Ok(UpdateRate::from_mins(60))
+        );
+        assert_eq!(
+            parse(r#"update_rate = "0.5s""#),
+            Ok(UpdateRate::from_millis(500))
+        );
+        assert_eq!(
+            parse(r#"update_rate = " 2 s ""#),
+            Ok(UpdateRate::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn invalid_strings() {
+        for rate in ["", "s", "2 days", "-2s", "1.2.3s", "fast"] {
+            assert!(
+                rate.parse::<UpdateRate>().is_err(),
+                "`{rate}` should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn serialized_as_milliseconds() {
+        let settings: ProcessSettings = toml::from_str(r#"update_rate = "2s""#).unwrap();
+        assert_eq!(
+            toml::to_string(&settings).unwrap().trim(),
+            "update_rate = 2000"
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let rate = UpdateRate::from(Duration::from_millis(1500));
+        assert_eq!(rate.as_duration(), Duration::from_millis(1500));
+        assert_eq!(rate.as_delta().num_milliseconds(), 1500);
+        assert!(UpdateRate::ZERO.is_zero());
+        assert!(UpdateRate(Duration::MAX).as_delta() > chrono::TimeDelta::days(365));
+    }
+}
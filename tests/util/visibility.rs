@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use capybar::util::visibility::{idle_work, BarState, Visibility};
+
+    const IDLE: BarState = BarState {
+        configured: true,
+        frame_pending: false,
+        visible: true,
+    };
+
+    #[test]
+    fn visible_until_first_enter() {
+        let mut visibility = Visibility::new();
+        assert!(visibility.is_visible());
+
+        assert!(!visibility.enter("DP-1"));
+        visibility.leave(&"DP-1");
+        assert!(!visibility.is_visible());
+
+        assert!(visibility.enter("DP-1"));
+        assert!(!visibility.enter("HDMI-A-1"));
+        visibility.leave(&"DP-1");
+        assert!(visibility.is_visible());
+    }
+
+    #[test]
+    fn hidden_while_powered_off() {
+        let mut visibility = Visibility::new();
+        visibility.enter("DP-1");
+
+        assert!(!visibility.set_powered(false));
+        assert!(!visibility.is_visible());
+        assert!(!visibility.set_powered(false));
+        assert!(visibility.set_powered(true));
+
+        // Leaving every output while powered off keeps the bar hidden after power on
+        visibility.set_powered(false);
+        visibility.leave(&"DP-1");
+        assert!(!visibility.set_powered(true));
+        assert!(!visibility.is_visible());
+    }
+
+    #[test]
+    fn idle_tick_skips_hidden_bars() {
+        let hidden = BarState {
+            visible: false,
+            ..IDLE
+        };
+        let waiting = BarState {
+            frame_pending: true,
+            ..IDLE
+        };
+        let unconfigured = BarState {
+            configured: false,
+            ..IDLE
+        };
+
+        let work = idle_work([(0, IDLE), (1, hidden), (2, waiting), (3, unconfigured)]);
+        assert!(work.run_services);
+        assert_eq!(work.draw, vec![0]);
+
+        let work = idle_work([(0, hidden), (1, hidden)]);
+        assert!(!work.run_services);
+        assert!(work.draw.is_empty());
+
+        // Services of a visible bar run even if it waits for a frame callback
+        let work = idle_work([(0, waiting), (1, hidden)]);
+        assert!(work.run_services);
+        assert!(work.draw.is_empty());
+
+        assert!(!idle_work::<usize>([]).run_services);
+    }
+}
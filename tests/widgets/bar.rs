@@ -0,0 +1,325 @@
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use capybar::{
+        root::Environment,
+        util::Color,
+        widgets::{
+            containers::{
+                bar::{Bar, BarSettings},
+                row::RowSettings,
+            },
+            progress_bar::{ProgressBar, ProgressBarSettings},
+            Margin, Style, Widget, WidgetData, WidgetNew,
+        },
+    };
+
+    use crate::widgets::fixed::Fixed;
+
+    fn bar(width: usize) -> Bar {
+        Bar::new(
+            None,
+            BarSettings {
+                default_data: WidgetData::with_width(width),
+                ..BarSettings::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fitting_widgets() {
+        let mut bar = bar(100);
+        bar.left().get_mut().add_widget(Fixed::new(10, 10));
+        bar.left().get_mut().add_widget(Fixed::new(10, 10));
+        bar.center().get_mut().add_widget(Fixed::new(30, 10));
+        bar.right().get_mut().add_widget(Fixed::new(30, 10));
+
+        assert!(bar.init().is_ok());
+    }
+
+    #[test]
+    fn left_overflow() {
+        let mut bar = bar(100);
+        bar.left().get_mut().add_widget(Fixed::new(120, 10));
+
+        let err = bar.init().unwrap_err().to_string();
+        assert!(err.contains("left"), "{err}");
+        assert!(err.contains("20px overflow"), "{err}");
+    }
+
+    #[test]
+    fn right_overflow() {
+        let mut bar = bar(100);
+        bar.left().get_mut().add_widget(Fixed::new(60, 10));
+        bar.right().get_mut().add_widget(Fixed::new(50, 10));
+
+        let err = bar.init().unwrap_err().to_string();
+        assert!(err.contains("right"), "{err}");
+        assert!(err.contains("10px overflow"), "{err}");
+    }
+
+    #[test]
+    fn center_overflow() {
+        let mut bar = bar(100);
+        bar.left().get_mut().add_widget(Fixed::new(30, 10));
+        bar.center().get_mut().add_widget(Fixed::new(50, 10));
+
+        let err = bar.init().unwrap_err().to_string();
+        assert!(err.contains("center"), "{err}");
+        assert!(err.contains("10px overflow"), "{err}");
+    }
+
+    #[test]
+    fn center_grown_after_init() {
+        let mut bar = bar(100);
+        bar.center().get_mut().add_widget(Fixed::new(30, 10));
+        bar.init().unwrap();
+        bar.prepare().unwrap();
+
+        bar.center().get_mut().widgets_mut()[0].data_mut().width = 130;
+        let err = bar.prepare().unwrap_err().to_string();
+        assert!(err.contains("center"), "{err}");
+        assert!(err.contains("30px overflow"), "{err}");
+    }
+
+    #[test]
+    fn padding_counts() {
+        let mut bar = bar(100);
+        bar.left().get_mut().add_widget(Fixed::new(50, 10));
+        bar.left().get_mut().add_widget(Fixed::new(45, 10));
+
+        let err = bar.init().unwrap_err().to_string();
+        assert!(err.contains("5px overflow"), "{err}");
+    }
+
+    #[test]
+    fn fit_content_width() {
+        let mut bar = Bar::new(
+            None,
+            BarSettings {
+                fit_content: true,
+                ..BarSettings::default()
+            },
+        )
+        .unwrap();
+        bar.left().get_mut().add_widget(Fixed::new(40, 10));
+        bar.center().get_mut().add_widget(Fixed::new(30, 10));
+        bar.right().get_mut().add_widget(Fixed::new(20, 10));
+
+        bar.init().unwrap();
+        assert_eq!(bar.data().width, 30 + 2 * 40);
+    }
+
+    #[test]
+    fn rows_inset_by_style() {
+        let mut bar = Bar::new(
+            None,
+            BarSettings {
+                default_data: WidgetData::with_width(1920),
+                style: Style {
+                    border: Some((2, Color::WHITE)),
+                    margin: Margin {
+                        left: 5,
+                        right: 4,
+                        ..Margin::default()
+                    },
+                    padding: Margin {
+                        right: 3,
+                        ..Margin::default()
+                    },
+                    ..Style::default()
+                },
+                ..BarSettings::default()
+            },
+        )
+        .unwrap();
+        bar.left().get_mut().add_widget(Fixed::new(10, 10));
+        bar.right().get_mut().add_widget(Fixed::new(30, 10));
+        bar.init().unwrap();
+
+        // Rows align their widgets before the bar places the rows, as they do on every draw
+        for _ in 0..3 {
+            bar.prepare().unwrap();
+        }
+        assert_eq!(bar.data().width, 1920);
+
+        let right = bar.right().get_mut().widgets_mut()[0].data().position.0;
+        assert_eq!(right + 30, 1920 - 4 - 2 - 3);
+
+        let left = bar.left().get_mut().widgets_mut()[0].data().position.0;
+        assert_eq!(left, 5 + 2);
+    }
+
+    #[test]
+    fn headless_draw() {
+        let env = Rc::new(Environment::headless(40, 4));
+        let mut bar = bar(40);
+
+        let progress = ProgressBar::new(
+            None,
+            ProgressBarSettings {
+                width: 10,
+                height: 4,
+                fill_color: Color::RED,
+                ..ProgressBarSettings::default()
+            },
+        )
+        .unwrap();
+        progress.set_progress(1.0);
+        bar.right().get_mut().add_widget(Box::new(progress));
+
+        bar.bind(Rc::clone(&env)).unwrap();
+        bar.init().unwrap();
+        bar.prepare().unwrap();
+        bar.draw().unwrap();
+
+        let mut drawer = env.drawer.borrow_mut();
+        let canvas = drawer.canvas().unwrap();
+        for x in 0..40 {
+            let expected = match x >= 30 {
+                true => [0, 0, 255, 255],
+                false => [0, 0, 0, 0],
+            };
+            assert_eq!(canvas.pixel(x, 0), Some(expected), "pixel {x}");
+        }
+    }
+
+    #[test]
+    fn center_row_percent_of_bar() {
+        let mut bar = Bar::new(
+            None,
+            BarSettings {
+                default_data: WidgetData::with_width(400),
+                center_settings: RowSettings {
+                    default_data: WidgetData {
+                        width_percent: Some(50.0),
+                        ..WidgetData::default()
+                    },
+                    ..RowSettings::default()
+                },
+                ..BarSettings::default()
+            },
+        )
+        .unwrap();
+        bar.center().get_mut().add_widget(Fixed::new(20, 10));
+        bar.center().get_mut().widgets_mut()[0]
+            .data_mut()
+            .width_percent = Some(100.0);
+        bar.init().unwrap();
+        bar.prepare().unwrap();
+
+        let center = bar.center().get_mut();
+        assert_eq!(center.widgets_mut()[0].data().width, 200);
+        assert_eq!(center.data().width, 200);
+        assert_eq!(center.data().position.0, 100);
+    }
+
+    #[test]
+    fn fixed_height() {
+        let mut bar = Bar::new(
+            None,
+            BarSettings {
+                default_data: WidgetData::with_width(100),
+                height: 30,
+                ..BarSettings::default()
+            },
+        )
+        .unwrap();
+        bar.left().get_mut().add_widget(Fixed::new(10, 10));
+        bar.right().get_mut().add_widget(Fixed::new(10, 20));
+
+        bar.init().unwrap();
+        assert_eq!(bar.data().height, 30);
+
+        bar.prepare().unwrap();
+        assert_eq!(bar.data().height, 30);
+        assert_eq!(bar.left().get_mut().data().position.1, 10);
+        assert_eq!(bar.right().get_mut().data().position.1, 5);
+    }
+
+    #[test]
+    fn fixed_height_grows_to_content() {
+        let mut bar = Bar::new(
+            None,
+            BarSettings {
+                default_data: WidgetData::with_width(100),
+                height: 15,
+                ..BarSettings::default()
+            },
+        )
+        .unwrap();
+        bar.center().get_mut().add_widget(Fixed::new(10, 20));
+
+        bar.init().unwrap();
+        assert_eq!(bar.data().height, 20);
+    }
+
+    #[test]
+    fn tooltip_settings_from_config() {
+        let settings: BarSettings =
+            toml::from_str("tooltip = { delay_ms = 200, size = 12 }").unwrap();
+        assert_eq!(settings.tooltip.delay_ms, 200);
+
+        let text = settings.tooltip.text_settings("Volume");
+        assert_eq!(text.text, "Volume");
+        assert_eq!(text.size, 12.0);
+        assert_eq!(text.style.padding.left, 6);
+    }
+
+    #[test]
+    fn percent_width_from_config() {
+        let settings: BarSettings =
+            toml::from_str("center_settings = { width_percent = 50 }").unwrap();
+        assert_eq!(
+            settings.center_settings.default_data.width_percent,
+            Some(50.0)
+        );
+        assert_eq!(settings.left_settings.default_data.width_percent, None);
+    }
+
+    #[test]
+    fn debug_tree_lists_rows() {
+        let mut bar = bar(40);
+        bar.right().get_mut().add_widget(Fixed::new(10, 4));
+        bar.init().unwrap();
+        bar.prepare().unwrap();
+
+        let tree = bar.debug_tree();
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(lines.len(), 5, "{tree}");
+        assert!(lines[0].starts_with("Bar (0, 0) 40x"), "{tree}");
+        assert!(lines[1..].iter().all(|line| line.starts_with("  ")));
+        assert!(lines[3].starts_with("  Row ("), "{tree}");
+
+        let fixed = bar.right().get_mut().widgets_mut()[0].data().position;
+        assert_eq!(
+            lines[4],
+            format!("    Fixed ({}, {}) 10x4", fixed.0, fixed.1)
+        );
+    }
+
+    #[test]
+    fn draws_rows_in_z_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut bar = bar(100);
+        bar.left()
+            .get_mut()
+            .add_widget(Fixed::recording(10, 10, 0, Rc::clone(&log)));
+        bar.center()
+            .get_mut()
+            .add_widget(Fixed::recording(10, 10, 1, Rc::clone(&log)));
+        bar.right()
+            .get_mut()
+            .add_widget(Fixed::recording(10, 10, 2, Rc::clone(&log)));
+        bar.left().borrow().data_mut().z = 1;
+
+        bar.bind(Rc::new(Environment::headless(100, 10))).unwrap();
+        bar.init().unwrap();
+        bar.prepare().unwrap();
+        bar.draw().unwrap();
+
+        assert_eq!(*log.borrow(), vec![1, 2, 0]);
+    }
+}
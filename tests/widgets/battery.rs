@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use battery::State;
+    use capybar::{
+        util::Color,
+        widgets::{
+            battery::{BatteryInfo, BatterySettings},
+            text::TextSettings,
+        },
+    };
+
+    #[test]
+    fn icon_in_range() {
+        let settings = BatterySettings::default();
+        assert_eq!(settings.icon(0, false), settings.battery_not_charging[0]);
+        assert_eq!(settings.icon(55, false), settings.battery_not_charging[5]);
+        assert_eq!(settings.icon(100, false), settings.battery_not_charging[10]);
+        assert_eq!(settings.icon(100, true), settings.battery_charging[10]);
+    }
+
+    #[test]
+    fn icon_above_full() {
+        let settings = BatterySettings::default();
+        assert_eq!(settings.icon(101, false), settings.battery_not_charging[10]);
+        assert_eq!(settings.icon(101, true), settings.battery_charging[10]);
+        assert_eq!(
+            settings.icon(i8::MAX, false),
+            settings.battery_not_charging[10]
+        );
+    }
+
+    #[test]
+    fn icon_below_empty() {
+        let settings = BatterySettings::default();
+        assert_eq!(settings.icon(-1, false), settings.battery_not_charging[0]);
+        assert_eq!(settings.icon(-1, true), settings.battery_charging[0]);
+        assert_eq!(
+            settings.icon(i8::MIN, false),
+            settings.battery_not_charging[0]
+        );
+    }
+
+    #[test]
+    fn time_remaining() {
+        // 36 kJ at 10 W is an hour
+        let discharging = BatteryInfo::new(36000.0, 72000.0, 10.0, State::Discharging);
+        assert_eq!(
+            discharging.time_remaining(),
+            Some(Duration::from_secs(3600))
+        );
+
+        let charging = BatteryInfo::new(36000.0, 72000.0, 20.0, State::Charging);
+        assert_eq!(charging.time_remaining(), Some(Duration::from_secs(1800)));
+
+        let unknown = BatteryInfo::new(36000.0, 72000.0, 10.0, State::Unknown);
+        assert_eq!(unknown.time_remaining(), None);
+
+        let no_rate = BatteryInfo::new(36000.0, 72000.0, 0.0, State::Discharging);
+        assert_eq!(no_rate.time_remaining(), None);
+    }
+
+    #[test]
+    fn time_of_combined_batteries() {
+        let first = BatteryInfo::new(36000.0, 72000.0, 10.0, State::Discharging);
+        let second = BatteryInfo::new(0.0, 72000.0, 0.0, State::Unknown);
+
+        // Energy of both batteries is drained by the combined rate
+        let combined = first.clone() + first + second;
+        assert_eq!(combined.state(), State::Discharging);
+        assert_eq!(combined.time_remaining(), Some(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn time_format() {
+        let settings = BatterySettings::default();
+        assert_eq!(
+            settings.format_time(Duration::from_secs(83 * 60 + 59)),
+            "1h23m"
+        );
+        assert_eq!(settings.format_time(Duration::from_secs(5 * 60)), "0h05m");
+    }
+
+    #[test]
+    fn text_with_time() {
+        let info = BatteryInfo::new(36000.0, 72000.0, 10.0, State::Discharging);
+        let mut settings = BatterySettings::default();
+        assert_eq!(settings.text(&info), "50%");
+
+        settings.show_time = true;
+        assert_eq!(settings.text(&info), "50% 1h00m");
+
+        let unknown = BatteryInfo::new(36000.0, 72000.0, 10.0, State::Unknown);
+        assert_eq!(settings.text(&unknown), "50%");
+    }
+
+    #[test]
+    fn warning_color() {
+        let settings = BatterySettings {
+            text_settings: TextSettings {
+                font_color: Color::WHITE,
+                ..TextSettings::default()
+            },
+            warning_threshold: 20,
+            warning_color: Color::RED,
+            ..BatterySettings::default()
+        };
+
+        assert_eq!(settings.color(20, State::Discharging), Color::RED);
+        assert_eq!(settings.color(5, State::Unknown), Color::RED);
+        assert_eq!(settings.color(21, State::Discharging), Color::WHITE);
+        assert_eq!(settings.color(5, State::Charging), Color::WHITE);
+    }
+}
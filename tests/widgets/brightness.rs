@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use capybar::widgets::brightness::BrightnessSettings;
+
+    /// Fake backlight directory with provided devices as (name, brightness, max_brightness)
+    fn backlight(name: &str, devices: &[(&str, &str, &str)]) -> PathBuf {
+        let base =
+            std::env::temp_dir().join(format!("capybar-backlight-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+
+        for (device, brightness, max) in devices {
+            let dir = base.join(device);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("brightness"), brightness).unwrap();
+            fs::write(dir.join("max_brightness"), max).unwrap();
+        }
+        fs::create_dir_all(&base).unwrap();
+
+        base
+    }
+
+    #[test]
+    fn icons() {
+        let settings = BrightnessSettings::default();
+        assert_eq!(settings.icon(0), "󰃞");
+        assert_eq!(settings.icon(50), "󰃟");
+        assert_eq!(settings.icon(100), "󰃠");
+    }
+
+    #[test]
+    fn first_device() {
+        let base = backlight(
+            "first",
+            &[
+                ("b_backlight", "10\n", "100\n"),
+                ("a_backlight", "120\n", "480\n"),
+            ],
+        );
+
+        let settings = BrightnessSettings::default();
+        assert_eq!(
+            settings.find_device(&base).unwrap(),
+            base.join("a_backlight")
+        );
+        assert_eq!(settings.read(&base).unwrap(), 25);
+    }
+
+    #[test]
+    fn configured_device() {
+        let base = backlight(
+            "configured",
+            &[
+                ("a_backlight", "120\n", "480\n"),
+                ("b_backlight", "10\n", "100\n"),
+            ],
+        );
+
+        let settings = BrightnessSettings {
+            device: Some("b_backlight".to_string()),
+            ..BrightnessSettings::default()
+        };
+        assert_eq!(settings.read(&base).unwrap(), 10);
+    }
+
+    #[test]
+    fn missing_device() {
+        let base = backlight("missing", &[]);
+
+        assert!(BrightnessSettings::default().read(&base).is_err());
+
+        let settings = BrightnessSettings {
+            device: Some("intel_backlight".to_string()),
+            ..BrightnessSettings::default()
+        };
+        assert!(settings.read(&base).is_err());
+    }
+
+    #[test]
+    fn zero_max_brightness() {
+        let base = backlight("zero", &[("backlight", "0\n", "0\n")]);
+        assert!(BrightnessSettings::default().read(&base).is_err());
+    }
+}
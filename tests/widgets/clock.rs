@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use capybar::widgets::{
+        clock::{Clock, ClockSettings},
+        WidgetNew,
+    };
+    use chrono::{Local, TimeDelta, TimeZone, Utc};
+
+    fn clock(format: &str, locale: Option<&str>) -> Result<Clock, capybar::widgets::WidgetError> {
+        Clock::new(
+            None,
+            ClockSettings {
+                format: format.to_string(),
+                locale: locale.map(str::to_string),
+                ..ClockSettings::default()
+            },
+        )
+    }
+
+    fn clock_in(timezone: &str) -> Result<Clock, capybar::widgets::WidgetError> {
+        Clock::new(
+            None,
+            ClockSettings {
+                format: "%H:%M %Z".to_string(),
+                timezone: Some(timezone.to_string()),
+                ..ClockSettings::default()
+            },
+        )
+    }
+
+    #[test]
+    fn unlocalised() {
+        let time = Local.with_ymd_and_hms(2024, 1, 1, 15, 4, 0).unwrap();
+        let clock = clock("%A %I:%M %p", None).unwrap();
+        assert_eq!(clock.format(&time), "Monday 03:04 PM");
+    }
+
+    #[test]
+    fn localised() {
+        let time = Local.with_ymd_and_hms(2024, 1, 1, 15, 4, 0).unwrap();
+        let clock = clock("%A %H:%M", Some("de_DE")).unwrap();
+        assert_eq!(clock.format(&time), "Montag 15:04");
+    }
+
+    #[test]
+    fn invalid_format() {
+        assert!(clock("%H:%", None).is_err());
+        assert!(clock("%Q", None).is_err());
+    }
+
+    #[test]
+    fn unknown_locale() {
+        assert!(clock("%H:%M", Some("xx_XX")).is_err());
+    }
+
+    #[test]
+    fn timezones() {
+        let time = Utc
+            .with_ymd_and_hms(2024, 1, 1, 15, 4, 0)
+            .unwrap()
+            .with_timezone(&Local);
+        assert_eq!(clock_in("UTC").unwrap().format(&time), "15:04 UTC");
+        assert_eq!(
+            clock_in("America/New_York").unwrap().format(&time),
+            "10:04 EST"
+        );
+        assert_eq!(clock_in("Asia/Kolkata").unwrap().format(&time), "20:34 IST");
+    }
+
+    #[test]
+    fn unknown_timezone() {
+        assert!(clock_in("Mars/Olympus_Mons").is_err());
+    }
+
+    #[test]
+    fn ticks_every_minute() {
+        let settings = ClockSettings {
+            format: "%a %d %b %H:%M".to_string(),
+            ..ClockSettings::default()
+        };
+        assert_eq!(settings.tick(), TimeDelta::minutes(1));
+    }
+
+    #[test]
+    fn ticks_every_second() {
+        for format in ["%H:%M:%S", "%T", "%s", "%+"] {
+            let settings = ClockSettings {
+                format: format.to_string(),
+                ..ClockSettings::default()
+            };
+            assert_eq!(settings.tick(), TimeDelta::seconds(1), "{format}");
+        }
+    }
+}
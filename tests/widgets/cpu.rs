@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{util::format::NumberFormat, widgets::cpu::CPUSettings};
+
+    #[test]
+    fn default_is_overall_usage() {
+        assert!(!CPUSettings::default().per_core);
+    }
+
+    #[test]
+    fn cores_without_unit() {
+        let settings = CPUSettings {
+            per_core: true,
+            ..CPUSettings::default()
+        };
+        assert_eq!(
+            settings.format_cores(&[12.4, 45.0, 8.0, 89.6]),
+            "12 45 8 90"
+        );
+        assert_eq!(settings.format_cores(&[]), "");
+    }
+
+    #[test]
+    fn cores_use_decimals() {
+        let settings = CPUSettings {
+            per_core: true,
+            number_format: NumberFormat {
+                decimals: 1,
+                ..NumberFormat::percent()
+            },
+            ..CPUSettings::default()
+        };
+        assert_eq!(settings.format_cores(&[1.25, 50.0]), "1.3 50.0");
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use capybar::{
+        root::Environment,
+        widgets::{
+            containers::row::{Row, RowSettings},
+            custom::{self, CustomSettings},
+            Widget, WidgetError, WidgetNew, WidgetsSettingsList,
+        },
+    };
+    use serde::Deserialize;
+
+    use crate::widgets::fixed::Fixed;
+
+    #[derive(Deserialize)]
+    struct FixedSettings {
+        width: usize,
+        height: usize,
+    }
+
+    fn build_fixed(
+        _env: Option<Rc<Environment>>,
+        settings: &toml::Table,
+    ) -> Result<Box<dyn Widget>, WidgetError> {
+        let settings: FixedSettings = custom::parse_settings(settings)?;
+        Ok(Fixed::new(settings.width, settings.height))
+    }
+
+    fn parse(config: &str) -> WidgetsSettingsList {
+        toml::from_str(config).unwrap()
+    }
+
+    #[test]
+    fn registered_widget_from_config() {
+        custom::register("fixed", build_fixed);
+        let settings = parse(
+            r#"
+            widget = "custom"
+            settings = { name = "fixed", width = 12, height = 7 }
+            "#,
+        );
+
+        let mut row = Row::new(None, RowSettings::default()).unwrap();
+        settings.create_in_container(&mut row).unwrap();
+
+        assert_eq!(row.len(), 1);
+        assert_eq!(row.widgets_mut()[0].data().width, 12);
+        assert_eq!(row.widgets_mut()[0].data().height, 7);
+    }
+
+    #[test]
+    fn unregistered_widget() {
+        let settings = CustomSettings {
+            name: "not-registered".to_string(),
+            ..CustomSettings::default()
+        };
+        let mut row = Row::new(None, RowSettings::default()).unwrap();
+
+        let err = WidgetsSettingsList::Custom(settings)
+            .create_in_container(&mut row)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("\"not-registered\" is not registered"),
+            "{err}"
+        );
+        assert!(row.is_empty());
+    }
+
+    #[test]
+    fn invalid_settings() {
+        custom::register("fixed_invalid", build_fixed);
+        let settings = parse(
+            r#"
+            widget = "custom"
+            settings = { name = "fixed_invalid", width = "wide" }
+            "#,
+        );
+
+        let mut row = Row::new(None, RowSettings::default()).unwrap();
+        assert!(settings.create_in_container(&mut row).is_err());
+    }
+}
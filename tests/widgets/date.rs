@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{
+        util::UpdateRate,
+        widgets::{
+            date::{Date, DateSettings},
+            WidgetNew,
+        },
+    };
+    use chrono::{Local, TimeZone};
+
+    fn date(format: &str) -> Result<Date, capybar::widgets::WidgetError> {
+        Date::new(
+            None,
+            DateSettings {
+                format: format.to_string(),
+                ..DateSettings::default()
+            },
+        )
+    }
+
+    #[test]
+    fn default_format() {
+        let time = Local.with_ymd_and_hms(2024, 3, 7, 15, 4, 0).unwrap();
+        let date = Date::new(None, DateSettings::default()).unwrap();
+        assert_eq!(date.format(&time), "2024-03-07");
+    }
+
+    #[test]
+    fn custom_format() {
+        let time = Local.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(date("%a %d %b").unwrap().format(&time), "Mon 01 Jan");
+    }
+
+    #[test]
+    fn invalid_format() {
+        assert!(date("%Y-%").is_err());
+    }
+
+    #[test]
+    fn zero_update_rate() {
+        let settings = DateSettings {
+            update_rate: UpdateRate::ZERO,
+            ..DateSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn from_config() {
+        let settings: DateSettings = toml::from_str(r#"format = "%d.%m""#).unwrap();
+        assert_eq!(settings.format, "%d.%m");
+        assert_eq!(settings.update_rate, UpdateRate::from_secs(60));
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use capybar::widgets::disk::DiskSettings;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn settings(format: &str) -> DiskSettings {
+        DiskSettings {
+            format: format.to_string(),
+            ..DiskSettings::default()
+        }
+    }
+
+    #[test]
+    fn default_settings() {
+        let settings = DiskSettings::default();
+        assert_eq!(settings.mount, "/");
+        assert_eq!(settings.format_usage(10 * GIB, 54 * GIB), "54.0G");
+    }
+
+    #[test]
+    fn number_format() {
+        let settings: DiskSettings = toml::from_str(
+            r#"format = "{used}/{total}"
+number_format = { decimals = 2, unit = "GiB", unit_space = true }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            settings.format_usage(3 * GIB / 4, GIB / 4),
+            "0.75 GiB/1.00 GiB"
+        );
+    }
+
+    #[test]
+    fn all_tokens() {
+        let settings = settings("{used} {free} {total} {percent}%");
+        assert_eq!(
+            settings.format_usage(GIB / 2, 3 * GIB / 2),
+            "0.5 1.5 2.0 25%"
+        );
+    }
+
+    #[test]
+    fn empty_disk() {
+        assert_eq!(settings("{percent}%").format_usage(0, 0), "0%");
+    }
+
+    #[test]
+    fn from_config() {
+        let settings: DiskSettings = toml::from_str(r#"mount = "/home""#).unwrap();
+        assert_eq!(settings.mount, "/home");
+        assert_eq!(settings.format, "{free}G");
+    }
+}
@@ -0,0 +1,122 @@
+//! Widget of a fixed size that does not need a Wayland connection. Used to test containers.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use capybar::{
+    root::Environment,
+    widgets::{Interactive, ScrollEvent, Widget, WidgetData, WidgetError, WidgetList},
+};
+
+pub struct Fixed {
+    data: RefCell<WidgetData>,
+    env: Option<Rc<Environment>>,
+
+    /// Counter of clicks. Widget is interactive only if it or `scrolled` is set
+    clicks: Option<Rc<Cell<usize>>>,
+    /// Sum of received scrolls
+    scrolled: Option<Rc<Cell<ScrollEvent>>>,
+    /// Id pushed to the log on every draw
+    draws: Option<(usize, Rc<RefCell<Vec<usize>>>)>,
+}
+
+impl Fixed {
+    pub fn new(width: usize, height: usize) -> Box<Self> {
+        Box::new(Self {
+            data: RefCell::new(WidgetData::with_size(width, height)),
+            env: None,
+            clicks: None,
+            scrolled: None,
+            draws: None,
+        })
+    }
+
+    pub fn clickable(width: usize, height: usize, clicks: Rc<Cell<usize>>) -> Box<Self> {
+        Box::new(Self {
+            clicks: Some(clicks),
+            ..*Self::new(width, height)
+        })
+    }
+
+    pub fn scrollable(width: usize, height: usize, scrolled: Rc<Cell<ScrollEvent>>) -> Box<Self> {
+        Box::new(Self {
+            scrolled: Some(scrolled),
+            ..*Self::new(width, height)
+        })
+    }
+
+    pub fn recording(
+        width: usize,
+        height: usize,
+        id: usize,
+        log: Rc<RefCell<Vec<usize>>>,
+    ) -> Box<Self> {
+        Box::new(Self {
+            draws: Some((id, log)),
+            ..*Self::new(width, height)
+        })
+    }
+}
+
+impl Interactive for Fixed {
+    fn click(&self, _button: u32) -> Result<(), WidgetError> {
+        if let Some(clicks) = &self.clicks {
+            clicks.set(clicks.get() + 1);
+        }
+        Ok(())
+    }
+
+    fn scroll(&self, event: ScrollEvent) -> Result<(), WidgetError> {
+        if let Some(scrolled) = &self.scrolled {
+            let sum = scrolled.get();
+            scrolled.set(ScrollEvent {
+                delta_x: sum.delta_x + event.delta_x,
+                delta_y: sum.delta_y + event.delta_y,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Widget for Fixed {
+    fn name(&self) -> WidgetList {
+        WidgetList::Custom("Fixed".to_string())
+    }
+
+    fn bind(&mut self, env: Rc<Environment>) -> Result<(), WidgetError> {
+        self.env = Some(env);
+        Ok(())
+    }
+
+    fn env(&self) -> Option<Rc<Environment>> {
+        self.env.clone()
+    }
+
+    fn prepare(&self) -> Result<(), WidgetError> {
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<(), WidgetError> {
+        if let Some((id, log)) = &self.draws {
+            log.borrow_mut().push(*id);
+        }
+        Ok(())
+    }
+
+    fn init(&self) -> Result<(), WidgetError> {
+        Ok(())
+    }
+
+    fn data_cell(&self) -> &RefCell<WidgetData> {
+        &self.data
+    }
+
+    fn as_interactive(&self) -> Option<&dyn Interactive> {
+        match (&self.clicks, &self.scrolled) {
+            (None, None) => None,
+            _ => Some(self),
+        }
+    }
+}
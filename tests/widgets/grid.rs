@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use capybar::widgets::{
+        containers::grid::{CellAlign, CellAlignment, CellSettings, Grid, GridSettings},
+        Widget, WidgetNew,
+    };
+
+    use crate::widgets::fixed::Fixed;
+
+    fn grid(settings: GridSettings) -> Grid {
+        let mut grid = Grid::new(
+            None,
+            GridSettings {
+                rows: 2,
+                columns: 2,
+                ..settings
+            },
+        )
+        .unwrap();
+        grid.add_widget(Fixed::new(10, 20));
+        grid.add_widget(Fixed::new(30, 10));
+        grid.add_widget(Fixed::new(20, 5));
+        grid
+    }
+
+    fn positions(grid: &mut Grid) -> Vec<(usize, usize)> {
+        grid.widgets_mut()
+            .iter()
+            .map(|w| (w.data().position.0, w.data().position.1))
+            .collect()
+    }
+
+    #[test]
+    fn cells_fill_rows() {
+        let mut grid = grid(GridSettings {
+            row_gap: 2,
+            column_gap: 4,
+            ..GridSettings::default()
+        });
+        grid.init().unwrap();
+
+        assert_eq!(grid.data().width, 20 + 4 + 30);
+        assert_eq!(grid.data().height, 20 + 2 + 5);
+
+        grid.prepare().unwrap();
+        assert_eq!(positions(&mut grid), vec![(0, 0), (24, 0), (0, 22)]);
+    }
+
+    #[test]
+    fn aligned_in_cells() {
+        let mut grid = grid(GridSettings {
+            align: CellAlignment {
+                horizontal: CellAlign::End,
+                vertical: CellAlign::Center,
+            },
+            cells: vec![CellSettings {
+                row: 1,
+                column: 0,
+                align: CellAlignment::default(),
+            }],
+            ..GridSettings::default()
+        });
+        grid.prepare().unwrap();
+
+        assert_eq!(positions(&mut grid), vec![(10, 0), (20, 5), (0, 20)]);
+    }
+
+    #[test]
+    fn too_many_widgets() {
+        let mut grid = grid(GridSettings::default());
+        grid.add_widget(Fixed::new(1, 1));
+        grid.add_widget(Fixed::new(1, 1));
+
+        let err = grid.init().unwrap_err().to_string();
+        assert!(err.contains("5 widgets"), "{err}");
+    }
+
+    #[test]
+    fn settings_from_config() {
+        let settings: GridSettings = toml::from_str(
+            r#"
+            rows = 2
+            columns = 3
+            align = { vertical = "center" }
+            cells = [{ row = 0, column = 2, horizontal = "end" }]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.alignment(1, 1).vertical, CellAlign::Center);
+        assert_eq!(settings.alignment(0, 2).horizontal, CellAlign::End);
+        assert_eq!(settings.alignment(0, 2).vertical, CellAlign::Start);
+    }
+}
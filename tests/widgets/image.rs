@@ -0,0 +1,97 @@
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use capybar::widgets::{
+        image::{load, Image, ImageSettings},
+        Widget, WidgetNew,
+    };
+    use image::{Rgba, RgbaImage};
+
+    /// Red 4x2 PNG with a transparent top left pixel
+    fn png(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("capybar-image-{name}-{}.png", std::process::id()));
+        let mut image = RgbaImage::from_pixel(4, 2, Rgba([255, 0, 0, 255]));
+        image.put_pixel(0, 0, Rgba([0, 0, 0, 0]));
+        image.save(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn natural_size() {
+        let pixels = load(&png("natural"), None, None).unwrap();
+        assert_eq!((pixels.width, pixels.height), (4, 2));
+        assert_eq!(&pixels.rgba[..8], &[0, 0, 0, 0, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn keeps_aspect_ratio() {
+        let path = png("aspect");
+
+        let pixels = load(&path, Some(8), None).unwrap();
+        assert_eq!((pixels.width, pixels.height), (8, 4));
+        assert_eq!(pixels.rgba.len(), 8 * 4 * 4);
+
+        let pixels = load(&path, None, Some(1)).unwrap();
+        assert_eq!((pixels.width, pixels.height), (2, 1));
+
+        let pixels = load(&path, Some(3), Some(3)).unwrap();
+        assert_eq!((pixels.width, pixels.height), (3, 3));
+    }
+
+    #[test]
+    fn decoded_on_init() {
+        let image = Image::new(
+            None,
+            ImageSettings {
+                path: png("init"),
+                height: Some(4),
+                ..ImageSettings::default()
+            },
+        )
+        .unwrap();
+        assert!(image.pixels().is_none());
+
+        image.init().unwrap();
+        image.prepare().unwrap();
+        assert_eq!(image.data().width, 8);
+        assert_eq!(image.data().height, 4);
+    }
+
+    #[test]
+    fn missing_file() {
+        let image = Image::new(
+            None,
+            ImageSettings {
+                path: PathBuf::from("/nonexistent/logo.png"),
+                ..ImageSettings::default()
+            },
+        )
+        .unwrap();
+        assert!(image.init().is_err());
+    }
+
+    #[cfg(not(feature = "svg"))]
+    #[test]
+    fn svg_requires_feature() {
+        let error = load(&PathBuf::from("logo.svg"), None, None).unwrap_err();
+        assert!(error.to_string().contains("svg"));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg() {
+        let path = std::env::temp_dir().join(format!("capybar-image-{}.svg", std::process::id()));
+        std::fs::write(
+            &path,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="5">
+<rect width="10" height="5" fill="#0000ff"/></svg>"##,
+        )
+        .unwrap();
+
+        let pixels = load(&path, None, Some(10)).unwrap();
+        assert_eq!((pixels.width, pixels.height), (20, 10));
+        assert_eq!(&pixels.rgba[..4], &[0, 0, 255, 255]);
+    }
+}
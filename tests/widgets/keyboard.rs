@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use capybar::services::clients::{wayland::keyboard::layout_names, KeyboardLayouts};
+
+    const KEYMAP: &str = r#"xkb_keymap {
+xkb_keycodes "evdev+aliases(qwerty)" {
+	minimum = 8;
+	maximum = 255;
+	indicator 1 = "Caps Lock";
+};
+xkb_types "complete" {
+	type "ONE_LEVEL" {
+		modifiers= none;
+		level_name[Level1]= "Any";
+	};
+};
+xkb_symbols "pc+us+ru:2+inet(evdev)" {
+	name[Group1]="English (US)";
+	name[Group2]="Russian";
+	key <AE01> { [ 1, exclam ], [ 1, exclam ] };
+};
+};"#;
+
+    #[test]
+    fn names_from_keymap() {
+        assert_eq!(layout_names(KEYMAP), vec!["English (US)", "Russian"]);
+    }
+
+    #[test]
+    fn numeric_groups() {
+        let keymap = "name[2]=\"German\";\nname[1] = \"English (UK)\";";
+        assert_eq!(layout_names(keymap), vec!["English (UK)", "German"]);
+    }
+
+    #[test]
+    fn missing_names() {
+        assert!(layout_names("").is_empty());
+        assert_eq!(layout_names("name[Group2]=\"German\";"), vec!["", "German"]);
+    }
+
+    #[test]
+    fn active_layout() {
+        let layouts = KeyboardLayouts {
+            names: layout_names(KEYMAP),
+            active: 1,
+        };
+        assert_eq!(layouts.active_name(), Some("Russian"));
+
+        let layouts = KeyboardLayouts {
+            active: 2,
+            ..layouts
+        };
+        assert_eq!(layouts.active_name(), None);
+    }
+}
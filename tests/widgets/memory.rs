@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use capybar::widgets::memory::MemorySettings;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn settings(format: &str) -> MemorySettings {
+        MemorySettings {
+            format: format.to_string(),
+            ..MemorySettings::default()
+        }
+    }
+
+    #[test]
+    fn default_format() {
+        let settings = MemorySettings::default();
+        assert_eq!(settings.format_usage(4 * GIB, 16 * GIB), "4.0/16.0G");
+    }
+
+    #[test]
+    fn number_format() {
+        let settings: MemorySettings = toml::from_str(
+            r#"format = "{used}/{total}"
+number_format = { decimals = 2, unit = "GiB", unit_space = true }"#,
+        )
+        .unwrap();
+        assert_eq!(settings.format_usage(3 * GIB / 4, GIB), "0.75 GiB/1.00 GiB");
+    }
+
+    #[test]
+    fn all_tokens() {
+        let settings = settings("{used} {total} {percent}%");
+        assert_eq!(settings.format_usage(GIB / 2, 2 * GIB), "0.5 2.0 25%");
+    }
+
+    #[test]
+    fn zero_total() {
+        let settings = settings("{percent}%");
+        assert_eq!(settings.format_usage(0, 0), "0%");
+        assert_eq!(settings.format_usage(GIB, 0), "0%");
+    }
+}
@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{services::microphone::MicrophoneState, widgets::microphone::MicrophoneSettings};
+
+    fn state(volume: u32, muted: bool) -> MicrophoneState {
+        MicrophoneState {
+            available: true,
+            volume,
+            muted,
+        }
+    }
+
+    #[test]
+    fn icon_follows_mute() {
+        let settings = MicrophoneSettings::default();
+        assert_eq!(settings.icon(&state(40, false)), settings.icon);
+        assert_eq!(settings.icon(&state(40, true)), settings.muted_icon);
+    }
+
+    #[test]
+    fn level() {
+        let settings = MicrophoneSettings::default();
+        assert_eq!(settings.text(&state(40, false)), "40%");
+        assert_eq!(settings.text(&state(40, true)), "40%");
+
+        let settings = MicrophoneSettings {
+            show_level: false,
+            ..MicrophoneSettings::default()
+        };
+        assert_eq!(settings.text(&state(40, false)), "");
+    }
+
+    #[test]
+    fn no_source() {
+        let settings = MicrophoneSettings::default();
+        let state = MicrophoneState::default();
+        assert_eq!(settings.icon(&state), settings.muted_icon);
+        assert_eq!(settings.text(&state), "");
+    }
+}
@@ -0,0 +1,29 @@
+mod bar;
+mod battery;
+mod brightness;
+mod clock;
+mod cpu;
+mod custom;
+mod date;
+mod disk;
+mod fixed;
+mod grid;
+mod image;
+mod keyboard;
+mod memory;
+mod microphone;
+mod mpris;
+mod notifications;
+mod power_profile;
+mod progress_bar;
+mod row;
+mod script;
+mod separator;
+mod spacer;
+mod temperature;
+mod text;
+mod tray;
+mod volume;
+mod weather;
+mod window_title;
+mod workspaces;
@@ -0,0 +1,109 @@
+#[cfg(all(test, feature = "mpris"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use capybar::{
+        services::mpris::{parse_metadata, PlaybackStatus, PlayerState},
+        widgets::mpris::MprisSettings,
+    };
+    use zbus::zvariant::{OwnedValue, Value};
+
+    fn player(name: &str, status: PlaybackStatus) -> PlayerState {
+        PlayerState {
+            bus_name: format!("org.mpris.MediaPlayer2.{name}"),
+            status,
+            artist: "Artist".to_string(),
+            title: "Title".to_string(),
+        }
+    }
+
+    #[test]
+    fn select_playing_by_default() {
+        let players = [
+            player("firefox", PlaybackStatus::Paused),
+            player("spotify", PlaybackStatus::Playing),
+        ];
+        let settings = MprisSettings::default();
+        assert_eq!(settings.select(&players).unwrap().name(), "spotify");
+        assert_eq!(settings.select(&players[..1]).unwrap().name(), "firefox");
+        assert!(settings.select(&[]).is_none());
+    }
+
+    #[test]
+    fn select_configured_player() {
+        let players = [
+            player("firefox", PlaybackStatus::Paused),
+            player("spotify", PlaybackStatus::Playing),
+        ];
+
+        for name in ["firefox", "org.mpris.MediaPlayer2.firefox"] {
+            let settings = MprisSettings {
+                player: Some(name.to_string()),
+                ..MprisSettings::default()
+            };
+            assert_eq!(settings.select(&players).unwrap().name(), "firefox");
+        }
+
+        let settings = MprisSettings {
+            player: Some("vlc".to_string()),
+            ..MprisSettings::default()
+        };
+        assert!(settings.select(&players).is_none());
+    }
+
+    #[test]
+    fn text() {
+        let settings = MprisSettings::default();
+        let mut player = player("spotify", PlaybackStatus::Playing);
+        assert_eq!(settings.text(&player), "Artist - Title");
+
+        player.artist.clear();
+        assert_eq!(settings.text(&player), "Title");
+    }
+
+    #[test]
+    fn text_truncated() {
+        let settings = MprisSettings {
+            max_length: 8,
+            ..MprisSettings::default()
+        };
+        let player = player("spotify", PlaybackStatus::Playing);
+        assert_eq!(settings.text(&player), "Artist …");
+
+        let settings = MprisSettings {
+            max_length: 0,
+            ..MprisSettings::default()
+        };
+        assert_eq!(settings.text(&player), "Artist - Title");
+    }
+
+    #[test]
+    fn status() {
+        assert_eq!(PlaybackStatus::parse("Playing"), PlaybackStatus::Playing);
+        assert_eq!(PlaybackStatus::parse("Paused"), PlaybackStatus::Paused);
+        assert_eq!(PlaybackStatus::parse("Stopped"), PlaybackStatus::Stopped);
+        assert_eq!(PlaybackStatus::parse("???"), PlaybackStatus::Stopped);
+    }
+
+    #[test]
+    fn metadata() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "xesam:artist".to_string(),
+            OwnedValue::try_from(Value::from(vec!["A", "B"])).unwrap(),
+        );
+        metadata.insert(
+            "xesam:title".to_string(),
+            OwnedValue::try_from(Value::from("Song")).unwrap(),
+        );
+        assert_eq!(
+            parse_metadata(&metadata),
+            ("A, B".to_string(), "Song".to_string())
+        );
+
+        assert_eq!(
+            parse_metadata(&HashMap::new()),
+            (String::new(), String::new())
+        );
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(all(test, feature = "notifications"))]
+mod tests {
+    use capybar::{
+        services::notifications::{Daemon, NotificationsState},
+        widgets::notifications::NotificationsSettings,
+    };
+
+    fn state(count: u32, dnd: bool) -> NotificationsState {
+        NotificationsState {
+            daemon: Some(Daemon::Swaync),
+            count,
+            dnd,
+        }
+    }
+
+    #[test]
+    fn icon_follows_dnd() {
+        let settings = NotificationsSettings::default();
+        assert_eq!(settings.icon(&state(0, false)), settings.icon);
+        assert_eq!(settings.icon(&state(0, true)), settings.dnd_icon);
+    }
+
+    #[test]
+    fn count_hidden_when_zero() {
+        let settings = NotificationsSettings::default();
+        assert_eq!(settings.text(&state(3, false)), "3");
+        assert_eq!(settings.text(&state(0, false)), "");
+
+        let settings = NotificationsSettings {
+            show_zero: true,
+            ..NotificationsSettings::default()
+        };
+        assert_eq!(settings.text(&state(0, false)), "0");
+    }
+
+    #[test]
+    fn only_icon_without_daemon() {
+        let settings = NotificationsSettings {
+            show_zero: true,
+            ..NotificationsSettings::default()
+        };
+        let state = NotificationsState::default();
+
+        assert_eq!(settings.icon(&state), settings.icon);
+        assert_eq!(settings.text(&state), "");
+        assert!(state.toggle_dnd().is_ok());
+    }
+
+    #[test]
+    fn settings_from_config() {
+        let settings: NotificationsSettings =
+            toml::from_str("dnd_icon = \"D\"\nshow_zero = true").unwrap();
+        assert_eq!(settings.dnd_icon, "D");
+        assert!(settings.show_zero);
+        assert_eq!(settings.icon, NotificationsSettings::default().icon);
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(all(test, feature = "power_profile"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use capybar::{
+        services::power_profile::{parse_profiles, PowerProfileState, Profile},
+        widgets::power_profile::PowerProfileSettings,
+    };
+    use zbus::zvariant::{OwnedValue, Value};
+
+    fn state(profile: Profile, available: &[Profile]) -> PowerProfileState {
+        PowerProfileState {
+            profile: Some(profile),
+            available: available.to_vec(),
+        }
+    }
+
+    #[test]
+    fn cycles_available_profiles() {
+        let all = state(Profile::Balanced, &Profile::ALL);
+        assert_eq!(all.next(), Some(Profile::Performance));
+
+        let all = state(Profile::Performance, &Profile::ALL);
+        assert_eq!(all.next(), Some(Profile::PowerSaver));
+
+        // Performance is only available on some machines
+        let limited = state(Profile::Balanced, &[Profile::PowerSaver, Profile::Balanced]);
+        assert_eq!(limited.next(), Some(Profile::PowerSaver));
+
+        assert_eq!(PowerProfileState::default().next(), None);
+    }
+
+    #[test]
+    fn profiles_property() {
+        let profile = |name: &str| {
+            HashMap::from([(
+                "Profile".to_string(),
+                OwnedValue::try_from(Value::from(name)).unwrap(),
+            )])
+        };
+        let profiles = [
+            profile("power-saver"),
+            profile("unknown"),
+            profile("balanced"),
+        ];
+        assert_eq!(
+            parse_profiles(&profiles),
+            vec![Profile::PowerSaver, Profile::Balanced]
+        );
+    }
+
+    #[test]
+    fn icons_and_label() {
+        let settings = PowerProfileSettings::default();
+        let balanced = state(Profile::Balanced, &Profile::ALL);
+        assert_eq!(settings.icon(&balanced), settings.balanced_icon);
+        assert_eq!(settings.text(&balanced), "");
+
+        let settings = PowerProfileSettings {
+            show_label: true,
+            ..PowerProfileSettings::default()
+        };
+        assert_eq!(settings.text(&balanced), "balanced");
+    }
+
+    #[test]
+    fn without_daemon() {
+        let state = PowerProfileState::default();
+        assert_eq!(PowerProfileSettings::default().icon(&state), "");
+
+        let settings = PowerProfileSettings {
+            unavailable_icon: Some("x".to_string()),
+            show_label: true,
+            ..PowerProfileSettings::default()
+        };
+        assert_eq!(settings.icon(&state), "x");
+        assert_eq!(settings.text(&state), "");
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use capybar::{
+        root::Environment,
+        util::{signals::SignalNames, Color},
+        widgets::{
+            progress_bar::{ProgressBar, ProgressBarSettings},
+            Style, Widget, WidgetData, WidgetNew,
+        },
+    };
+
+    #[test]
+    fn from_config() {
+        let settings: ProgressBarSettings = toml::from_str(
+            r##"width = 50
+height = 4
+fill_color = "#ff0000"
+bar_border = [1, "#000000"]
+signal = { custom = "download" }"##,
+        )
+        .unwrap();
+        assert_eq!(settings.fill_color, Color::RED);
+        assert_eq!(settings.border, Some((1, Color::BLACK)));
+        assert_eq!(
+            settings.signal,
+            Some(SignalNames::Custom("download".to_string()))
+        );
+
+        let bar = ProgressBar::new(None, settings).unwrap();
+        bar.prepare().unwrap();
+        assert_eq!(bar.data().width, 50);
+        assert_eq!(bar.data().height, 4);
+    }
+
+    #[test]
+    fn fill_is_clamped_and_rounded() {
+        let bar = ProgressBar::new(
+            None,
+            ProgressBarSettings {
+                width: 12,
+                border: Some((1, Color::BLACK)),
+                ..ProgressBarSettings::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(bar.fill_width(), 0);
+
+        bar.set_progress(0.33);
+        assert_eq!(bar.fill_width(), 3);
+
+        bar.set_progress(0.36);
+        assert_eq!(bar.fill_width(), 4);
+
+        bar.set_progress(1.5);
+        assert_eq!(bar.progress(), 1.0);
+        assert_eq!(bar.fill_width(), 10);
+
+        bar.set_progress(-1.0);
+        assert_eq!(bar.fill_width(), 0);
+
+        bar.set_progress(f32::NAN);
+        assert_eq!(bar.progress(), 0.0);
+    }
+
+    #[test]
+    fn border_wider_than_widget() {
+        let env = Rc::new(Environment::headless(8, 8));
+        let mut bar = ProgressBar::new(
+            None,
+            ProgressBarSettings {
+                width: 3,
+                height: 1,
+                style: Style {
+                    border: Some((5, Color::RED)),
+                    ..Style::default()
+                },
+                ..ProgressBarSettings::default()
+            },
+        )
+        .unwrap();
+        bar.bind(Rc::clone(&env)).unwrap();
+        bar.prepare().unwrap();
+
+        // E.g. squeezed by a container
+        *bar.data_mut() = WidgetData::with_size(3, 1);
+        bar.draw().unwrap();
+
+        let mut drawer = env.drawer.borrow_mut();
+        let canvas = drawer.canvas().unwrap();
+        // Border stays inside of the widget
+        assert_eq!(canvas.pixel(0, 1), Some([0, 0, 0, 0]));
+        assert_eq!(canvas.pixel(3, 0), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn progress_change_is_damaged() {
+        let env = Rc::new(Environment::headless(20, 10));
+        let mut bar = ProgressBar::new(None, ProgressBarSettings::default()).unwrap();
+        bar.bind(Rc::clone(&env)).unwrap();
+        bar.draw().unwrap();
+        env.drawer.borrow_mut().clear_damage();
+
+        bar.draw().unwrap();
+        assert!(!env.drawer.borrow().is_damaged());
+
+        bar.set_progress(0.5);
+        bar.draw().unwrap();
+        assert!(env.drawer.borrow().is_damaged());
+    }
+}
@@ -0,0 +1,350 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use capybar::{
+        root::Environment,
+        widgets::{
+            containers::row::{Alignment, Row, RowSettings},
+            separator::{Separator, SeparatorSettings},
+            Margin, Position, ScrollEvent, Style, Widget, WidgetData, WidgetError, WidgetNew,
+            WidgetsSettingsList,
+        },
+    };
+
+    use crate::widgets::fixed::Fixed;
+
+    fn row(alignment: Alignment, data: WidgetData) -> Row {
+        let mut row = Row::new(
+            None,
+            RowSettings {
+                alignment,
+                default_data: data,
+                ..RowSettings::default()
+            },
+        )
+        .unwrap();
+        row.add_widget(Fixed::new(10, 20));
+        row.add_widget(Fixed::new(30, 10));
+        row
+    }
+
+    fn positions(row: &mut Row) -> Vec<(usize, usize)> {
+        row.widgets_mut()
+            .iter()
+            .map(|w| (w.data().position.0, w.data().position.1))
+            .collect()
+    }
+
+    fn widths(row: &mut Row) -> Vec<usize> {
+        row.widgets_mut().iter().map(|w| w.data().width).collect()
+    }
+
+    #[test]
+    fn percent_widths_share_space_left() {
+        let mut row = row(
+            Alignment::GrowthHorizontalRight(10),
+            WidgetData::with_width(200),
+        );
+        row.add_widget(Fixed::new(0, 10));
+        row.widgets_mut()[1].data_mut().width_percent = Some(25.0);
+        row.widgets_mut()[2].data_mut().width_percent = Some(75.0);
+        row.prepare().unwrap();
+
+        // 200 - 10 fixed - 2 paddings of 10
+        assert_eq!(widths(&mut row), vec![10, 42, 127]);
+        assert_eq!(positions(&mut row)[2].0, 10 + 10 + 42 + 10);
+    }
+
+    #[test]
+    fn percent_widths_over_100_are_scaled() {
+        let mut row = row(
+            Alignment::GrowthHorizontalRight(0),
+            WidgetData::with_width(100),
+        );
+        row.widgets_mut()[0].data_mut().width_percent = Some(100.0);
+        row.widgets_mut()[1].data_mut().width_percent = Some(300.0);
+        row.prepare().unwrap();
+
+        assert_eq!(widths(&mut row), vec![25, 75]);
+    }
+
+    #[test]
+    fn percent_widths_need_known_width() {
+        let mut row = row(Alignment::GrowthHorizontalRight(0), WidgetData::default());
+        row.widgets_mut()[0].data_mut().width_percent = Some(50.0);
+        row.prepare().unwrap();
+
+        assert_eq!(widths(&mut row), vec![10, 30]);
+    }
+
+    #[test]
+    fn growth_vertical_down() {
+        let mut row = row(Alignment::GrowthVerticalDown(5), WidgetData::default());
+        row.prepare().unwrap();
+
+        assert_eq!(positions(&mut row), vec![(0, 0), (0, 25)]);
+        assert_eq!(row.data().width, 30);
+        assert_eq!(row.data().height, 35);
+    }
+
+    #[test]
+    fn growth_vertical_up() {
+        let mut row = row(
+            Alignment::GrowthVerticalUp(5),
+            WidgetData::with_position(Position::new(0, 100)),
+        );
+        row.prepare().unwrap();
+
+        assert_eq!(positions(&mut row), vec![(0, 80), (0, 65)]);
+        assert_eq!(row.data().width, 30);
+        assert_eq!(row.data().height, 35);
+        assert_eq!(row.data().position.1, 65);
+    }
+
+    #[test]
+    fn growth_vertical_up_overflow() {
+        let row = row(
+            Alignment::GrowthVerticalUp(5),
+            WidgetData::with_position(Position::new(0, 25)),
+        );
+        assert!(row.prepare().is_err());
+    }
+
+    #[test]
+    fn centering_vertical() {
+        let mut row = row(Alignment::CenteringVertical, WidgetData::with_size(0, 50));
+        row.prepare().unwrap();
+
+        assert_eq!(positions(&mut row), vec![(0, 0), (0, 40)]);
+        assert_eq!(row.data().width, 30);
+    }
+
+    #[test]
+    fn centering_vertical_single() {
+        let mut row = Row::new(
+            None,
+            RowSettings {
+                alignment: Alignment::CenteringVertical,
+                default_data: WidgetData::with_size(0, 50),
+                ..RowSettings::default()
+            },
+        )
+        .unwrap();
+        row.add_widget(Fixed::new(10, 20));
+        row.prepare().unwrap();
+
+        assert_eq!(positions(&mut row), vec![(0, 15)]);
+    }
+
+    #[test]
+    fn padding_insets_widgets() {
+        let mut row = Row::new(
+            None,
+            RowSettings {
+                alignment: Alignment::GrowthHorizontalRight(5),
+                style: Style {
+                    padding: Margin {
+                        left: 3,
+                        right: 4,
+                        up: 1,
+                        down: 2,
+                    },
+                    ..Style::default()
+                },
+                ..RowSettings::default()
+            },
+        )
+        .unwrap();
+        row.add_widget(Fixed::new(10, 20));
+        row.add_widget(Fixed::new(30, 10));
+        row.prepare().unwrap();
+
+        assert_eq!(positions(&mut row), vec![(3, 1), (18, 1)]);
+        assert_eq!(row.data().width, 45 + 7);
+        assert_eq!(row.data().height, 20 + 3);
+        assert_eq!(row.content_width(), 45 + 7);
+    }
+
+    #[test]
+    fn tooltip_of_hovered_widget() {
+        let mut row = Row::new(
+            None,
+            RowSettings {
+                style: Style {
+                    tooltip: Some("row".to_string()),
+                    ..Style::default()
+                },
+                ..RowSettings::default()
+            },
+        )
+        .unwrap();
+        row.add_widget(Fixed::new(10, 20));
+        row.add_widget(Box::new(
+            Separator::new(
+                None,
+                SeparatorSettings {
+                    thickness: 20,
+                    height: 20,
+                    style: Style {
+                        tooltip: Some("separator".to_string()),
+                        ..Style::default()
+                    },
+                    ..SeparatorSettings::default()
+                },
+            )
+            .unwrap(),
+        ));
+        row.prepare().unwrap();
+
+        assert_eq!(row.tooltip_at(25, 0).as_deref(), Some("separator"));
+        assert_eq!(row.tooltip_at(5, 5).as_deref(), Some("row"));
+        assert_eq!(row.tooltip_at(100, 5), None);
+    }
+
+    #[test]
+    fn centering_vertical_overflow() {
+        let row = row(Alignment::CenteringVertical, WidgetData::with_size(0, 25));
+        assert!(row.prepare().is_err());
+    }
+
+    #[test]
+    fn click_routed_to_widget_under_pointer() {
+        let first = Rc::new(Cell::new(0));
+        let second = Rc::new(Cell::new(0));
+        let mut row = Row::new(
+            None,
+            RowSettings {
+                alignment: Alignment::GrowthHorizontalRight(5),
+                ..RowSettings::default()
+            },
+        )
+        .unwrap();
+        row.add_widget(Fixed::clickable(10, 10, Rc::clone(&first)));
+        row.add_widget(Fixed::clickable(10, 10, Rc::clone(&second)));
+        row.prepare().unwrap();
+
+        assert!(row.click_at(17, 5, 0x110).unwrap());
+        assert_eq!((first.get(), second.get()), (0, 1));
+
+        assert!(row.click_at(3, 5, 0x110).unwrap());
+        assert_eq!((first.get(), second.get()), (1, 1));
+    }
+
+    #[test]
+    fn click_outside_widgets_ignored() {
+        let clicks = Rc::new(Cell::new(0));
+        let mut row = Row::new(None, RowSettings::default()).unwrap();
+        row.add_widget(Fixed::clickable(10, 10, Rc::clone(&clicks)));
+        row.add_widget(Fixed::new(10, 10));
+        row.prepare().unwrap();
+
+        assert!(!row.click_at(12, 5, 0x110).unwrap());
+        assert!(!row.click_at(25, 5, 0x110).unwrap());
+        assert!(!row.click_at(5, 50, 0x110).unwrap());
+        assert_eq!(clicks.get(), 0);
+    }
+
+    #[test]
+    fn scroll_routed_to_widget_under_pointer() {
+        let first = Rc::new(Cell::new(ScrollEvent::default()));
+        let second = Rc::new(Cell::new(ScrollEvent::default()));
+        let mut row = Row::new(None, RowSettings::default()).unwrap();
+        row.add_widget(Fixed::scrollable(10, 10, Rc::clone(&first)));
+        row.add_widget(Fixed::scrollable(10, 10, Rc::clone(&second)));
+        row.prepare().unwrap();
+
+        let scroll = ScrollEvent {
+            delta_x: 0.0,
+            delta_y: 15.0,
+        };
+        assert!(row.scroll_at(5, 5, scroll).unwrap());
+        assert!(row.scroll_at(5, 5, scroll).unwrap());
+        assert_eq!(first.get().delta_y, 30.0);
+        assert_eq!(second.get(), ScrollEvent::default());
+    }
+
+    #[test]
+    fn scroll_reports_both_axes() {
+        let scrolled = Rc::new(Cell::new(ScrollEvent::default()));
+        let mut row = Row::new(None, RowSettings::default()).unwrap();
+        row.add_widget(Fixed::scrollable(10, 10, Rc::clone(&scrolled)));
+        row.prepare().unwrap();
+
+        let scroll = ScrollEvent {
+            delta_x: -4.0,
+            delta_y: 2.5,
+        };
+        assert!(row.scroll_at(5, 5, scroll).unwrap());
+        assert!(!row.scroll_at(15, 5, scroll).unwrap());
+        assert_eq!(scrolled.get(), scroll);
+    }
+
+    #[test]
+    fn nested_row_from_config() {
+        let settings: WidgetsSettingsList = toml::from_str(
+            r#"
+            widget = "row"
+            [settings]
+            alignment = { type = "GrowthHorizontalRight", padding = 2 }
+
+            [[settings.widgets]]
+            widget = "spacer"
+            settings = { width = 5 }
+
+            [[settings.widgets]]
+            widget = "spacer"
+            settings = { width = 7 }
+            "#,
+        )
+        .unwrap();
+
+        let mut row = Row::new(None, RowSettings::default()).unwrap();
+        settings.create_in_container(&mut row).unwrap();
+        row.prepare().unwrap();
+
+        assert_eq!(row.len(), 1);
+        assert_eq!(row.widgets_mut()[0].data().width, 5 + 2 + 7);
+    }
+
+    #[test]
+    fn try_data_reports_borrowed_data() {
+        let widget = Fixed::new(10, 20);
+        {
+            let _data = widget.data_mut();
+            assert!(matches!(
+                widget.try_data(),
+                Err(WidgetError::DataBorrowed(_))
+            ));
+            assert!(matches!(
+                widget.try_data_mut(),
+                Err(WidgetError::DataBorrowed(_))
+            ));
+        }
+
+        let data = widget.try_data().unwrap();
+        assert_eq!(data.width, 10);
+        assert!(widget.try_data().is_ok());
+        assert!(widget.try_data_mut().is_err());
+    }
+
+    #[test]
+    fn draws_in_z_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut row = Row::new(None, RowSettings::default()).unwrap();
+        for (id, z) in [(0, 1), (1, 0), (2, 1), (3, -1)] {
+            let widget = Fixed::recording(10, 10, id, Rc::clone(&log));
+            widget.data_mut().z = z;
+            row.add_widget(widget);
+        }
+
+        row.bind(Rc::new(Environment::headless(40, 10))).unwrap();
+        row.prepare().unwrap();
+        row.draw().unwrap();
+
+        assert_eq!(*log.borrow(), vec![3, 1, 0, 2]);
+    }
+}
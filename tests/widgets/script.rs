@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        rc::Rc,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use capybar::{
+        root::Environment,
+        util::UpdateRate,
+        widgets::{
+            script::{Script, ScriptSettings},
+            Widget, WidgetNew,
+        },
+    };
+
+    fn settings(command: &str, args: &[&str]) -> ScriptSettings {
+        ScriptSettings {
+            command: command.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            error_text: "failed".to_string(),
+            ..ScriptSettings::default()
+        }
+    }
+
+    #[test]
+    fn trimmed_stdout() {
+        let settings = settings("sh", &["-c", "echo '  hello  '"]);
+        assert_eq!(settings.run(), "hello");
+    }
+
+    #[test]
+    fn non_zero_exit() {
+        let settings = settings("sh", &["-c", "echo partial; exit 1"]);
+        assert_eq!(settings.run(), "failed");
+    }
+
+    #[test]
+    fn spawn_failure() {
+        let settings = settings("capybar-command-that-does-not-exist", &[]);
+        assert_eq!(settings.run(), "failed");
+    }
+
+    #[test]
+    fn from_config() {
+        let settings: ScriptSettings = toml::from_str(
+            r#"
+            command = "date"
+            args = ["+%s"]
+            update_rate = 1000
+            "#,
+        )
+        .unwrap();
+        assert_eq!(settings.args, vec!["+%s"]);
+        assert_eq!(settings.error_text, "ERR");
+    }
+
+    #[test]
+    fn validation() {
+        let settings: ScriptSettings = toml::from_str(r#"command = "date""#).unwrap();
+        assert_eq!(settings.update_rate, UpdateRate::from_secs(1));
+        assert!(settings.validate().is_ok());
+
+        let settings = ScriptSettings {
+            update_rate: UpdateRate::ZERO,
+            ..settings
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn output_arrives_without_blocking() {
+        let env = Rc::new(Environment::headless(100, 20));
+        env.fonts.add_font_by_name("DejaVu Sans").unwrap();
+
+        let mut settings = settings("sh", &["-c", "sleep 0.2; echo hello"]);
+        settings.text_settings.size = 16.0;
+        let mut script = Script::new(None, settings).unwrap();
+        script.bind(env).unwrap();
+
+        let start = Instant::now();
+        script.init().unwrap();
+        script.prepare().unwrap();
+        assert!(start.elapsed() < Duration::from_millis(200));
+        assert_eq!(script.data().width, 0);
+
+        while script.data().width == 0 {
+            assert!(start.elapsed() < Duration::from_secs(5), "no output");
+            thread::sleep(Duration::from_millis(20));
+            script.prepare().unwrap();
+        }
+    }
+}
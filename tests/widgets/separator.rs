@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{
+        util::Color,
+        widgets::{
+            containers::row::{Alignment, Row, RowSettings},
+            separator::{Separator, SeparatorSettings},
+            Widget, WidgetNew,
+        },
+    };
+
+    use crate::widgets::fixed::Fixed;
+
+    #[test]
+    fn from_config() {
+        let settings: SeparatorSettings = toml::from_str(
+            r##"thickness = 2
+color = "#ff0000"
+height = 12"##,
+        )
+        .unwrap();
+        assert_eq!(settings.color, Color::RED);
+
+        let separator = Separator::new(None, settings).unwrap();
+        separator.prepare().unwrap();
+        assert_eq!(separator.data().width, 2);
+        assert_eq!(separator.data().height, 12);
+    }
+
+    #[test]
+    fn centered_in_row() {
+        let mut row = Row::new(
+            None,
+            RowSettings {
+                alignment: Alignment::GrowthHorizontalRight(5),
+                ..RowSettings::default()
+            },
+        )
+        .unwrap();
+        row.add_widget(Fixed::new(10, 30));
+        row.add_widget(Box::new(
+            Separator::new(
+                None,
+                SeparatorSettings {
+                    height: 10,
+                    ..SeparatorSettings::default()
+                },
+            )
+            .unwrap(),
+        ));
+        row.add_widget(Fixed::new(10, 20));
+        row.prepare().unwrap();
+
+        let positions: Vec<_> = row
+            .widgets_mut()
+            .iter()
+            .map(|w| w.data().position)
+            .map(|p| (p.0, p.1))
+            .collect();
+        assert_eq!(positions, vec![(0, 0), (15, 10), (21, 0)]);
+        assert_eq!(row.data().height, 30);
+    }
+}
@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    use capybar::widgets::{
+        spacer::{Spacer, SpacerSettings},
+        Widget, WidgetNew,
+    };
+
+    #[test]
+    fn reserves_width() {
+        let spacer = Spacer::new(None, SpacerSettings::default()).unwrap();
+        spacer.prepare().unwrap();
+        assert_eq!(spacer.data().width, 10);
+        assert_eq!(spacer.data().height, 0);
+    }
+
+    #[test]
+    fn from_config() {
+        let settings: SpacerSettings = toml::from_str("width = 25\nmargin = [5, 5, 0, 0]").unwrap();
+        let spacer = Spacer::new(None, settings).unwrap();
+        spacer.prepare().unwrap();
+        assert_eq!(spacer.data().width, 35);
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{util::Color, widgets::temperature::TemperatureSettings};
+
+    const SENSORS: [(&str, Option<f32>); 3] = [
+        ("acpitz temp1", None),
+        ("coretemp Package id 0", Some(54.0)),
+        ("amdgpu edge", Some(61.0)),
+    ];
+
+    #[test]
+    fn first_sensor_with_reading() {
+        let settings = TemperatureSettings::default();
+        assert_eq!(settings.select(SENSORS), Some(54.0));
+    }
+
+    #[test]
+    fn sensor_by_label() {
+        let settings = TemperatureSettings {
+            component: Some("AMDGPU".to_string()),
+            ..TemperatureSettings::default()
+        };
+        assert_eq!(settings.select(SENSORS), Some(61.0));
+    }
+
+    #[test]
+    fn missing_sensor() {
+        let settings = TemperatureSettings {
+            component: Some("nvme".to_string()),
+            ..TemperatureSettings::default()
+        };
+        assert_eq!(settings.select(SENSORS), None);
+
+        let settings = TemperatureSettings {
+            component: Some("acpitz".to_string()),
+            ..TemperatureSettings::default()
+        };
+        assert_eq!(settings.select(SENSORS), None);
+    }
+
+    #[test]
+    fn threshold_colors() {
+        let settings: TemperatureSettings = toml::from_str(
+            r##"
+font_color = "#ffffff"
+warning_threshold = 60
+warning_color = "#00ff00"
+critical_threshold = 80
+"##,
+        )
+        .unwrap();
+
+        assert_eq!(settings.color(59.9), Color::WHITE);
+        assert_eq!(settings.color(60.0), Color::GREEN);
+        assert_eq!(settings.color(80.0), Color::RED);
+    }
+
+    #[test]
+    fn number_format() {
+        let settings = TemperatureSettings::default();
+        assert_eq!(settings.number_format.format(54.4), "54°C");
+
+        let settings: TemperatureSettings =
+            toml::from_str(r#"number_format = { decimals = 1, unit = "C", unit_space = true }"#)
+                .unwrap();
+        assert_eq!(settings.number_format.format(54.44), "54.4 C");
+    }
+}
@@ -0,0 +1,407 @@
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use capybar::{
+        root::Environment,
+        util::{fonts::FontsMap, Color},
+        widgets::{
+            icon_text::{IconText, IconTextSettings},
+            text::{font_runs, Text, TextAlign, TextSettings, ELLIPSIS},
+            Margin, Position, Style, Widget, WidgetData, WidgetNew,
+        },
+    };
+
+    fn text(width: usize, horizontal_align: TextAlign) -> Text {
+        Text::new(
+            None,
+            TextSettings {
+                default_data: WidgetData::with_width(width),
+                horizontal_align,
+                ..TextSettings::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn aligned_text_keeps_width() {
+        for align in [TextAlign::Center, TextAlign::Right] {
+            let text = text(200, align);
+            text.init().unwrap();
+            assert_eq!(text.data().width, 200);
+        }
+    }
+
+    #[test]
+    fn left_aligned_text_fits_content() {
+        let text = text(200, TextAlign::Left);
+        text.init().unwrap();
+        assert_eq!(text.data().width, 0);
+    }
+
+    #[test]
+    fn alignment_without_width() {
+        let text = text(0, TextAlign::Center);
+        text.init().unwrap();
+        assert_eq!(text.data().width, 0);
+    }
+
+    #[test]
+    fn align_from_config() {
+        let settings: TextSettings = toml::from_str(r#"horizontal_align = "center""#).unwrap();
+        assert_eq!(settings.horizontal_align, TextAlign::Center);
+
+        let settings: TextSettings = toml::from_str("").unwrap();
+        assert_eq!(settings.horizontal_align, TextAlign::Left);
+    }
+
+    #[test]
+    fn change_color_keeps_layout() {
+        let mut text = text(200, TextAlign::Center);
+        text.init().unwrap();
+
+        text.change_color(Color::RED);
+        assert_eq!(text.color(), Color::RED);
+        assert_eq!(text.data().width, 200);
+    }
+
+    #[test]
+    fn icon_text_colors() {
+        let mut icon_text = IconText::new(None, IconTextSettings::default()).unwrap();
+
+        icon_text.change_icon_color(Color::RED);
+        icon_text.change_text_color(Color::BLUE);
+        assert_eq!(icon_text.icon_color(), Color::RED);
+        assert_eq!(icon_text.text_color(), Color::BLUE);
+
+        icon_text.change_color(Color::GREEN);
+        assert_eq!(icon_text.icon_color(), Color::GREEN);
+        assert_eq!(icon_text.text_color(), Color::GREEN);
+    }
+
+    #[test]
+    fn dirty_on_change() {
+        let mut text = text(0, TextAlign::Left);
+        assert!(!text.data().dirty);
+
+        text.change_text("");
+        assert!(!text.data().dirty);
+
+        text.change_text("12:00");
+        assert!(text.data().dirty);
+
+        text.data_mut().dirty = false;
+        text.change_text("12:00");
+        assert!(!text.data().dirty);
+
+        text.change_color(Color::RED);
+        assert!(text.data().dirty);
+
+        text.data_mut().dirty = false;
+        text.change_color(Color::RED);
+        assert!(!text.data().dirty);
+    }
+
+    #[test]
+    fn max_width_limits_width() {
+        let text = Text::new(
+            None,
+            TextSettings {
+                default_data: WidgetData::with_width(200),
+                horizontal_align: TextAlign::Center,
+                max_width: 120,
+                ..TextSettings::default()
+            },
+        )
+        .unwrap();
+        text.init().unwrap();
+        assert_eq!(text.data().width, 120);
+    }
+
+    #[test]
+    fn fitting_text_does_not_scroll() {
+        let text = Text::new(
+            None,
+            TextSettings {
+                max_width: 120,
+                scroll: true,
+                ..TextSettings::default()
+            },
+        )
+        .unwrap();
+        text.init().unwrap();
+        assert!(!text.is_scrolling());
+        assert_eq!(text.next_update(), None);
+    }
+
+    #[test]
+    fn scroll_from_config() {
+        let settings: TextSettings = toml::from_str("scroll = true\nmax_width = 120").unwrap();
+        assert!(settings.scroll);
+        assert_eq!(settings.max_width, 120);
+
+        let settings: TextSettings = toml::from_str("").unwrap();
+        assert!(!settings.scroll);
+        assert_eq!(settings.max_width, 0);
+    }
+
+    #[test]
+    fn padding_from_config() {
+        let settings: TextSettings = toml::from_str("style = { padding = [3, 4, 1, 2] }").unwrap();
+        let text = Text::new(None, settings).unwrap();
+        text.prepare().unwrap();
+
+        assert_eq!(text.data().width, 7);
+        assert_eq!(text.data().height, 3);
+    }
+
+    #[test]
+    fn fallback_font_runs() {
+        let fonts = FontsMap::new();
+        let mono = fonts.add_font_by_name("DejaVu Sans Mono").unwrap();
+        let sans = fonts.add_font_by_name("DejaVu Sans").unwrap();
+        let fonts = fonts.fonts_vec();
+        // Pick a glyph by coverage, so the test doesn't depend on which fonts fontconfig resolves
+        let only_sans = ('\u{2100}'..='\u{22ff}')
+            .find(|&c| {
+                fonts[mono].lookup_glyph_index(c) == 0 && fonts[sans].lookup_glyph_index(c) != 0
+            })
+            .expect("DejaVu Sans should cover a glyph missing from DejaVu Sans Mono");
+        let text = format!("ab {only_sans}{only_sans} c");
+        let glyphs = format!("{only_sans}{only_sans} ");
+
+        assert_eq!(
+            font_runs(&fonts, &text, mono, &[sans]),
+            vec![("ab ", mono), (glyphs.as_str(), sans), ("c", mono)]
+        );
+        let text = format!("a{only_sans}");
+        assert_eq!(
+            font_runs(&fonts, &text, mono, &[]),
+            vec![(text.as_str(), mono)]
+        );
+        assert_eq!(font_runs(&fonts, "", mono, &[sans]), vec![]);
+        assert_eq!(font_runs(&fonts, " ", mono, &[sans]), vec![(" ", mono)]);
+    }
+
+    #[test]
+    fn fonts_of_environment() {
+        let width = |font: &str| {
+            let env = Rc::new(Environment::headless(1, 1));
+            assert_eq!(env.fonts.add_font_by_name(font).unwrap(), 0);
+
+            let mut text = Text::new(
+                None,
+                TextSettings {
+                    text: "www".to_string(),
+                    size: 16.0,
+                    ..TextSettings::default()
+                },
+            )
+            .unwrap();
+            text.bind(env).unwrap();
+            text.prepare().unwrap();
+            let width = text.data().width;
+            width
+        };
+
+        // Both fonts get id 0 in their own environment
+        let sans = width("DejaVu Sans");
+        let mono = width("DejaVu Sans Mono");
+        assert!(sans > 0 && mono > 0);
+        assert_ne!(sans, mono);
+    }
+
+    fn ellipsized(text: &str, ellipsis: bool) -> Text {
+        let env = Rc::new(Environment::headless(1, 1));
+        env.fonts.add_font_by_name("DejaVu Sans").unwrap();
+
+        let text = Text::new(
+            Some(env),
+            TextSettings {
+                text: text.to_string(),
+                size: 16.0,
+                max_width: 60,
+                ellipsis,
+                ..TextSettings::default()
+            },
+        )
+        .unwrap();
+        text.prepare().unwrap();
+        text
+    }
+
+    #[test]
+    fn long_text_is_ellipsized() {
+        let text = ellipsized("A very long window title", true);
+        let shown = text.get_text();
+        assert!(shown.ends_with(ELLIPSIS));
+        assert!(shown.len() < "A very long window title".len());
+        assert!(text.data().width <= 60);
+        assert!(text.data().width > 0);
+    }
+
+    #[test]
+    fn fitting_text_is_not_ellipsized() {
+        assert_eq!(ellipsized("Hi", true).get_text(), "Hi");
+    }
+
+    #[test]
+    fn ellipsis_disabled_clips() {
+        let text = ellipsized("A very long window title", false);
+        assert_eq!(text.get_text(), "A very long window title");
+        assert_eq!(text.data().width, 60);
+    }
+
+    #[test]
+    fn decorations_span_text() {
+        let env = Rc::new(Environment::headless(200, 40));
+        env.fonts.add_font_by_name("DejaVu Sans").unwrap();
+
+        let text = Text::new(
+            Some(env.clone()),
+            TextSettings {
+                text: "Hello".to_string(),
+                size: 16.0,
+                underline: true,
+                strikethrough: true,
+                decoration_color: Some(Color::RED),
+                ..TextSettings::default()
+            },
+        )
+        .unwrap();
+        text.prepare().unwrap();
+
+        let decorations = text.decorations();
+        assert_eq!(decorations.len(), 2);
+        let (start, end, underline) = decorations[0];
+        let (_, _, strikethrough) = decorations[1];
+        assert!(start < end && end <= text.data().width);
+        assert!(strikethrough < underline);
+
+        text.draw().unwrap();
+        let mut drawer = env.drawer.borrow_mut();
+        let canvas = drawer.canvas().unwrap();
+        assert_eq!(
+            canvas.pixel((start + end) / 2, underline),
+            Some([0, 0, 255, 255])
+        );
+        assert_eq!(canvas.pixel(end + 1, underline), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn decoration_thickness_scales() {
+        let settings = |size, decoration_thickness| TextSettings {
+            size,
+            decoration_thickness,
+            ..TextSettings::default()
+        };
+        assert_eq!(settings(12.0, 0).decoration_thickness(), 1);
+        assert_eq!(settings(32.0, 0).decoration_thickness(), 2);
+        assert_eq!(settings(12.0, 3).decoration_thickness(), 3);
+    }
+
+    #[test]
+    fn icon_font_must_be_loaded() {
+        let env = Rc::new(Environment::headless(1, 1));
+        env.fonts.add_font_by_name("DejaVu Sans").unwrap();
+
+        let mut icon_text = IconText::new(Some(env.clone()), IconTextSettings::default()).unwrap();
+        icon_text.change_icon("x");
+        assert!(icon_text.init().is_err());
+
+        let icon_text = IconText::new(
+            Some(env),
+            IconTextSettings {
+                icon_fontid: 0,
+                ..IconTextSettings::default()
+            },
+        )
+        .unwrap();
+        assert!(icon_text.init().is_ok());
+    }
+
+    #[test]
+    fn icon_text_layout() {
+        let env = Rc::new(Environment::headless(200, 40));
+        env.fonts.add_font_by_name("DejaVu Sans").unwrap();
+
+        let mut icon_text = IconText::new(
+            Some(env),
+            IconTextSettings {
+                default_data: WidgetData::with_position(Position::new(10, 5)),
+                style: Style {
+                    margin: Margin {
+                        left: 3,
+                        right: 0,
+                        up: 4,
+                        down: 0,
+                    },
+                    ..Style::default()
+                },
+                icon_fontid: 0,
+                ..IconTextSettings::default()
+            },
+        )
+        .unwrap();
+        icon_text.change_icon("x");
+        icon_text.change_text("abc");
+        icon_text.init().unwrap();
+        icon_text.prepare().unwrap();
+
+        // Icon and text are placed next to each other, inner margins are part of their sizes
+        let icon = *icon_text.icon().data();
+        let text = *icon_text.text().data();
+        assert_eq!((icon.position.0, icon.position.1), (10 + 3, 5 + 4));
+        assert_eq!(
+            (text.position.0, text.position.1),
+            (10 + 3 + icon.width, 5 + 4)
+        );
+        assert_eq!(icon_text.data().width, 3 + icon.width + text.width);
+
+        // Drawing insets both by their own margins once
+        for _ in 0..3 {
+            icon_text.draw().unwrap();
+            let icon_position = icon_text.icon().data().position;
+            let text_position = icon_text.text().data().position;
+            assert_eq!((icon_position.0, icon_position.1), (10 + 3 + 2, 5 + 4));
+            assert_eq!(
+                (text_position.0, text_position.1),
+                (10 + 3 + icon.width + 2, 5 + 4 + 1)
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_draws_keep_position() {
+        let env = Rc::new(Environment::headless(200, 40));
+        env.fonts.add_font_by_name("DejaVu Sans").unwrap();
+
+        let mut text = Text::new(
+            Some(env),
+            TextSettings {
+                default_data: WidgetData::with_position(Position::new(10, 5)),
+                style: Style {
+                    margin: Margin::uniform(3),
+                    padding: Margin::symmetric(4, 1),
+                    ..Style::default()
+                },
+                ..TextSettings::default()
+            },
+        )
+        .unwrap();
+        text.change_text("abc");
+        text.init().unwrap();
+
+        for _ in 0..10 {
+            text.draw().unwrap();
+            assert_eq!(text.data().position, Position::new(10 + 3 + 4, 5 + 3 + 1));
+        }
+
+        // Widget placed anew by it's parent is inset from the new position
+        text.data_mut().position = Position::new(50, 0);
+        text.draw().unwrap();
+        text.draw().unwrap();
+        assert_eq!(text.data().position, Position::new(50 + 3 + 4, 3 + 1));
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(all(test, feature = "tray"))]
+mod tests {
+    use capybar::{
+        services::tray::{parse_service, TrayIcon, TrayItem, DEFAULT_ITEM_PATH},
+        util::Color,
+        widgets::tray::TraySettings,
+    };
+
+    fn item(title: &str, id: &str) -> TrayItem {
+        TrayItem {
+            destination: ":1.42".to_string(),
+            path: DEFAULT_ITEM_PATH.to_string(),
+            id: id.to_string(),
+            title: title.to_string(),
+            icon_name: String::new(),
+            icons: Vec::new(),
+        }
+    }
+
+    fn icon(size: usize) -> TrayIcon {
+        TrayIcon {
+            width: size,
+            height: size,
+            argb: vec![0xff; size * size * 4],
+        }
+    }
+
+    #[test]
+    fn service_with_bus_name() {
+        assert_eq!(
+            parse_service("org.example.App", Some(":1.7")),
+            Some(("org.example.App".to_string(), DEFAULT_ITEM_PATH.to_string()))
+        );
+    }
+
+    #[test]
+    fn service_with_bus_name_and_path() {
+        assert_eq!(
+            parse_service(":1.7/org/example/Item", None),
+            Some((":1.7".to_string(), "/org/example/Item".to_string()))
+        );
+    }
+
+    #[test]
+    fn service_with_path_only() {
+        assert_eq!(parse_service("/org/example/Item", None), None);
+        assert_eq!(parse_service("", Some(":1.7")), None);
+    }
+
+    #[test]
+    fn best_icon() {
+        let icons = [icon(16), icon(32), icon(22)];
+        assert_eq!(TrayIcon::best(&icons, 20).unwrap().height, 22);
+        assert_eq!(TrayIcon::best(&icons, 48).unwrap().height, 32);
+        assert_eq!(TrayIcon::best(&icons, 8).unwrap().height, 16);
+        assert!(TrayIcon::best(&[], 20).is_none());
+    }
+
+    #[test]
+    fn broken_icons_skipped() {
+        let broken = TrayIcon {
+            width: 24,
+            height: 24,
+            argb: vec![0; 10],
+        };
+        assert!(TrayIcon::best(&[broken], 24).is_none());
+    }
+
+    #[test]
+    fn icon_pixels_are_argb() {
+        let icon = TrayIcon {
+            width: 1,
+            height: 1,
+            argb: vec![0x80, 0x10, 0x20, 0x30],
+        };
+        assert_eq!(
+            icon.pixel(0, 0),
+            Some(Color::from_rgba(0x10, 0x20, 0x30, 0x80))
+        );
+        assert_eq!(icon.pixel(1, 0), None);
+    }
+
+    #[test]
+    fn click_methods() {
+        assert_eq!(TrayItem::method_for(0x110), Some("Activate"));
+        assert_eq!(TrayItem::method_for(0x111), Some("ContextMenu"));
+        assert_eq!(TrayItem::method_for(0x112), Some("SecondaryActivate"));
+        assert_eq!(TrayItem::method_for(0x113), None);
+    }
+
+    #[test]
+    fn fallback_label() {
+        let settings = TraySettings::default();
+        assert_eq!(settings.label(&item("network", "nm-applet")).text, "N");
+        assert_eq!(settings.label(&item("", "discord")).text, "D");
+        assert_eq!(settings.label(&item("", "")).text, "?");
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use capybar::{
+        services::volume::{parse_pactl, parse_wpctl, VolumeState},
+        widgets::volume::VolumeSettings,
+    };
+
+    fn state(volume: u32, muted: bool) -> VolumeState {
+        VolumeState { volume, muted }
+    }
+
+    #[test]
+    fn wpctl() {
+        assert_eq!(parse_wpctl("Volume: 0.45\n"), Some(state(45, false)));
+        assert_eq!(parse_wpctl("Volume: 0.30 [MUTED]\n"), Some(state(30, true)));
+        assert_eq!(parse_wpctl("Volume: 1.50\n"), Some(state(150, false)));
+        assert_eq!(parse_wpctl("Error: no sink\n"), None);
+    }
+
+    #[test]
+    fn pactl() {
+        let volume = "Volume: front-left: 29491 /  45% / -20.79 dB,   \
+                      front-right: 29491 /  45% / -20.79 dB\n        \
+                      balance 0.00\n";
+        assert_eq!(parse_pactl(volume, "Mute: no\n"), Some(state(45, false)));
+        assert_eq!(parse_pactl(volume, "Mute: yes\n"), Some(state(45, true)));
+        assert_eq!(parse_pactl(volume, "garbage"), None);
+        assert_eq!(parse_pactl("", "Mute: no"), None);
+    }
+
+    #[test]
+    fn icons() {
+        let settings = VolumeSettings {
+            icons: vec!["low".to_string(), "mid".to_string(), "high".to_string()],
+            muted_icon: "muted".to_string(),
+            ..VolumeSettings::default()
+        };
+
+        assert_eq!(settings.icon(&state(0, false)), "low");
+        assert_eq!(settings.icon(&state(50, false)), "mid");
+        assert_eq!(settings.icon(&state(100, false)), "high");
+        assert_eq!(settings.icon(&state(150, false)), "high");
+        assert_eq!(settings.icon(&state(150, true)), "muted");
+    }
+
+    #[test]
+    fn no_icons() {
+        let settings = VolumeSettings {
+            icons: Vec::new(),
+            ..VolumeSettings::default()
+        };
+        assert_eq!(settings.icon(&state(50, false)), settings.muted_icon);
+    }
+}
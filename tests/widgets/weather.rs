@@ -0,0 +1,86 @@
+#[cfg(all(test, feature = "weather"))]
+mod tests {
+    use capybar::{
+        services::weather::{parse, Condition, WeatherState},
+        util::UpdateRate,
+        widgets::weather::{Units, WeatherSettings},
+    };
+
+    #[test]
+    fn wttr_response() {
+        let body =
+            r#"{"current_condition": [{"temp_C": "-3", "temp_F": "27", "weatherCode": "338"}]}"#;
+        assert_eq!(
+            parse(body).unwrap(),
+            WeatherState {
+                temperature: -3.0,
+                condition: Condition::Snow,
+            }
+        );
+    }
+
+    #[test]
+    fn open_meteo_response() {
+        let body = r#"{
+            "current_units": {"temperature_2m": "°C", "weather_code": "wmo code"},
+            "current": {"temperature_2m": 21.4, "weather_code": 2}
+        }"#;
+        assert_eq!(
+            parse(body).unwrap(),
+            WeatherState {
+                temperature: 21.4,
+                condition: Condition::PartlyCloudy,
+            }
+        );
+
+        let body = r#"{
+            "current_weather_units": {"temperature": "°F"},
+            "current_weather": {"temperature": 50.0, "weathercode": 95}
+        }"#;
+        assert_eq!(
+            parse(body).unwrap(),
+            WeatherState {
+                temperature: 10.0,
+                condition: Condition::Thunderstorm,
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_response() {
+        assert!(parse("Unknown location").is_err());
+        assert!(parse(r#"{"current": {}}"#).is_err());
+    }
+
+    #[test]
+    fn text_and_icon() {
+        let state = WeatherState {
+            temperature: -0.3,
+            condition: Condition::Clear,
+        };
+
+        let settings = WeatherSettings::default();
+        assert_eq!(settings.text(Some(&state)), "0°C");
+        assert_ne!(settings.icon(Some(&state)), settings.unavailable_icon);
+        assert_eq!(settings.text(None), "");
+        assert_eq!(settings.icon(None), settings.unavailable_icon);
+
+        let settings = WeatherSettings {
+            format: "{condition} {temperature}{unit}".to_string(),
+            units: Units::Imperial,
+            ..WeatherSettings::default()
+        };
+        assert_eq!(settings.text(Some(&state)), "Clear 31°F");
+    }
+
+    #[test]
+    fn validation() {
+        assert!(WeatherSettings::default().validate().is_ok());
+
+        let settings = WeatherSettings {
+            update_rate: UpdateRate::ZERO,
+            ..WeatherSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(all(test, feature = "hyprland"))]
+mod tests {
+    use capybar::widgets::{
+        window_title::{WindowTitle, WindowTitleSettings},
+        Widget, WidgetNew,
+    };
+
+    #[test]
+    fn empty_title_fallback() {
+        let settings = WindowTitleSettings {
+            empty_title: "Desktop".to_string(),
+            ..WindowTitleSettings::default()
+        };
+        assert_eq!(settings.title("Firefox"), "Firefox");
+        assert_eq!(settings.title(""), "Desktop");
+        assert_eq!(settings.title("  "), "Desktop");
+    }
+
+    #[test]
+    fn from_config() {
+        let settings: WindowTitleSettings = toml::from_str(
+            r#"
+            max_width = 300
+            scroll = true
+            empty_title = "Desktop"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(settings.text_settings.max_width, 300);
+        assert!(settings.text_settings.scroll);
+        assert_eq!(settings.empty_title, "Desktop");
+    }
+
+    #[test]
+    fn init_without_env() {
+        let title = WindowTitle::new(None, WindowTitleSettings::default()).unwrap();
+        assert!(title.init().is_err());
+    }
+}
@@ -0,0 +1,34 @@
+#[cfg(all(test, feature = "hyprland"))]
+mod tests {
+    use capybar::{
+        util::Color,
+        widgets::{workspaces::WorkspacesSettings, Background, Style},
+    };
+
+    fn settings(show_names: bool) -> WorkspacesSettings {
+        WorkspacesSettings {
+            active_style: Style {
+                background: Some(Background::Solid(Color::from_hex(0xff0000ff))),
+                ..Style::default()
+            },
+            show_names,
+            ..WorkspacesSettings::default()
+        }
+    }
+
+    #[test]
+    fn label_text() {
+        assert_eq!(settings(false).label(3, "code", false).text, "3");
+        assert_eq!(settings(true).label(3, "code", false).text, "code");
+    }
+
+    #[test]
+    fn active_label_style() {
+        let settings = settings(false);
+        assert_eq!(
+            settings.label(1, "1", true).style.background,
+            Some(Background::Solid(Color::from_hex(0xff0000ff)))
+        );
+        assert_eq!(settings.label(2, "2", false).style.background, None);
+    }
+}